@@ -21,10 +21,10 @@ use sc_chain_spec::ChainSpecExtension;
 use sp_core::{Pair, Public, crypto::UncheckedInto, sr25519};
 use serde::{Serialize, Deserialize};
 use node_runtime::{
-	AuthorityDiscoveryConfig, BabeConfig, BalancesConfig, CouncilConfig,
+	AssetsConfig, AuthorityDiscoveryConfig, BabeConfig, BalancesConfig, CouncilConfig,
 	DemocracyConfig, GrandpaConfig, ImOnlineConfig, SessionConfig, SessionKeys, StakerStatus,
 	StakingConfig, ElectionsConfig, IndicesConfig, SocietyConfig, SudoConfig, SystemConfig,
-	TechnicalCommitteeConfig, wasm_binary_unwrap, MAX_NOMINATIONS,
+	TechnicalCommitteeConfig, VestingConfig, wasm_binary_unwrap, MAX_NOMINATIONS,
 };
 use node_runtime::Block;
 use node_runtime::constants::currency::*;
@@ -37,7 +37,7 @@ use pallet_im_online::sr25519::{AuthorityId as ImOnlineId};
 use sp_authority_discovery::AuthorityId as AuthorityDiscoveryId;
 use sp_runtime::{Perbill, traits::{Verify, IdentifyAccount}};
 
-pub use node_primitives::{AccountId, Balance, Signature};
+pub use node_primitives::{AccountId, AssetId, Balance, BlockNumber, Signature};
 pub use node_runtime::GenesisConfig;
 
 type AccountPublic = <Signature as Verify>::Signer;
@@ -149,7 +149,9 @@ fn testnet_config_genesis() -> GenesisConfig {
 		initial_authorities,
 		vec![],
 		root_key,
-		Some(endowed_accounts)
+		Some(endowed_accounts),
+		vec![],
+		vec![],
 	)
 }
 
@@ -203,6 +205,32 @@ pub fn authority_keys_from_seed(seed: &str) -> (
 	)
 }
 
+/// A vesting schedule set at genesis: the account, the block at which vesting begins, the
+/// number of blocks over which the locked amount vests linearly, and the amount that stays
+/// liquid from the start.
+pub type GenesisVestingSchedule = (AccountId, BlockNumber, BlockNumber, Balance);
+
+/// A fungible asset created at genesis, together with its metadata and initial holders.
+#[derive(Clone)]
+pub struct GenesisAsset {
+	/// The asset identifier.
+	pub id: AssetId,
+	/// The owner, who is also the initial issuer, admin and freezer of the asset.
+	pub owner: AccountId,
+	/// Whether holding the asset is enough to keep an account alive.
+	pub is_sufficient: bool,
+	/// The minimum balance an account must hold of the asset.
+	pub min_balance: Balance,
+	/// The asset name.
+	pub name: Vec<u8>,
+	/// The asset ticker symbol.
+	pub symbol: Vec<u8>,
+	/// The number of decimals used to represent one unit.
+	pub decimals: u8,
+	/// Accounts endowed with the asset and their balances.
+	pub balances: Vec<(AccountId, Balance)>,
+}
+
 /// Helper function to create GenesisConfig for testing
 pub fn testnet_genesis(
 	initial_authorities: Vec<(
@@ -216,6 +244,8 @@ pub fn testnet_genesis(
 	initial_nominators: Vec<AccountId>,
 	root_key: AccountId,
 	endowed_accounts: Option<Vec<AccountId>>,
+	vesting_schedules: Vec<GenesisVestingSchedule>,
+	initial_assets: Vec<GenesisAsset>,
 ) -> GenesisConfig {
 	let mut endowed_accounts: Vec<AccountId> = endowed_accounts.unwrap_or_else(|| {
 		vec![
@@ -338,6 +368,21 @@ pub fn testnet_genesis(
 			max_members: 999,
 		},
 		transaction_storage: Default::default(),
+		migrations: Default::default(),
+		vesting: VestingConfig {
+			vesting: vesting_schedules,
+		},
+		assets: AssetsConfig {
+			assets: initial_assets.iter()
+				.map(|a| (a.id, a.owner.clone(), a.is_sufficient, a.min_balance))
+				.collect(),
+			metadata: initial_assets.iter()
+				.map(|a| (a.id, a.name.clone(), a.symbol.clone(), a.decimals))
+				.collect(),
+			accounts: initial_assets.iter()
+				.flat_map(|a| a.balances.iter().map(move |(who, amount)| (a.id, who.clone(), *amount)))
+				.collect(),
+		},
 	}
 }
 
@@ -349,6 +394,8 @@ fn development_config_genesis() -> GenesisConfig {
 		vec![],
 		get_account_id_from_seed::<sr25519::Public>("Alice"),
 		None,
+		vec![],
+		vec![],
 	)
 }
 
@@ -376,6 +423,8 @@ fn local_testnet_genesis() -> GenesisConfig {
 		vec![],
 		get_account_id_from_seed::<sr25519::Public>("Alice"),
 		None,
+		vec![],
+		vec![],
 	)
 }
 
@@ -409,6 +458,8 @@ pub(crate) mod tests {
 			vec![],
 			get_account_id_from_seed::<sr25519::Public>("Alice"),
 			None,
+			vec![],
+			vec![],
 		)
 	}
 
@@ -449,7 +500,7 @@ pub(crate) mod tests {
 			integration_test_config_with_two_authorities(),
 			|config| {
 				let NewFullBase { task_manager, client, network, transaction_pool, .. }
-					= new_full_base(config,|_, _| ())?;
+					= new_full_base(config, None, |_, _| ())?;
 				Ok(sc_service_test::TestNetComponents::new(task_manager, client, network, transaction_pool))
 			},
 			|config| {
@@ -469,6 +520,46 @@ pub(crate) mod tests {
 		local_testnet_config().build_storage().unwrap();
 	}
 
+	#[test]
+	fn test_create_genesis_with_vesting_schedules() {
+		let alice = get_account_id_from_seed::<sr25519::Public>("Alice");
+		testnet_genesis(
+			vec![
+				authority_keys_from_seed("Alice"),
+			],
+			vec![],
+			alice.clone(),
+			None,
+			vec![(alice, 0, 100, 1_000 * DOLLARS)],
+			vec![],
+		).build_storage().unwrap();
+	}
+
+	#[test]
+	fn test_create_genesis_with_assets() {
+		let alice = get_account_id_from_seed::<sr25519::Public>("Alice");
+		let bob = get_account_id_from_seed::<sr25519::Public>("Bob");
+		testnet_genesis(
+			vec![
+				authority_keys_from_seed("Alice"),
+			],
+			vec![],
+			alice.clone(),
+			None,
+			vec![],
+			vec![GenesisAsset {
+				id: 1,
+				owner: alice.clone(),
+				is_sufficient: false,
+				min_balance: 1,
+				name: b"Trust Dollar".to_vec(),
+				symbol: b"TUSD".to_vec(),
+				decimals: 12,
+				balances: vec![(alice, 1_000 * DOLLARS), (bob, 500 * DOLLARS)],
+			}],
+		).build_storage().unwrap();
+	}
+
 	#[test]
 	fn test_staging_test_net_chain_spec() {
 		testnet_config().build_storage().unwrap();
@@ -14,6 +14,7 @@
 // You should have received a copy of the GNU General Public License
 // along with this program. If not, see <https://www.gnu.org/licenses/>.
 
+use node_primitives::AccountId;
 use sc_cli::{RunCmd, KeySubcommand, SignCmd, VanityCmd, VerifyCmd};
 use structopt::StructOpt;
 
@@ -26,6 +27,35 @@ pub struct Cli {
 	#[allow(missing_docs)]
 	#[structopt(flatten)]
 	pub run: RunCmd,
+	#[allow(missing_docs)]
+	#[structopt(flatten)]
+	pub payout: PayoutParams,
+}
+
+/// Parameters of the validator payout service.
+#[derive(Debug, StructOpt)]
+pub struct PayoutParams {
+	/// Submit `payout_stakers` for this validator stash at the end of every era, signed with the
+	/// `payo` key in the keystore. May be given more than once.
+	#[structopt(long = "payout-validator", value_name = "SS58")]
+	pub payout_validators: Vec<AccountId>,
+
+	/// How many times an unclaimed payout is submitted again before it is given up.
+	#[structopt(long = "payout-max-retries", value_name = "COUNT", default_value = "5")]
+	pub payout_max_retries: u32,
+}
+
+impl PayoutParams {
+	/// The configuration of the payout service, if it is enabled.
+	pub fn config(&self) -> Option<crate::payout::PayoutConfig> {
+		if self.payout_validators.is_empty() {
+			return None
+		}
+		Some(crate::payout::PayoutConfig {
+			validators: self.payout_validators.clone(),
+			max_retries: self.payout_max_retries,
+		})
+	}
 }
 
 /// Possible subcommands of the main binary.
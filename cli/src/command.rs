@@ -70,10 +70,11 @@ pub fn run() -> Result<()> {
 	match &cli.subcommand {
 		None => {
 			let runner = cli.create_runner(&cli.run)?;
+			let payout = cli.payout.config();
 			runner.run_node_until_exit(|config| async move {
 				match config.role {
 					Role::Light => service::new_light(config),
-					_ => service::new_full(config),
+					_ => service::new_full(config, payout),
 				}.map_err(sc_cli::Error::Service)
 			})
 		}
@@ -102,7 +103,9 @@ pub fn run() -> Result<()> {
 		},
 		Some(Subcommand::CheckBlock(cmd)) => {
 			let runner = cli.create_runner(cmd)?;
-			runner.async_run(|config| {
+			runner.async_run(|mut config| {
+				// Imports blocks, see `new_full_base`.
+				config.offchain_worker.indexing_enabled = true;
 				let PartialComponents { client, task_manager, import_queue, ..}
 					= new_partial(&config)?;
 				Ok((cmd.run(client, import_queue), task_manager))
@@ -126,7 +129,9 @@ pub fn run() -> Result<()> {
 		},
 		Some(Subcommand::ImportBlocks(cmd)) => {
 			let runner = cli.create_runner(cmd)?;
-			runner.async_run(|config| {
+			runner.async_run(|mut config| {
+				// Imports blocks, see `new_full_base`.
+				config.offchain_worker.indexing_enabled = true;
 				let PartialComponents { client, task_manager, import_queue, ..}
 					= new_partial(&config)?;
 				Ok((cmd.run(client, import_queue), task_manager))
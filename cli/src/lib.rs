@@ -28,8 +28,9 @@
 
 pub mod chain_spec;
 
+pub mod payout;
 #[macro_use]
-mod service;
+pub mod service;
 #[cfg(feature = "browser")]
 mod browser;
 #[cfg(feature = "cli")]
@@ -0,0 +1,494 @@
+// Copyright 2019 TrustBase Network
+// This file is part of TrustBase library.
+//
+// The TrustBase library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Lesser General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// The TrustBase library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU Lesser General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Pays out the era rewards of a set of validators.
+//!
+//! Staking rewards of an era are only paid once someone calls `payout_stakers` for every
+//! validator, and are lost after `HistoryDepth` eras. Once enabled with `--payout-validator`,
+//! this service watches the active era on finalized blocks, and when it changes submits
+//! `payout_stakers` for the era that ended, for each configured validator, signed with the
+//! [`PAYOUT_KEY_TYPE`] key in the node's keystore.
+//!
+//! A payout that is still unclaimed [`RETRY_PERIOD`] blocks after it was submitted, as its
+//! transaction failed or expired, is submitted again, up to `--payout-max-retries` times.
+//! Payouts take the nonce that follows the transactions of the payout key in the pool, so that a
+//! retry does not replace a payout that is still pending.
+
+use std::sync::Arc;
+use codec::{Decode, Encode};
+use futures::prelude::*;
+use frame_support::storage::{StorageMap, StorageValue};
+use frame_system_rpc_runtime_api::AccountNonceApi;
+use node_primitives::{AccountId, Balance, Block, BlockNumber, Hash, Index};
+use node_runtime::{Call, Runtime, SignedExtra, SignedPayload, UncheckedExtrinsic};
+use prometheus_endpoint::{register, Counter, Gauge, PrometheusError, Registry, U64};
+use sc_client_api::{BlockchainEvents, StorageProvider, backend::Backend};
+use sp_api::{Core, ProvideRuntimeApi};
+use sp_blockchain::HeaderBackend;
+use sp_core::{crypto::KeyTypeId, sr25519, storage::StorageKey};
+use sp_keystore::{SyncCryptoStore, SyncCryptoStorePtr};
+use sp_runtime::{generic::{BlockId, Era}, traits::Header as HeaderT};
+use sp_transaction_pool::{InPoolTransaction, TransactionPool, TransactionSource};
+use std::convert::TryFrom;
+
+const LOG_TARGET: &str = "payout";
+
+/// The type of the keystore key that signs payouts. Insert one with
+/// `key insert --key-type payo --scheme sr25519`.
+pub const PAYOUT_KEY_TYPE: KeyTypeId = KeyTypeId(*b"payo");
+
+/// The number of blocks a payout transaction is valid for, and after which an unclaimed payout
+/// is submitted again.
+pub const RETRY_PERIOD: BlockNumber = 32;
+
+/// Configuration of the payout service.
+#[derive(Debug, Clone)]
+pub struct PayoutConfig {
+	/// The stashes of the validators to pay out.
+	pub validators: Vec<AccountId>,
+	/// How many times a payout is submitted again before it is given up.
+	pub max_retries: u32,
+}
+
+#[derive(Clone)]
+struct Metrics {
+	submitted: Counter<U64>,
+	failed: Counter<U64>,
+	claimed: Counter<U64>,
+	era: Gauge<U64>,
+}
+
+impl Metrics {
+	fn register(registry: &Registry) -> Result<Self, PrometheusError> {
+		Ok(Self {
+			submitted: register(
+				Counter::new("payout_submitted_total", "Number of payout_stakers transactions submitted")?,
+				registry,
+			)?,
+			failed: register(
+				Counter::new("payout_failed_total", "Number of payouts given up after all retries")?,
+				registry,
+			)?,
+			claimed: register(
+				Counter::new("payout_claimed_total", "Number of payouts seen claimed on chain")?,
+				registry,
+			)?,
+			era: register(
+				Gauge::new("payout_active_era", "The active era at the last finalized block")?,
+				registry,
+			)?,
+		})
+	}
+}
+
+/// A payout that is not claimed yet.
+struct Pending {
+	validator: AccountId,
+	era: pallet_staking::EraIndex,
+	submissions: u32,
+	next_submission: BlockNumber,
+}
+
+/// Run the payout service until the client's finality stream ends.
+pub async fn run<C, BE, P>(
+	client: Arc<C>,
+	transaction_pool: Arc<P>,
+	keystore: SyncCryptoStorePtr,
+	config: PayoutConfig,
+	registry: Option<Registry>,
+) where
+	C: ProvideRuntimeApi<Block> + StorageProvider<Block, BE> + BlockchainEvents<Block>
+		+ HeaderBackend<Block> + Send + Sync + 'static,
+	C::Api: AccountNonceApi<Block, AccountId, Index>,
+	BE: Backend<Block> + 'static,
+	P: TransactionPool<Block = Block> + 'static,
+{
+	let metrics = registry.and_then(|registry| Metrics::register(&registry)
+		.map_err(|e| log::warn!(target: LOG_TARGET, "Failed to register payout metrics: {:?}", e))
+		.ok()
+	);
+	let signer = match SyncCryptoStore::sr25519_public_keys(&*keystore, PAYOUT_KEY_TYPE).into_iter().next() {
+		Some(signer) => signer,
+		None => {
+			log::error!(
+				target: LOG_TARGET,
+				"No `{:?}` key in the keystore, validator payouts are disabled.",
+				PAYOUT_KEY_TYPE,
+			);
+			return
+		},
+	};
+	log::info!(
+		target: LOG_TARGET,
+		"Paying out {} validators as {}.",
+		config.validators.len(),
+		AccountId::from(signer),
+	);
+
+	let mut pending: Vec<Pending> = Vec::new();
+	let mut last_era = None;
+	let mut finality_notifications = client.finality_notification_stream();
+	while let Some(notification) = finality_notifications.next().await {
+		let at = BlockId::Hash(notification.hash);
+		let number = *notification.header.number();
+		let active_era = match read::<_, _, pallet_staking::ActiveEraInfo>(
+			&*client,
+			&at,
+			pallet_staking::ActiveEra::<Runtime>::hashed_key().to_vec(),
+		) {
+			Some(active_era) => active_era.index,
+			None => continue,
+		};
+		if let Some(metrics) = &metrics {
+			metrics.era.set(active_era as u64);
+		}
+
+		// the era that ended before the node started may be unpaid as well.
+		if last_era != Some(active_era) && active_era > 0 {
+			for validator in &config.validators {
+				if !pending.iter().any(|p| p.validator == *validator && p.era == active_era - 1) {
+					pending.push(Pending {
+						validator: validator.clone(),
+						era: active_era - 1,
+						submissions: 0,
+						next_submission: number,
+					});
+				}
+			}
+		}
+		last_era = Some(active_era);
+
+		pending.retain(|payout| {
+			if is_claimed(&*client, &at, &payout.validator, payout.era) {
+				log::debug!(target: LOG_TARGET, "Era {} of {} is paid out.", payout.era, payout.validator);
+				if let Some(metrics) = &metrics {
+					metrics.claimed.inc();
+				}
+				false
+			} else if payout.submissions > config.max_retries {
+				log::warn!(
+					target: LOG_TARGET,
+					"Giving up on era {} of {} after {} submissions.",
+					payout.era,
+					payout.validator,
+					payout.submissions,
+				);
+				if let Some(metrics) = &metrics {
+					metrics.failed.inc();
+				}
+				false
+			} else {
+				true
+			}
+		});
+
+		let mut due = pending.iter_mut().filter(|payout| payout.next_submission <= number).peekable();
+		if due.peek().is_none() {
+			continue
+		}
+		let best = client.info().best_hash;
+		let best_number = client.info().best_number;
+		let mut nonce = match client.runtime_api().account_nonce(&BlockId::Hash(best), signer.into()) {
+			// payouts still in the pool are not in the nonce at best yet.
+			Ok(nonce) => next_nonce(&*transaction_pool, &signer.into(), nonce),
+			Err(e) => {
+				log::warn!(target: LOG_TARGET, "Failed to read the nonce of the payout key: {:?}", e);
+				continue
+			},
+		};
+		for payout in due {
+			payout.submissions += 1;
+			payout.next_submission = number + RETRY_PERIOD;
+
+			let call = Call::Staking(pallet_staking::Call::payout_stakers(payout.validator.clone(), payout.era));
+			let xt = match sign(&*client, &*keystore, &signer, call, nonce, best, best_number) {
+				Ok(xt) => xt,
+				Err(e) => {
+					log::warn!(target: LOG_TARGET, "Failed to sign a payout: {}", e);
+					continue
+				},
+			};
+			match transaction_pool.submit_one(&BlockId::Hash(best), TransactionSource::Local, xt).await {
+				Ok(hash) => {
+					log::info!(
+						target: LOG_TARGET,
+						"Submitted the payout of era {} of {} as {:?}.",
+						payout.era,
+						payout.validator,
+						hash,
+					);
+					nonce += 1;
+					if let Some(metrics) = &metrics {
+						metrics.submitted.inc();
+					}
+				},
+				Err(e) => log::warn!(
+					target: LOG_TARGET,
+					"Failed to submit the payout of era {} of {}: {:?}",
+					payout.era,
+					payout.validator,
+					e,
+				),
+			}
+		}
+	}
+}
+
+/// The nonce of `who` after its transactions that are ready in `pool`, from its `nonce` on chain,
+/// as `system_accountNextIndex` reports it.
+fn next_nonce<P: TransactionPool>(pool: &P, who: &AccountId, nonce: Index) -> Index {
+	let mut nonce = nonce;
+	let mut tag = (who, nonce).encode();
+	// the ready transactions of an account come in the order of their nonces.
+	for xt in pool.ready() {
+		if xt.provides().get(0) == Some(&tag) {
+			nonce += 1;
+			tag = (who, nonce).encode();
+		}
+	}
+	nonce
+}
+
+/// Read and decode the storage value at `key`.
+fn read<C, BE, T: Decode>(client: &C, at: &BlockId<Block>, key: Vec<u8>) -> Option<T> where
+	C: StorageProvider<Block, BE>,
+	BE: Backend<Block>,
+{
+	client.storage(at, &StorageKey(key))
+		.ok()
+		.flatten()
+		.and_then(|data| T::decode(&mut &data.0[..]).ok())
+}
+
+/// Whether the rewards of `validator` for `era` are claimed, or can not be claimed any more as
+/// it is no longer bonded.
+fn is_claimed<C, BE>(client: &C, at: &BlockId<Block>, validator: &AccountId, era: pallet_staking::EraIndex) -> bool where
+	C: StorageProvider<Block, BE>,
+	BE: Backend<Block>,
+{
+	read::<_, _, AccountId>(client, at, pallet_staking::Bonded::<Runtime>::hashed_key_for(validator))
+		.and_then(|controller| read::<_, _, pallet_staking::StakingLedger<AccountId, Balance>>(
+			client,
+			at,
+			pallet_staking::Ledger::<Runtime>::hashed_key_for(&controller),
+		))
+		.map_or(true, |ledger| ledger.claimed_rewards.contains(&era))
+}
+
+/// Sign `call` with the payout key, as a transaction that is valid for `RETRY_PERIOD` blocks
+/// from `best`.
+///
+/// The extension tuple must be kept in line with [`node_runtime::SignedExtra`].
+fn sign<C>(
+	client: &C,
+	keystore: &dyn SyncCryptoStore,
+	signer: &sr25519::Public,
+	call: Call,
+	nonce: Index,
+	best: Hash,
+	best_number: BlockNumber,
+) -> Result<UncheckedExtrinsic, String> where
+	C: ProvideRuntimeApi<Block> + HeaderBackend<Block>,
+{
+	let version = client.runtime_api().version(&BlockId::Hash(best)).map_err(|e| format!("{:?}", e))?;
+	let genesis_hash = client.info().genesis_hash;
+	let extra: SignedExtra = (
+		frame_system::CheckSpecVersion::new(),
+		frame_system::CheckTxVersion::new(),
+		frame_system::CheckGenesis::new(),
+		frame_system::CheckEra::from(Era::mortal(RETRY_PERIOD as u64, best_number as u64)),
+		frame_system::CheckNonce::from(nonce),
+		frame_system::CheckWeight::new(),
+		pallet_asset_tx_payment::ChargeAssetTxPayment::from(0, None),
+	);
+	let raw_payload = SignedPayload::from_raw(
+		call,
+		extra,
+		(version.spec_version, version.transaction_version, genesis_hash, best, (), (), ()),
+	);
+	let signature = raw_payload
+		.using_encoded(|payload| SyncCryptoStore::sign_with(keystore, PAYOUT_KEY_TYPE, &(*signer).into(), payload))
+		.map_err(|e| format!("{:?}", e))?
+		.ok_or_else(|| "the payout key is not in the keystore".to_string())?;
+	let signature = sr25519::Signature::try_from(&signature[..])
+		.map_err(|_| "the keystore returned an invalid signature".to_string())?;
+	let (call, extra, _) = raw_payload.deconstruct();
+
+	Ok(UncheckedExtrinsic::new_signed(
+		call,
+		sp_runtime::MultiAddress::Id(AccountId::from(*signer)),
+		signature.into(),
+		extra,
+	))
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use std::{thread, time::Duration};
+	use node_runtime::{CheckedExtrinsic, VERSION, constants::time::SLOT_DURATION};
+	use node_testing::{
+		client::{Backend, Client, ClientBlockImportExt, TestClientBuilder, TestClientBuilderExt},
+		keyring::{alice, dave, signed_extra},
+	};
+	use sc_block_builder::BlockBuilderProvider;
+	use sc_keystore::LocalKeystore;
+	use sp_consensus::BlockOrigin;
+	use sp_consensus_babe::{BABE_ENGINE_ID, digests::{PreDigest, SecondaryPlainPreDigest}};
+	use sp_core::{hashing::twox_128, testing::TaskExecutor};
+	use sp_runtime::generic::{Digest, DigestItem};
+
+	/// A chain of finalized blocks, in which storage is put with the sudo key of alice.
+	struct Chain {
+		client: Arc<Client>,
+		number: BlockNumber,
+		nonce: Index,
+	}
+
+	impl Chain {
+		fn new() -> Self {
+			let sudo_key = [twox_128(b"Sudo"), twox_128(b"Key")].concat();
+			let client = TestClientBuilder::new().add_extra_storage(sudo_key, alice().encode()).build();
+			Self { client: Arc::new(client), number: 0, nonce: 0 }
+		}
+
+		/// Import and finalize the next block, putting `storage` in it.
+		fn finalize_block(&mut self, storage: Vec<(Vec<u8>, Vec<u8>)>) {
+			self.number += 1;
+			let slot = self.number as u64;
+			let pre_digest = PreDigest::SecondaryPlain(SecondaryPlainPreDigest {
+				authority_index: 0,
+				slot: slot.into(),
+			});
+			let digest = Digest { logs: vec![DigestItem::PreRuntime(BABE_ENGINE_ID, pre_digest.encode())] };
+			let mut builder = self.client.new_block(digest).unwrap();
+			builder.push(UncheckedExtrinsic {
+				signature: None,
+				function: Call::Timestamp(pallet_timestamp::Call::set(slot * SLOT_DURATION)),
+			}.into()).unwrap();
+			if !storage.is_empty() {
+				let call = Call::System(frame_system::Call::set_storage(storage));
+				builder.push(node_testing::keyring::sign(
+					CheckedExtrinsic {
+						signed: Some((alice(), signed_extra(self.nonce, 0))),
+						function: Call::Sudo(pallet_sudo::Call::sudo(Box::new(call))),
+					},
+					VERSION.spec_version,
+					VERSION.transaction_version,
+					self.client.info().genesis_hash.into(),
+				).into()).unwrap();
+				self.nonce += 1;
+			}
+			let block = builder.build().unwrap().block;
+			futures::executor::block_on(self.client.import_as_final(BlockOrigin::Own, block)).unwrap();
+		}
+
+		/// Finalize blocks up to `number`.
+		fn finalize_until(&mut self, number: BlockNumber) {
+			while self.number < number {
+				self.finalize_block(vec![]);
+			}
+		}
+	}
+
+	fn active_era(index: pallet_staking::EraIndex) -> (Vec<u8>, Vec<u8>) {
+		let info = pallet_staking::ActiveEraInfo { index, start: None };
+		(pallet_staking::ActiveEra::<Runtime>::hashed_key().to_vec(), info.encode())
+	}
+
+	fn metric(registry: &Registry, name: &str) -> u64 {
+		registry.gather().into_iter()
+			.find(|family| family.get_name() == name)
+			.map_or(0, |family| {
+				let metric = &family.get_metric()[0];
+				(metric.get_counter().get_value() + metric.get_gauge().get_value()) as u64
+			})
+	}
+
+	/// Wait for the service, which runs on its own thread, to get to `condition`.
+	fn wait_for(condition: impl Fn() -> bool) {
+		for _ in 0..200 {
+			if condition() {
+				return
+			}
+			thread::sleep(Duration::from_millis(50));
+		}
+		panic!("the payout service did not get there in time");
+	}
+
+	#[test]
+	fn pays_out_every_era_and_gives_up_after_the_retries() {
+		let mut chain = Chain::new();
+		let client = chain.client.clone();
+		let pool = sc_transaction_pool::BasicPool::new_full(
+			Default::default(),
+			true.into(),
+			None,
+			TaskExecutor::new(),
+			client.clone(),
+		);
+		let keystore: SyncCryptoStorePtr = Arc::new(LocalKeystore::in_memory());
+		SyncCryptoStore::sr25519_generate_new(&*keystore, PAYOUT_KEY_TYPE, Some("//Charlie")).unwrap();
+		let registry = Registry::new();
+		let config = PayoutConfig { validators: vec![dave()], max_retries: 1 };
+		{
+			let (client, pool, registry) = (client.clone(), pool.clone(), registry.clone());
+			thread::spawn(move || futures::executor::block_on(
+				run::<_, Backend, _>(client, pool, keystore, config, Some(registry))
+			));
+		}
+		let submitted = || metric(&registry, "payout_submitted_total");
+
+		// the era that ended before the service started is paid out.
+		chain.finalize_block(vec![active_era(1)]);
+		wait_for(|| submitted() == 1);
+		assert_eq!(metric(&registry, "payout_active_era"), 1);
+		assert_eq!(pool.status().ready, 1);
+
+		// the payout is not included, so it is submitted again with the next nonce.
+		chain.finalize_until(RETRY_PERIOD);
+		chain.finalize_block(vec![]);
+		wait_for(|| submitted() == 2);
+		assert_eq!(pool.status().ready, 2);
+		let nonces: Vec<Index> = pool.ready()
+			.map(|xt| {
+				let xt = UncheckedExtrinsic::decode(&mut &xt.data().encode()[..]).unwrap();
+				let (_, _, extra) = xt.signature.unwrap();
+				(extra.4).0
+			})
+			.collect();
+		assert_eq!(nonces, vec![0, 1]);
+
+		// and given up once the retries are used up.
+		chain.finalize_until(1 + 2 * RETRY_PERIOD);
+		wait_for(|| metric(&registry, "payout_failed_total") == 1);
+		assert_eq!(submitted(), 2);
+
+		// the payout of the next era is already claimed.
+		let ledger_key = pallet_staking::Ledger::<Runtime>::hashed_key_for(&alice());
+		let mut ledger = read::<_, Backend, pallet_staking::StakingLedger<AccountId, Balance>>(
+			&*client,
+			&BlockId::Number(chain.number),
+			ledger_key.clone(),
+		).unwrap();
+		ledger.claimed_rewards.push(1);
+		chain.finalize_block(vec![active_era(2), (ledger_key, ledger.encode())]);
+		wait_for(|| metric(&registry, "payout_claimed_total") == 1);
+		assert_eq!(metric(&registry, "payout_active_era"), 2);
+		assert_eq!(submitted(), 2);
+		assert_eq!(metric(&registry, "payout_failed_total"), 1);
+	}
+}
@@ -208,11 +208,16 @@ pub struct NewFullBase {
 /// Creates a full service from the configuration.
 pub fn new_full_base(
 	mut config: Configuration,
+	payout: Option<crate::payout::PayoutConfig>,
 	with_startup_data: impl FnOnce(
 		&sc_consensus_babe::BabeBlockImport<Block, FullClient, FullGrandpaBlockImport>,
 		&sc_consensus_babe::BabeLink<Block>,
 	)
 ) -> Result<NewFullBase, ServiceError> {
+	// The MMR pallet keeps its nodes only in the offchain database, where `mmr_generateProof`
+	// reads them, so blocks are always imported with offchain indexing.
+	config.offchain_worker.indexing_enabled = true;
+
 	let sc_service::PartialComponents {
 		client,
 		backend,
@@ -281,6 +286,19 @@ pub fn new_full_base(
 		},
 	)?;
 
+	if let Some(payout) = payout {
+		task_manager.spawn_handle().spawn(
+			"validator-payout",
+			crate::payout::run::<_, FullBackend, _>(
+				client.clone(),
+				transaction_pool.clone(),
+				keystore_container.sync_keystore(),
+				payout,
+				prometheus_registry.clone(),
+			),
+		);
+	}
+
 	let (block_import, grandpa_link, babe_link) = import_setup;
 
 	(with_startup_data)(&block_import, &babe_link);
@@ -416,11 +434,12 @@ pub fn new_full_base(
 	})
 }
 
-/// Builds a new service for a full client.
+/// Builds a new service for a full client, paying out validators if `payout` is given.
 pub fn new_full(
 	config: Configuration,
+	payout: Option<crate::payout::PayoutConfig>,
 ) -> Result<TaskManager, ServiceError> {
-	new_full_base(config, |_, _| ()).map(|NewFullBase { task_manager, .. }| {
+	new_full_base(config, payout, |_, _| ()).map(|NewFullBase { task_manager, .. }| {
 		task_manager
 	})
 }
@@ -659,7 +678,7 @@ mod tests {
 				let mut setup_handles = None;
 				let NewFullBase {
 					task_manager, client, network, transaction_pool, ..
-				} = new_full_base(config,
+				} = new_full_base(config, None,
 					|
 						block_import: &sc_consensus_babe::BabeBlockImport<Block, _, _>,
 						babe_link: &sc_consensus_babe::BabeLink<Block>,
@@ -795,7 +814,7 @@ mod tests {
 				let check_era = frame_system::CheckEra::from(Era::Immortal);
 				let check_nonce = frame_system::CheckNonce::from(index);
 				let check_weight = frame_system::CheckWeight::new();
-				let payment = pallet_transaction_payment::ChargeTransactionPayment::from(0);
+				let payment = pallet_asset_tx_payment::ChargeAssetTxPayment::from(0, None);
 				let extra = (
 					check_spec_version,
 					check_tx_version,
@@ -832,7 +851,7 @@ mod tests {
 			crate::chain_spec::tests::integration_test_config_with_two_authorities(),
 			|config| {
 				let NewFullBase { task_manager, client, network, transaction_pool, .. }
-					= new_full_base(config,|_, _| ())?;
+					= new_full_base(config, None, |_, _| ())?;
 				Ok(sc_service_test::TestNetComponents::new(task_manager, client, network, transaction_pool))
 			},
 			|config| {
@@ -0,0 +1,83 @@
+// Copyright 2019 TrustBase Network
+// This file is part of TrustBase library.
+//
+// The TrustBase library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Lesser General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// The TrustBase library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU Lesser General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Generates an MMR leaf proof over the RPC of a dev node started in this process.
+
+use sc_cli::SubstrateCli;
+use serde_json::{json, Value};
+use std::{io::{Read, Write}, net::TcpStream, thread, time::Duration};
+use structopt::StructOpt;
+
+const RPC_PORT: u16 = 45790;
+
+/// Start a dev node serving HTTP RPC on `RPC_PORT`, without `--enable-offchain-indexing`.
+fn start_dev_node() {
+	thread::spawn(|| {
+		let port = RPC_PORT.to_string();
+		let cli = node_cli::Cli::from_iter(&[
+			"trustbase", "--dev", "--tmp", "--rpc-port", &port, "--no-prometheus", "--no-telemetry",
+		]);
+		let runner = cli.create_runner(&cli.run).expect("dev node is configured");
+		runner.run_node_until_exit(|config| async move {
+			node_cli::service::new_full(config, None).map_err(sc_cli::Error::Service)
+		}).expect("dev node runs until the test exits");
+	});
+}
+
+/// Call `method` with `params` over HTTP, or `None` if the node is not up yet.
+fn rpc(method: &str, params: Value) -> Option<Value> {
+	let request = json!({ "jsonrpc": "2.0", "id": 1, "method": method, "params": params }).to_string();
+	let mut stream = TcpStream::connect(("127.0.0.1", RPC_PORT)).ok()?;
+	write!(
+		stream,
+		"POST / HTTP/1.1\r\nHost: 127.0.0.1\r\nContent-Type: application/json\r\n\
+		Content-Length: {}\r\nConnection: close\r\n\r\n{}",
+		request.len(),
+		request,
+	).ok()?;
+	let mut response = String::new();
+	stream.read_to_string(&mut response).ok()?;
+	let body = response.splitn(2, "\r\n\r\n").nth(1)?;
+	serde_json::from_str(body).ok()
+}
+
+/// The number of the best block, once the node answers.
+fn best_number() -> Option<u64> {
+	let header = rpc("chain_getHeader", json!([]))?;
+	let number = header["result"]["number"].as_str()?;
+	u64::from_str_radix(number.trim_start_matches("0x"), 16).ok()
+}
+
+#[test]
+fn generates_a_leaf_proof() {
+	start_dev_node();
+	let mut best = 0;
+	for _ in 0..60 {
+		best = best_number().unwrap_or(0);
+		if best >= 2 {
+			break;
+		}
+		thread::sleep(Duration::from_secs(1));
+	}
+	assert!(best >= 2, "dev node did not author blocks");
+
+	let response = rpc("mmr_generateProof", json!([1])).expect("node answers");
+	assert!(response["error"].is_null(), "{}", response);
+	let proof = &response["result"];
+	assert!(proof["blockHash"].is_string(), "{}", response);
+	assert!(proof["leaf"].is_string(), "{}", response);
+	assert!(proof["proof"].is_string(), "{}", response);
+}
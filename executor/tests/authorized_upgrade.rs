@@ -0,0 +1,57 @@
+// Copyright 2019 TrustBase Network
+// This file is part of TrustBase library.
+//
+// The TrustBase library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Lesser General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// The TrustBase library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU Lesser General Public License for more details.
+
+use frame_support::weights::{DispatchClass, Pays};
+use sp_runtime::traits::{BlakeTwo256, Hash};
+use node_runtime::{
+	CheckedExtrinsic, Call, Event, Runtime, Balances, System, RuntimeBlockWeights,
+};
+use node_testing::keyring::*;
+
+pub mod common;
+use self::common::*;
+
+#[test]
+fn enacting_an_authorized_upgrade_fits_in_a_block() {
+	let mut t = new_test_ext(compact_code_unwrap(), false);
+	// the current code, which does not raise the spec version.
+	let code = compact_code_unwrap().to_vec();
+
+	t.execute_with(|| {
+		pallet_authorized_upgrade::AuthorizedCodeHash::<Runtime>::put(BlakeTwo256::hash(&code));
+	});
+	let before = t.execute_with(|| Balances::free_balance(charlie()));
+
+	initialize_block(&mut t, 1);
+	let r = apply_extrinsic(&mut t, CheckedExtrinsic {
+		signed: Some((charlie(), signed_extra(0, 0))),
+		function: Call::AuthorizedUpgrade(pallet_authorized_upgrade::Call::enact_authorized_upgrade(code)),
+	});
+	// the extrinsic is included, and only fails in its dispatch.
+	assert!(matches!(r, Ok(Err(_))), "unexpected result: {:?}", r);
+
+	t.execute_with(|| {
+		let operational = RuntimeBlockWeights::get().get(DispatchClass::Operational).max_extrinsic;
+		let info = System::events().into_iter().find_map(|r| match r.event {
+			Event::System(frame_system::Event::ExtrinsicFailed(_, info)) => Some(info),
+			_ => None,
+		}).expect("the enactment failed");
+		assert_eq!(info.class, DispatchClass::Operational);
+		assert_eq!(info.pays_fee, Pays::Yes);
+		assert!(info.weight <= operational.unwrap());
+
+		// a failed enactment is charged its fee.
+		assert!(Balances::free_balance(charlie()) < before);
+		assert!(pallet_authorized_upgrade::AuthorizedCodeHash::<Runtime>::exists());
+	});
+}
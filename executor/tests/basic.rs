@@ -842,3 +842,38 @@ fn should_import_block_with_test_client() {
 
 	futures::executor::block_on(client.import(BlockOrigin::Own, block)).unwrap();
 }
+
+#[test]
+fn mmr_leaves_are_appended_on_block_import() {
+	let mut t = new_test_ext(compact_code_unwrap(), false);
+	let (block1, block2) = blocks();
+
+	t.execute_with(|| assert_eq!(node_runtime::Mmr::mmr_leaves(), 0));
+
+	executor_call::<NeverNativeValue, fn() -> _>(
+		&mut t,
+		"Core_execute_block",
+		&block1.0,
+		true,
+		None,
+	).0.unwrap();
+
+	let root1 = t.execute_with(|| {
+		// the leaf of a block is the hash of its parent.
+		assert_eq!(node_runtime::Mmr::mmr_leaves(), 1);
+		node_runtime::Mmr::mmr_root_hash()
+	});
+
+	executor_call::<NeverNativeValue, fn() -> _>(
+		&mut t,
+		"Core_execute_block",
+		&block2.0,
+		true,
+		None,
+	).0.unwrap();
+
+	t.execute_with(|| {
+		assert_eq!(node_runtime::Mmr::mmr_leaves(), 2);
+		assert_ne!(node_runtime::Mmr::mmr_root_hash(), root1);
+	});
+}
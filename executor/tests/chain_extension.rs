@@ -0,0 +1,137 @@
+// Copyright 2019 TrustBase Network
+// This file is part of TrustBase library.
+//
+// The TrustBase library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Lesser General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// The TrustBase library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU Lesser General Public License for more details.
+
+use codec::Encode;
+use sp_core::NeverNativeValue;
+use sp_keyring::AccountKeyring;
+use sp_runtime::traits::Hash as HashT;
+use frame_system::Phase;
+use node_runtime::{
+	CheckedExtrinsic, Call, Event, Runtime, System,
+	chain_extension::{FuncId, TrustBaseExtension},
+	constants::{time::SLOT_DURATION, currency::*},
+};
+use node_primitives::AccountId;
+use node_testing::keyring::*;
+
+pub mod common;
+use self::common::{*, sign};
+
+const CODE_CHAIN_EXTENSION: &str = include_str!("fixtures/chain_extension.wat");
+
+fn input(func_id: u32, args: impl Encode) -> Vec<u8> {
+	let mut input = func_id.to_le_bytes().to_vec();
+	args.encode_to(&mut input);
+	input
+}
+
+fn call_contract(addr: &AccountId, nonce: u32, input: Vec<u8>) -> CheckedExtrinsic {
+	CheckedExtrinsic {
+		signed: Some((charlie(), signed_extra(nonce, 0))),
+		function: Call::Contracts(
+			pallet_contracts::Call::call::<Runtime>(
+				sp_runtime::MultiAddress::Id(addr.clone()),
+				0,
+				500_000_000,
+				input,
+			)
+		),
+	}
+}
+
+#[test]
+fn contracts_can_read_runtime_state_through_the_chain_extension() {
+	let code = wat::parse_str(CODE_CHAIN_EXTENSION).unwrap();
+	let code_hash = <Runtime as frame_system::Config>::Hashing::hash(&code);
+	let addr = pallet_contracts::Pallet::<Runtime>::contract_address(&charlie(), &code_hash, &[]);
+	let subsistence = pallet_contracts::Pallet::<Runtime>::subsistence_threshold();
+
+	let message = b"trust me".to_vec();
+	let signature = AccountKeyring::Alice.sign(&message);
+	let public = AccountKeyring::Alice.public();
+	let subject = [1u8; 32];
+
+	let time = 42 * 1000;
+	let b = construct_block(
+		&mut new_test_ext(compact_code_unwrap(), false),
+		1,
+		GENESIS_HASH.into(),
+		vec![
+			CheckedExtrinsic {
+				signed: None,
+				function: Call::Timestamp(pallet_timestamp::Call::set(time)),
+			},
+			CheckedExtrinsic {
+				signed: Some((charlie(), signed_extra(0, 0))),
+				function: Call::Contracts(
+					pallet_contracts::Call::instantiate_with_code::<Runtime>(
+						10 * DOLLARS + subsistence,
+						500_000_000,
+						code,
+						Vec::new(),
+						Vec::new(),
+					)
+				),
+			},
+			call_contract(&addr, 1, input(FuncId::Balance as u32, alice())),
+			call_contract(&addr, 2, input(FuncId::Locks as u32, alice())),
+			call_contract(&addr, 3, input(FuncId::IsSocietyMember as u32, alice())),
+			call_contract(&addr, 4, input(FuncId::IsSocietyMember as u32, ferdie())),
+			call_contract(&addr, 5, input(
+				FuncId::Sr25519Verify as u32,
+				(signature.clone(), public, message),
+			)),
+			call_contract(&addr, 6, input(
+				FuncId::Sr25519Verify as u32,
+				(signature, public, b"trust you".to_vec()),
+			)),
+			call_contract(&addr, 7, input(FuncId::EpochRandomness as u32, subject)),
+			// an unknown function traps the contract.
+			call_contract(&addr, 8, input(0x0001_ffff, alice())),
+		],
+		(time / SLOT_DURATION).into(),
+	);
+
+	let mut t = new_test_ext(compact_code_unwrap(), false);
+
+	executor_call::<NeverNativeValue, fn() -> _>(
+		&mut t,
+		"Core_execute_block",
+		&b.0,
+		false,
+		None,
+	).0.unwrap();
+
+	t.execute_with(|| {
+		let events = System::events();
+		let emitted: Vec<Vec<u8>> = events.iter().filter_map(|r| match &r.event {
+			Event::Contracts(pallet_contracts::Event::ContractEmitted(contract, data))
+				if contract == &addr => Some(data.clone()),
+			_ => None,
+		}).collect();
+
+		assert_eq!(emitted, vec![
+			TrustBaseExtension::balance(&alice()).encode(),
+			TrustBaseExtension::locks(&alice()).encode(),
+			true.encode(),
+			false.encode(),
+			true.encode(),
+			false.encode(),
+			TrustBaseExtension::epoch_randomness(&subject).encode(),
+		]);
+		assert!(events.iter().any(|r| r.phase == Phase::ApplyExtrinsic(9) && matches!(
+			r.event,
+			Event::System(frame_system::Event::ExtrinsicFailed(..)),
+		)));
+	});
+}
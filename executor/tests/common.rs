@@ -35,8 +35,8 @@ use sc_executor::error::Result;
 
 use node_executor::Executor;
 use node_runtime::{
-	Header, Block, UncheckedExtrinsic, CheckedExtrinsic, Runtime, BuildStorage,
-	constants::currency::*,
+	Header, Block, UncheckedExtrinsic, CheckedExtrinsic, Call, Runtime, Babe, System, BuildStorage,
+	constants::{currency::*, time::SLOT_DURATION},
 };
 use node_primitives::{Hash, BlockNumber};
 use node_testing::keyring::*;
@@ -135,6 +135,29 @@ pub fn new_test_ext(code: &[u8], support_changes_trie: bool) -> TestExternalitie
 	ext
 }
 
+/// Initialize block `n`, with an empty digest, on top of `env`.
+pub fn initialize_block(env: &mut TestExternalities<BlakeTwo256>, n: BlockNumber) {
+	executor_call::<NeverNativeValue, fn() -> _>(
+		env,
+		"Core_initialize_block",
+		&from_block_number(n).encode(),
+		true,
+		None,
+	).0.unwrap();
+}
+
+/// Sign and apply `xt` to the block being built on top of `env`.
+pub fn apply_extrinsic(env: &mut TestExternalities<BlakeTwo256>, xt: CheckedExtrinsic) -> ApplyExtrinsicResult {
+	let r = executor_call::<NeverNativeValue, fn() -> _>(
+		env,
+		"BlockBuilder_apply_extrinsic",
+		&sign(xt).encode(),
+		true,
+		None,
+	).0.expect("application of an extrinsic failed").into_encoded();
+	ApplyExtrinsicResult::decode(&mut &r[..]).expect("apply result deserialization failed")
+}
+
 /// Construct a fake block.
 ///
 /// `extrinsics` must be a list of valid extrinsics, i.e. none of the extrinsics for example
@@ -214,3 +237,23 @@ pub fn construct_block(
 	let hash = header.blake2_256();
 	(Block { header, extrinsics }.encode(), hash.into())
 }
+
+/// Construct the block after the last one executed on `env`, `slots` slots after it, with the
+/// timestamp of its slot followed by `extrinsics`.
+pub fn construct_next_block(
+	env: &mut TestExternalities<BlakeTwo256>,
+	parent_hash: Hash,
+	slots: u64,
+	extrinsics: Vec<CheckedExtrinsic>,
+) -> (Vec<u8>, Hash) {
+	let (number, slot) = env.execute_with(|| {
+		(System::block_number() + 1, u64::from(Babe::current_slot()) + slots)
+	});
+
+	let mut block = vec![CheckedExtrinsic {
+		signed: None,
+		function: Call::Timestamp(pallet_timestamp::Call::set(slot * SLOT_DURATION)),
+	}];
+	block.extend(extrinsics);
+	construct_block(env, number, parent_hash, block, slot.into())
+}
@@ -0,0 +1,145 @@
+// Copyright 2019 TrustBase Network
+// This file is part of TrustBase library.
+//
+// The TrustBase library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Lesser General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// The TrustBase library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU Lesser General Public License for more details.
+
+use sp_runtime::{generic::Era, traits::BlakeTwo256};
+use node_runtime::{
+	CheckedExtrinsic, Call, Event, Runtime, Balances, ElectionProviderMultiPhase, Session,
+	SessionsPerEra, Staking, System,
+	constants::time::EPOCH_DURATION_IN_SLOTS,
+};
+use node_primitives::Hash;
+use node_testing::keyring::*;
+use pallet_election_provider_multi_phase::{
+	ElectionCompute, Event as ElectionEvent, SnapshotMetadata, SolutionOrSnapshotSize,
+};
+
+pub mod common;
+use self::common::*;
+
+/// Author empty blocks on top of `parent_hash` until `condition` holds, a block in every slot
+/// once the next election is at most a session away and a session at a time before. Returns the
+/// hash of the last block.
+fn run_until(
+	t: &mut TestExternalities<BlakeTwo256>,
+	mut parent_hash: Hash,
+	condition: impl Fn() -> bool,
+) -> Hash {
+	while !t.execute_with(&condition) {
+		let (number, skip_session) = t.execute_with(|| {
+			let era = Staking::current_era().unwrap_or(0);
+			let era_start = Staking::eras_start_session_index(era).unwrap_or(0);
+			(System::block_number(), Session::current_index() + 2 < era_start + SessionsPerEra::get())
+		});
+		assert!(number < 1_000, "the condition does not hold within 1000 blocks");
+
+		// a whole epoch of slots later the next session starts.
+		let slots = if skip_session { EPOCH_DURATION_IN_SLOTS } else { 1 };
+		parent_hash = construct_next_block(t, parent_hash, slots, vec![]).1;
+	}
+	parent_hash
+}
+
+/// How the election finalized in the last block, if it did.
+fn finalized_election() -> Option<Option<ElectionCompute>> {
+	System::events().into_iter().find_map(|record| match record.event {
+		Event::ElectionProviderMultiPhase(ElectionEvent::ElectionFinalized(compute)) => Some(compute),
+		_ => None,
+	})
+}
+
+#[test]
+fn signed_solution_is_elected_and_rewarded() {
+	let mut t = new_test_ext(compact_code_unwrap(), false);
+	let hash = run_until(&mut t, GENESIS_HASH.into(), || {
+		ElectionProviderMultiPhase::current_phase().is_signed()
+	});
+
+	let (solution, _) = t.execute_with(|| ElectionProviderMultiPhase::mine_solution(2).unwrap());
+	// the mortal era of `signed_extra` may have expired by the signed phase.
+	let mut extra = signed_extra(0, 0);
+	extra.3 = frame_system::CheckEra::from(Era::Immortal);
+	let (_, hash) = construct_next_block(&mut t, hash, 1, vec![CheckedExtrinsic {
+		signed: Some((alice(), extra)),
+		function: Call::ElectionProviderMultiPhase(
+			pallet_election_provider_multi_phase::Call::submit(solution, 0)
+		),
+	}]);
+	let (free, deposit) = t.execute_with(|| {
+		(Balances::free_balance(alice()), Balances::reserved_balance(alice()))
+	});
+	assert!(deposit > 0);
+
+	run_until(&mut t, hash, || finalized_election().is_some());
+	t.execute_with(|| {
+		assert_eq!(finalized_election(), Some(Some(ElectionCompute::Signed)));
+		assert_eq!(Staking::current_era(), Some(1));
+		// the deposit was returned with the reward.
+		assert_eq!(Balances::reserved_balance(alice()), 0);
+		assert!(Balances::free_balance(alice()) > free + deposit);
+	});
+}
+
+#[test]
+fn unsigned_solution_is_elected_without_signed_submissions() {
+	let mut t = new_test_ext(compact_code_unwrap(), false);
+	let hash = run_until(&mut t, GENESIS_HASH.into(), || {
+		ElectionProviderMultiPhase::current_phase().is_unsigned_open()
+	});
+
+	let (solution, witness) = t.execute_with(|| ElectionProviderMultiPhase::mine_solution(2).unwrap());
+	let (_, hash) = construct_next_block(&mut t, hash, 1, vec![CheckedExtrinsic {
+		signed: None,
+		function: Call::ElectionProviderMultiPhase(
+			pallet_election_provider_multi_phase::Call::submit_unsigned(solution, witness)
+		),
+	}]);
+
+	run_until(&mut t, hash, || finalized_election().is_some());
+	t.execute_with(|| {
+		assert_eq!(finalized_election(), Some(Some(ElectionCompute::Unsigned)));
+		assert_eq!(Staking::current_era(), Some(1));
+	});
+}
+
+#[test]
+fn on_chain_fallback_elects_without_any_solution() {
+	let mut t = new_test_ext(compact_code_unwrap(), false);
+	run_until(&mut t, GENESIS_HASH.into(), || finalized_election().is_some());
+
+	t.execute_with(|| {
+		assert_eq!(finalized_election(), Some(Some(ElectionCompute::OnChain)));
+		assert_eq!(Staking::current_era(), Some(1));
+	});
+}
+
+#[test]
+fn on_chain_fallback_is_not_attempted_when_it_does_not_fit_in_the_block() {
+	let mut t = new_test_ext(compact_code_unwrap(), false);
+	let hash = run_until(&mut t, GENESIS_HASH.into(), || {
+		ElectionProviderMultiPhase::current_phase().is_signed()
+	});
+
+	// the snapshot grows far beyond what a block can elect on chain, and nobody submits.
+	let validators = t.execute_with(|| {
+		SnapshotMetadata::<Runtime>::put(SolutionOrSnapshotSize { voters: 10_000_000, targets: 10_000 });
+		Session::validators()
+	});
+
+	run_until(&mut t, hash, || finalized_election().is_some());
+	t.execute_with(|| {
+		assert_eq!(finalized_election(), Some(None));
+		// no new era is planned, so the current validators stay.
+		assert_eq!(Staking::current_era(), Some(0));
+		assert_eq!(Session::validators(), validators);
+	});
+}
@@ -17,12 +17,13 @@ use frame_support::{
 	weights::{GetDispatchInfo, constants::ExtrinsicBaseWeight, IdentityFee, WeightToFeePolynomial},
 };
 use sp_core::NeverNativeValue;
-use sp_runtime::{Perbill, traits::One};
+use sp_runtime::{Perbill, Permill, traits::One};
 use node_runtime::{
-	CheckedExtrinsic, Call, Runtime, Balances, TransactionPayment, Multiplier,
-	TransactionByteFee,
+	CheckedExtrinsic, Call, Event, Origin, Runtime, Balances, System, TransactionPayment, Multiplier,
+	TransactionByteFee, Treasury, FeeSplit,
 	constants::{time::SLOT_DURATION, currency::*},
 };
+use pallet_fee_split::{ImbalanceKind, Split};
 use node_primitives::Balance;
 use node_testing::keyring::*;
 
@@ -203,6 +204,66 @@ fn transaction_fee_is_correct() {
 	});
 }
 
+#[test]
+fn fees_and_tips_follow_the_governed_split() {
+	let mut t = new_test_ext(compact_code_unwrap(), false);
+
+	t.execute_with(|| {
+		let fee_split = Split {
+			treasury: Permill::from_percent(50),
+			burn: Permill::from_percent(25),
+			ecosystem: Permill::from_percent(25),
+			..Default::default()
+		};
+		let tip_split = Split { ecosystem: Permill::from_percent(100), ..Default::default() };
+		assert!(FeeSplit::set_split(Origin::root(), ImbalanceKind::Fee, fee_split).is_ok());
+		assert!(FeeSplit::set_split(Origin::root(), ImbalanceKind::Tip, tip_split).is_ok());
+		assert!(FeeSplit::set_ecosystem_account(Origin::root(), Some(eve())).is_ok());
+		let _ = Balances::deposit_creating(&Treasury::account_id(), 1_000 * DOLLARS);
+	});
+	let (alice_before, eve_before, issuance_before) = t.execute_with(|| (
+		Balances::free_balance(alice()),
+		Balances::free_balance(eve()),
+		Balances::total_issuance(),
+	));
+
+	let tip = 1_000_000;
+	let xt = sign(CheckedExtrinsic {
+		signed: Some((alice(), signed_extra(0, tip))),
+		function: Call::Balances(default_transfer_call()),
+	});
+	let r = executor_call::<NeverNativeValue, fn() -> _>(
+		&mut t,
+		"Core_initialize_block",
+		&vec![].and(&from_block_number(1u32)),
+		true,
+		None,
+	).0;
+	assert!(r.is_ok());
+	let r = executor_call::<NeverNativeValue, fn() -> _>(
+		&mut t,
+		"BlockBuilder_apply_extrinsic",
+		&vec![].and(&xt),
+		true,
+		None,
+	).0;
+	assert!(r.is_ok());
+
+	t.execute_with(|| {
+		let fee = alice_before - Balances::free_balance(alice()) - 69 * DOLLARS - tip;
+		let burnt = Permill::from_percent(25) * fee;
+		let to_ecosystem = Permill::from_percent(25) * fee;
+
+		assert_eq!(Balances::free_balance(eve()), eve_before + to_ecosystem + tip);
+		assert_eq!(Balances::total_issuance(), issuance_before - burnt);
+		let deposited: Balance = System::events().iter().filter_map(|r| match r.event {
+			Event::Treasury(pallet_treasury::RawEvent::Deposit(amount)) => Some(amount),
+			_ => None,
+		}).sum();
+		assert_eq!(deposited, fee - burnt - to_ecosystem);
+	});
+}
+
 #[test]
 #[should_panic]
 #[cfg(feature = "stress-test")]
@@ -0,0 +1,49 @@
+;; Calls the chain extension function whose id is given in the first four bytes of the input,
+;; passing the rest of the input, and emits the output of the function as a contract event.
+;; This is what an ink! contract using the TrustBase chain extension compiles down to.
+(module
+	(import "seal0" "seal_input" (func $seal_input (param i32 i32)))
+	(import "seal0" "seal_call_chain_extension"
+		(func $seal_call_chain_extension (param i32 i32 i32 i32 i32) (result i32)))
+	(import "seal0" "seal_deposit_event" (func $seal_deposit_event (param i32 i32 i32 i32)))
+	(import "env" "memory" (memory 1 1))
+
+	;; [0, 4) size of the input buffer
+	(data (i32.const 0) "\00\20")
+
+	;; [4, 8) size of the output buffer
+	(data (i32.const 4) "\00\20")
+
+	;; [16, 8208) input buffer: function id followed by the function input
+
+	;; [16384, 24576) output buffer
+
+	(func $assert (param i32)
+		(block $ok
+			(br_if $ok (get_local 0))
+			(unreachable)
+		)
+	)
+
+	(func (export "deploy"))
+
+	(func (export "call")
+		(call $seal_input (i32.const 16) (i32.const 0))
+		(call $assert (i32.ge_u (i32.load (i32.const 0)) (i32.const 4)))
+		(call $assert (i32.eqz
+			(call $seal_call_chain_extension
+				(i32.load (i32.const 16))
+				(i32.const 20)
+				(i32.sub (i32.load (i32.const 0)) (i32.const 4))
+				(i32.const 16384)
+				(i32.const 4)
+			)
+		))
+		(call $seal_deposit_event
+			(i32.const 0)
+			(i32.const 0)
+			(i32.const 16384)
+			(i32.load (i32.const 4))
+		)
+	)
+)
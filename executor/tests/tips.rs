@@ -0,0 +1,98 @@
+// Copyright 2019 TrustBase Network
+// This file is part of TrustBase library.
+//
+// The TrustBase library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Lesser General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// The TrustBase library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU Lesser General Public License for more details.
+
+use frame_support::traits::Currency;
+use sp_runtime::{
+	generic::Era,
+	traits::{BlakeTwo256, Hash},
+};
+use node_runtime::{
+	CheckedExtrinsic, Call, Event, Runtime, Balances, System, Treasury, Tips, TipCountdown,
+	constants::currency::*,
+};
+use node_primitives::{AccountId, Balance};
+use node_testing::keyring::*;
+
+pub mod common;
+use self::common::*;
+
+#[test]
+fn council_tip_closes_and_pays_out_from_treasury() {
+	let mut t = new_test_ext(compact_code_unwrap(), false);
+
+	// alice and bob sit on the council and are therefore the tippers.
+	t.execute_with(|| {
+		let mut members: Vec<AccountId> = vec![alice(), bob()];
+		members.sort();
+		pallet_elections_phragmen::Members::<Runtime>::put(
+			members.into_iter()
+				.map(|who| pallet_elections_phragmen::SeatHolder { who, stake: 0, deposit: 0 })
+				.collect::<Vec<_>>()
+		);
+		let _ = Balances::deposit_creating(&Treasury::account_id(), 1_000 * DOLLARS);
+	});
+
+	let reason = b"found a bug".to_vec();
+	let hash = BlakeTwo256::hash_of(&(BlakeTwo256::hash(&reason[..]), ferdie()));
+
+	initialize_block(&mut t, 1);
+	let r = apply_extrinsic(&mut t, CheckedExtrinsic {
+		signed: Some((alice(), signed_extra(0, 0))),
+		function: Call::Tips(pallet_tips::Call::tip_new(reason, ferdie(), 10 * DOLLARS)),
+	});
+	assert_eq!(r, Ok(Ok(())));
+	let r = apply_extrinsic(&mut t, CheckedExtrinsic {
+		signed: Some((bob(), signed_extra(0, 0))),
+		function: Call::Tips(pallet_tips::Call::tip(hash, 20 * DOLLARS)),
+	});
+	assert_eq!(r, Ok(Ok(())));
+
+	t.execute_with(|| {
+		assert!(Tips::tips(hash).is_some());
+		assert_eq!(Balances::free_balance(ferdie()), 100 * DOLLARS);
+	});
+
+	// the countdown started with the first tip, so the tip can be closed once it has elapsed.
+	initialize_block(&mut t, 1 + TipCountdown::get());
+	let treasury_before = t.execute_with(|| Balances::free_balance(Treasury::account_id()));
+
+	// the mortal era of `signed_extra` has long expired by now.
+	let mut extra = signed_extra(0, 0);
+	extra.3 = frame_system::CheckEra::from(Era::Immortal);
+	let r = apply_extrinsic(&mut t, CheckedExtrinsic {
+		signed: Some((charlie(), extra)),
+		function: Call::Tips(pallet_tips::Call::close_tip(hash)),
+	});
+	assert_eq!(r, Ok(Ok(())));
+
+	t.execute_with(|| {
+		// the median of the two tips is paid out, with no finder's fee for a council tip.
+		assert_eq!(Balances::free_balance(ferdie()), 120 * DOLLARS);
+		assert!(Tips::tips(hash).is_none());
+
+		let events = System::events();
+		assert!(events.iter().any(|r| r.event ==
+			Event::Tips(pallet_tips::RawEvent::TipClosed(hash, ferdie(), 20 * DOLLARS))
+		));
+
+		// the treasury still collects its share of charlie's fee.
+		let fees_deposited: Balance = events.iter().filter_map(|r| match r.event {
+			Event::Treasury(pallet_treasury::RawEvent::Deposit(amount)) => Some(amount),
+			_ => None,
+		}).sum();
+		assert_eq!(
+			Balances::free_balance(Treasury::account_id()),
+			treasury_before - 20 * DOLLARS + fees_deposited,
+		);
+	});
+}
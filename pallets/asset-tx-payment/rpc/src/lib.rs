@@ -0,0 +1,115 @@
+// Copyright 2019 TrustBase Network
+// This file is part of TrustBase library.
+//
+// The TrustBase library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Lesser General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// The TrustBase library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU Lesser General Public License for more details.
+//
+// You should have received a copy of the GNU Lesser General Public License
+// along with the TrustBase library. If not, see <http://www.gnu.org/licenses/>.
+
+//! RPC interface for quoting transaction fees in an asset.
+
+use std::sync::Arc;
+use std::convert::TryInto;
+use codec::{Codec, Decode};
+use sp_blockchain::HeaderBackend;
+use jsonrpc_core::{Error as RpcError, ErrorCode, Result};
+use jsonrpc_derive::rpc;
+use sp_runtime::{generic::BlockId, traits::{Block as BlockT, MaybeDisplay}};
+use sp_api::ProvideRuntimeApi;
+use sp_core::Bytes;
+use sp_rpc::number::NumberOrHex;
+pub use pallet_asset_tx_payment_rpc_runtime_api::AssetTxPaymentApi as AssetTxPaymentRuntimeApi;
+
+/// Asset fee payment RPC methods.
+#[rpc]
+pub trait AssetTxPaymentApi<BlockHash, AssetId, ResponseType> {
+	/// Quote the partial fee of an encoded extrinsic in `asset_id`, or `None` if fees cannot be
+	/// paid in that asset.
+	#[rpc(name = "payment_queryFeeInAsset")]
+	fn query_fee_in_asset(
+		&self,
+		encoded_xt: Bytes,
+		asset_id: AssetId,
+		at: Option<BlockHash>,
+	) -> Result<Option<ResponseType>>;
+}
+
+/// A struct that implements the [`AssetTxPaymentApi`].
+pub struct AssetTxPayment<C, P> {
+	client: Arc<C>,
+	_marker: std::marker::PhantomData<P>,
+}
+
+impl<C, P> AssetTxPayment<C, P> {
+	/// Create new `AssetTxPayment` with the given reference to the client.
+	pub fn new(client: Arc<C>) -> Self {
+		Self { client, _marker: Default::default() }
+	}
+}
+
+/// Error type of this RPC api.
+pub enum Error {
+	/// The transaction was not decodable.
+	DecodeError,
+	/// The call to runtime failed.
+	RuntimeError,
+}
+
+impl From<Error> for i64 {
+	fn from(e: Error) -> i64 {
+		match e {
+			Error::RuntimeError => 1,
+			Error::DecodeError => 2,
+		}
+	}
+}
+
+impl<C, Block, Balance, AssetId> AssetTxPaymentApi<<Block as BlockT>::Hash, AssetId, NumberOrHex>
+	for AssetTxPayment<C, Block>
+where
+	Block: BlockT,
+	C: 'static + ProvideRuntimeApi<Block> + HeaderBackend<Block>,
+	C::Api: AssetTxPaymentRuntimeApi<Block, Balance, AssetId>,
+	Balance: Codec + MaybeDisplay + Copy + TryInto<NumberOrHex>,
+	AssetId: Codec + Send + Sync + 'static,
+{
+	fn query_fee_in_asset(
+		&self,
+		encoded_xt: Bytes,
+		asset_id: AssetId,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> Result<Option<NumberOrHex>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(||
+			// If the block hash is not supplied assume the best block.
+			self.client.info().best_hash
+		));
+
+		let encoded_len = encoded_xt.len() as u32;
+
+		let uxt: Block::Extrinsic = Decode::decode(&mut &*encoded_xt).map_err(|e| RpcError {
+			code: ErrorCode::ServerError(Error::DecodeError.into()),
+			message: "Unable to query fee in asset.".into(),
+			data: Some(format!("{:?}", e).into()),
+		})?;
+		let fee = api.query_fee_in_asset(&at, uxt, encoded_len, asset_id).map_err(|e| RpcError {
+			code: ErrorCode::ServerError(Error::RuntimeError.into()),
+			message: "Unable to query fee in asset.".into(),
+			data: Some(format!("{:?}", e).into()),
+		})?;
+
+		fee.map(|fee| fee.try_into().map_err(|_| RpcError {
+			code: ErrorCode::InvalidParams,
+			message: format!("{} doesn't fit in NumberOrHex representation", fee),
+			data: None,
+		})).transpose()
+	}
+}
@@ -0,0 +1,373 @@
+// Copyright 2019 TrustBase Network
+// This file is part of TrustBase library.
+//
+// The TrustBase library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Lesser General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// The TrustBase library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU Lesser General Public License for more details.
+//
+// You should have received a copy of the GNU Lesser General Public License
+// along with the TrustBase library. If not, see <http://www.gnu.org/licenses/>.
+
+//! # Asset Transaction Payment Pallet
+//!
+//! Lets the signer of a transaction pay its fee in a fungible asset instead of the native
+//! currency.
+//!
+//! The fee is always computed in the native currency by `pallet_transaction_payment`, so the
+//! weight, length and multiplier logic are shared. When the signer names an asset in the
+//! [`ChargeAssetTxPayment`] signed extension, the native fee is converted with the
+//! governance-set [`ConversionRates`] entry of that asset and withdrawn from the signer's asset
+//! balance. After dispatch the unused part is refunded and the rest is handed to
+//! [`Config::OnAssetFees`].
+//!
+//! Transactions that do not name an asset are charged exactly as with
+//! `pallet_transaction_payment::ChargeTransactionPayment`.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::{Encode, Decode};
+use frame_support::{
+	traits::{
+		Get,
+		tokens::fungibles::{Inspect, Mutate},
+	},
+	weights::{DispatchClass, DispatchInfo, PostDispatchInfo, Weight},
+	dispatch::DispatchResult,
+};
+use sp_runtime::{
+	FixedU128, FixedPointNumber, FixedPointOperand,
+	traits::{
+		DispatchInfoOf, Dispatchable, PostDispatchInfoOf, SaturatedConversion, Saturating,
+		SignedExtension, Zero,
+	},
+	transaction_validity::{
+		TransactionPriority, TransactionValidity, TransactionValidityError, ValidTransaction,
+		InvalidTransaction,
+	},
+};
+use pallet_transaction_payment::{ChargeTransactionPayment, OnChargeTransaction};
+
+pub use pallet::*;
+
+#[cfg(test)]
+mod mock;
+#[cfg(test)]
+mod tests;
+
+/// Balance type of the native currency used for fees.
+pub type BalanceOf<T> =
+	<<T as pallet_transaction_payment::Config>::OnChargeTransaction as OnChargeTransaction<T>>::Balance;
+/// Asset identifier type used by [`Config::Fungibles`].
+pub type AssetIdOf<T> =
+	<<T as Config>::Fungibles as Inspect<<T as frame_system::Config>::AccountId>>::AssetId;
+/// Asset balance type used by [`Config::Fungibles`].
+pub type AssetBalanceOf<T> =
+	<<T as Config>::Fungibles as Inspect<<T as frame_system::Config>::AccountId>>::Balance;
+
+/// Handler for fees and tips that were paid in an asset.
+pub trait HandleAssetFees<AccountId, AssetId, Balance> {
+	/// Take care of `fee` and `tip` of `asset`, which have already been withdrawn from the
+	/// payer and are no longer part of any account's balance.
+	fn handle_asset_fees(asset: AssetId, fee: Balance, tip: Balance);
+}
+
+impl<AccountId, AssetId, Balance> HandleAssetFees<AccountId, AssetId, Balance> for () {
+	fn handle_asset_fees(_: AssetId, _: Balance, _: Balance) {}
+}
+
+/// Weight functions needed for this pallet.
+pub trait WeightInfo {
+	fn set_conversion_rate() -> Weight;
+}
+
+impl WeightInfo for () {
+	fn set_conversion_rate() -> Weight {
+		(15_000_000 as Weight)
+			.saturating_add(frame_support::weights::constants::RocksDbWeight::get().writes(1 as Weight))
+	}
+}
+
+#[frame_support::pallet]
+pub mod pallet {
+	use frame_support::pallet_prelude::*;
+	use frame_system::pallet_prelude::*;
+	use super::*;
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config + pallet_transaction_payment::Config {
+		/// The overarching event type.
+		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+
+		/// The fungible assets fees may be paid in.
+		type Fungibles: Mutate<Self::AccountId>;
+
+		/// Origin allowed to set or remove the conversion rate of an asset.
+		type RateOrigin: EnsureOrigin<Self::Origin>;
+
+		/// What to do with fees and tips paid in an asset.
+		type OnAssetFees: HandleAssetFees<Self::AccountId, AssetIdOf<Self>, AssetBalanceOf<Self>>;
+
+		/// Weight information for extrinsics in this pallet.
+		type WeightInfo: WeightInfo;
+	}
+
+	#[pallet::pallet]
+	#[pallet::generate_store(pub(super) trait Store)]
+	pub struct Pallet<T>(_);
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {}
+
+	/// The number of asset units charged per unit of native fee, for every asset that can be
+	/// used to pay fees.
+	#[pallet::storage]
+	#[pallet::getter(fn conversion_rate)]
+	pub type ConversionRates<T: Config> = StorageMap<_, Blake2_128Concat, AssetIdOf<T>, FixedU128>;
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	#[pallet::metadata(T::AccountId = "AccountId", AssetIdOf<T> = "AssetId", AssetBalanceOf<T> = "Balance")]
+	pub enum Event<T: Config> {
+		/// The conversion rate of an asset was set. \[asset, rate\]
+		ConversionRateSet(AssetIdOf<T>, FixedU128),
+		/// The asset can no longer be used to pay fees. \[asset\]
+		ConversionRateRemoved(AssetIdOf<T>),
+		/// A transaction fee was paid in an asset. \[who, asset, actual_fee, tip\]
+		AssetTxFeePaid(T::AccountId, AssetIdOf<T>, AssetBalanceOf<T>, AssetBalanceOf<T>),
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// A conversion rate must be greater than zero.
+		ZeroRate,
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Set the number of `asset` units charged per unit of native fee, or stop accepting
+		/// `asset` for fees if `rate` is `None`.
+		///
+		/// The dispatch origin for this call must be `RateOrigin`.
+		#[pallet::weight(<T as Config>::WeightInfo::set_conversion_rate())]
+		pub fn set_conversion_rate(
+			origin: OriginFor<T>,
+			asset: AssetIdOf<T>,
+			rate: Option<FixedU128>,
+		) -> DispatchResultWithPostInfo {
+			T::RateOrigin::ensure_origin(origin)?;
+			match rate {
+				Some(rate) => {
+					ensure!(!rate.is_zero(), Error::<T>::ZeroRate);
+					ConversionRates::<T>::insert(asset, rate);
+					Self::deposit_event(Event::ConversionRateSet(asset, rate));
+				},
+				None => {
+					ConversionRates::<T>::remove(asset);
+					Self::deposit_event(Event::ConversionRateRemoved(asset));
+				},
+			}
+			Ok(().into())
+		}
+	}
+}
+
+impl<T: Config> Pallet<T> {
+	/// Convert a native balance to `asset` at `rate`.
+	fn convert(rate: FixedU128, amount: BalanceOf<T>) -> AssetBalanceOf<T> {
+		rate.saturating_mul_int(amount.saturated_into::<u128>()).saturated_into()
+	}
+
+	/// The amount of `asset` charged for a native fee of `amount`, or `None` if fees cannot be
+	/// paid in `asset`.
+	pub fn to_asset_balance(asset: AssetIdOf<T>, amount: BalanceOf<T>) -> Option<AssetBalanceOf<T>> {
+		ConversionRates::<T>::get(asset).map(|rate| Self::convert(rate, amount))
+	}
+}
+
+/// How a transaction fee was paid up front.
+pub enum InitialPayment<T: Config> where
+	BalanceOf<T>: Send + Sync + From<u64> + FixedPointOperand,
+	<T as frame_system::Config>::Call: Dispatchable<Info=DispatchInfo, PostInfo=PostDispatchInfo>,
+{
+	/// The fee was withdrawn in the native currency by `pallet_transaction_payment`.
+	Native(<ChargeTransactionPayment<T> as SignedExtension>::Pre),
+	/// The fee was withdrawn in `asset`, converted at `rate`.
+	Asset {
+		who: T::AccountId,
+		asset: AssetIdOf<T>,
+		rate: FixedU128,
+		tip: BalanceOf<T>,
+		paid: AssetBalanceOf<T>,
+	},
+}
+
+/// Require the transactor to pay for themselves and maybe include a tip to gain additional
+/// priority in the queue. The fee is paid in `asset_id` if it is set, and in the native
+/// currency otherwise.
+#[derive(Encode, Decode, Clone, Eq, PartialEq)]
+pub struct ChargeAssetTxPayment<T: Config> {
+	#[codec(compact)]
+	tip: BalanceOf<T>,
+	asset_id: Option<AssetIdOf<T>>,
+}
+
+impl<T: Config> ChargeAssetTxPayment<T> where
+	BalanceOf<T>: Send + Sync + From<u64> + FixedPointOperand,
+	<T as frame_system::Config>::Call: Dispatchable<Info=DispatchInfo, PostInfo=PostDispatchInfo>,
+{
+	/// Utility constructor. Used only in client/factory code.
+	pub fn from(tip: BalanceOf<T>, asset_id: Option<AssetIdOf<T>>) -> Self {
+		Self { tip, asset_id }
+	}
+
+	fn native(&self) -> ChargeTransactionPayment<T> {
+		ChargeTransactionPayment::<T>::from(self.tip)
+	}
+
+	/// Withdraw the fee of a call in `asset`, returning the rate used, the native fee and the
+	/// amount paid.
+	fn withdraw_asset_fee(
+		&self,
+		who: &T::AccountId,
+		asset: AssetIdOf<T>,
+		info: &DispatchInfoOf<<T as frame_system::Config>::Call>,
+		len: usize,
+		commit: bool,
+	) -> Result<(FixedU128, BalanceOf<T>, AssetBalanceOf<T>), TransactionValidityError> {
+		let rate = ConversionRates::<T>::get(asset).ok_or(InvalidTransaction::Payment)?;
+		let fee = pallet_transaction_payment::Pallet::<T>::compute_fee(len as u32, info, self.tip);
+		let asset_fee = Pallet::<T>::convert(rate, fee);
+		if asset_fee.is_zero() {
+			return Ok((rate, fee, asset_fee));
+		}
+
+		T::Fungibles::can_withdraw(asset, who, asset_fee)
+			.into_result()
+			.map_err(|_| InvalidTransaction::Payment)?;
+		if commit {
+			T::Fungibles::burn_from(asset, who, asset_fee)
+				.map_err(|_| InvalidTransaction::Payment)?;
+		}
+		Ok((rate, fee, asset_fee))
+	}
+
+	/// The priority of a transaction whose native fee is `fee`, the same as
+	/// `ChargeTransactionPayment` gives it, so that the asset and its rate do not matter.
+	fn priority(
+		len: usize,
+		info: &DispatchInfoOf<<T as frame_system::Config>::Call>,
+		fee: BalanceOf<T>,
+	) -> TransactionPriority {
+		let max_block_weight = T::BlockWeights::get().max_block;
+		let max_block_length = *T::BlockLength::get().max.get(DispatchClass::Normal) as u64;
+
+		let bounded_weight = info.weight.max(1).min(max_block_weight);
+		let bounded_length = (len as u64).max(1).min(max_block_length);
+
+		let max_tx_per_block_weight = max_block_weight / bounded_weight;
+		let max_tx_per_block_length = max_block_length / bounded_length;
+		let coefficient: BalanceOf<T> =
+			max_tx_per_block_weight.min(max_tx_per_block_length).saturated_into();
+		fee.saturating_mul(coefficient).saturated_into()
+	}
+}
+
+impl<T: Config> sp_std::fmt::Debug for ChargeAssetTxPayment<T> {
+	#[cfg(feature = "std")]
+	fn fmt(&self, f: &mut sp_std::fmt::Formatter) -> sp_std::fmt::Result {
+		write!(f, "ChargeAssetTxPayment<{:?}, {:?}>", self.tip, self.asset_id.encode())
+	}
+	#[cfg(not(feature = "std"))]
+	fn fmt(&self, _: &mut sp_std::fmt::Formatter) -> sp_std::fmt::Result {
+		Ok(())
+	}
+}
+
+impl<T: Config> SignedExtension for ChargeAssetTxPayment<T> where
+	BalanceOf<T>: Send + Sync + From<u64> + FixedPointOperand,
+	AssetIdOf<T>: Send + Sync,
+	<T as frame_system::Config>::Call: Dispatchable<Info=DispatchInfo, PostInfo=PostDispatchInfo>,
+{
+	const IDENTIFIER: &'static str = "ChargeAssetTxPayment";
+	type AccountId = T::AccountId;
+	type Call = <T as frame_system::Config>::Call;
+	type AdditionalSigned = ();
+	type Pre = InitialPayment<T>;
+
+	fn additional_signed(&self) -> sp_std::result::Result<(), TransactionValidityError> { Ok(()) }
+
+	fn validate(
+		&self,
+		who: &Self::AccountId,
+		call: &Self::Call,
+		info: &DispatchInfoOf<Self::Call>,
+		len: usize,
+	) -> TransactionValidity {
+		match self.asset_id {
+			None => self.native().validate(who, call, info, len),
+			Some(asset) => {
+				let (_, fee, _) = self.withdraw_asset_fee(who, asset, info, len, false)?;
+				Ok(ValidTransaction {
+					priority: Self::priority(len, info, fee),
+					..Default::default()
+				})
+			},
+		}
+	}
+
+	fn pre_dispatch(
+		self,
+		who: &Self::AccountId,
+		call: &Self::Call,
+		info: &DispatchInfoOf<Self::Call>,
+		len: usize
+	) -> Result<Self::Pre, TransactionValidityError> {
+		match self.asset_id {
+			None => self.native().pre_dispatch(who, call, info, len).map(InitialPayment::Native),
+			Some(asset) => {
+				let (rate, _, paid) = self.withdraw_asset_fee(who, asset, info, len, true)?;
+				Ok(InitialPayment::Asset { who: who.clone(), asset, rate, tip: self.tip, paid })
+			},
+		}
+	}
+
+	fn post_dispatch(
+		pre: Self::Pre,
+		info: &DispatchInfoOf<Self::Call>,
+		post_info: &PostDispatchInfoOf<Self::Call>,
+		len: usize,
+		result: &DispatchResult,
+	) -> Result<(), TransactionValidityError> {
+		match pre {
+			InitialPayment::Native(pre) =>
+				ChargeTransactionPayment::<T>::post_dispatch(pre, info, post_info, len, result),
+			InitialPayment::Asset { who, asset, rate, tip, paid } => {
+				let actual_fee = pallet_transaction_payment::Pallet::<T>::compute_actual_fee(
+					len as u32,
+					info,
+					post_info,
+					tip,
+				);
+				let actual_asset_fee = Pallet::<T>::convert(rate, actual_fee).min(paid);
+				let refund = paid.saturating_sub(actual_asset_fee);
+				// A refund fails if the payer's account can no longer hold the asset, e.g. when
+				// it is below the minimum balance. It is then collected together with the fee.
+				let paid_fee = if refund.is_zero() || T::Fungibles::mint_into(asset, &who, refund).is_ok() {
+					actual_asset_fee
+				} else {
+					paid
+				};
+				let asset_tip = Pallet::<T>::convert(rate, tip).min(paid_fee);
+				T::OnAssetFees::handle_asset_fees(asset, paid_fee.saturating_sub(asset_tip), asset_tip);
+				Pallet::<T>::deposit_event(Event::AssetTxFeePaid(who, asset, paid_fee, asset_tip));
+				Ok(())
+			},
+		}
+	}
+}
@@ -0,0 +1,168 @@
+// Copyright 2019 TrustBase Network
+// This file is part of TrustBase library.
+//
+// The TrustBase library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Lesser General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// The TrustBase library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU Lesser General Public License for more details.
+//
+// You should have received a copy of the GNU Lesser General Public License
+// along with the TrustBase library. If not, see <http://www.gnu.org/licenses/>.
+
+//! Tests for the asset transaction payment pallet.
+
+use super::*;
+use crate::mock::*;
+use frame_support::{assert_noop, assert_ok, weights::Pays};
+use sp_runtime::traits::BadOrigin;
+
+const CALL: &<Test as frame_system::Config>::Call =
+	&Call::Balances(pallet_balances::Call::transfer(2, 69));
+
+fn info_from_weight(weight: Weight) -> DispatchInfo {
+	DispatchInfo { weight, ..Default::default() }
+}
+
+fn post_info_from_weight(weight: Weight) -> PostDispatchInfo {
+	PostDispatchInfo { actual_weight: Some(weight), pays_fee: Pays::Yes }
+}
+
+#[test]
+fn only_rate_origin_can_set_conversion_rate() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			AssetTxPayment::set_conversion_rate(Origin::signed(1), ASSET, Some(FixedU128::from(2))),
+			BadOrigin,
+		);
+		assert_noop!(
+			AssetTxPayment::set_conversion_rate(Origin::root(), ASSET, Some(FixedU128::zero())),
+			Error::<Test>::ZeroRate,
+		);
+		assert_ok!(AssetTxPayment::set_conversion_rate(Origin::root(), ASSET, Some(FixedU128::from(2))));
+		assert_eq!(AssetTxPayment::conversion_rate(ASSET), Some(FixedU128::from(2)));
+		assert_eq!(AssetTxPayment::to_asset_balance(ASSET, 10), Some(20));
+
+		assert_ok!(AssetTxPayment::set_conversion_rate(Origin::root(), ASSET, None));
+		assert_eq!(AssetTxPayment::to_asset_balance(ASSET, 10), None);
+	});
+}
+
+#[test]
+fn fee_is_paid_in_asset_and_refunded() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(AssetTxPayment::set_conversion_rate(Origin::root(), ASSET, Some(FixedU128::from(2))));
+		let len = 10;
+
+		// base fee 1 + length fee 10 + weight fee 5, converted at a rate of 2.
+		let pre = ChargeAssetTxPayment::<Test>::from(0, Some(ASSET))
+			.pre_dispatch(&3, CALL, &info_from_weight(5), len)
+			.unwrap();
+		assert_eq!(Assets::balance(ASSET, 3), 1_000 - 32);
+
+		// only 2 units of weight were used: 1 + 10 + 2 = 13, converted at a rate of 2.
+		assert_ok!(ChargeAssetTxPayment::<Test>::post_dispatch(
+			pre,
+			&info_from_weight(5),
+			&post_info_from_weight(2),
+			len,
+			&Ok(()),
+		));
+		assert_eq!(Assets::balance(ASSET, 3), 1_000 - 26);
+		assert_eq!(handled_fees(), vec![(ASSET, 26, 0)]);
+		// the native balance was never touched.
+		assert_eq!(Balances::free_balance(3), 0);
+	});
+}
+
+#[test]
+fn tip_is_converted_and_reported_separately() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(AssetTxPayment::set_conversion_rate(Origin::root(), ASSET, Some(FixedU128::from(2))));
+		let len = 10;
+
+		let pre = ChargeAssetTxPayment::<Test>::from(5, Some(ASSET))
+			.pre_dispatch(&3, CALL, &info_from_weight(5), len)
+			.unwrap();
+		assert_ok!(ChargeAssetTxPayment::<Test>::post_dispatch(
+			pre,
+			&info_from_weight(5),
+			&post_info_from_weight(5),
+			len,
+			&Ok(()),
+		));
+		// (1 + 10 + 5 + 5) * 2, of which the tip is 5 * 2.
+		assert_eq!(Assets::balance(ASSET, 3), 1_000 - 42);
+		assert_eq!(handled_fees(), vec![(ASSET, 32, 10)]);
+	});
+}
+
+#[test]
+fn priority_follows_the_native_fee() {
+	new_test_ext().execute_with(|| {
+		let native = ChargeAssetTxPayment::<Test>::from(0, None)
+			.validate(&2, CALL, &info_from_weight(5), 10)
+			.unwrap();
+
+		assert_ok!(AssetTxPayment::set_conversion_rate(Origin::root(), ASSET, Some(FixedU128::from(2))));
+		let asset = ChargeAssetTxPayment::<Test>::from(0, Some(ASSET))
+			.validate(&3, CALL, &info_from_weight(5), 10)
+			.unwrap();
+		assert_eq!(asset.priority, native.priority);
+
+		assert_ok!(AssetTxPayment::set_conversion_rate(Origin::root(), ASSET, Some(FixedU128::from(50))));
+		let dear_asset = ChargeAssetTxPayment::<Test>::from(0, Some(ASSET))
+			.validate(&3, CALL, &info_from_weight(5), 10)
+			.unwrap();
+		assert_eq!(dear_asset.priority, native.priority);
+	});
+}
+
+#[test]
+fn asset_without_rate_cannot_pay_fees() {
+	new_test_ext().execute_with(|| {
+		assert!(ChargeAssetTxPayment::<Test>::from(0, Some(ASSET))
+			.validate(&3, CALL, &info_from_weight(5), 10)
+			.is_err());
+		assert!(ChargeAssetTxPayment::<Test>::from(0, Some(ASSET))
+			.pre_dispatch(&3, CALL, &info_from_weight(5), 10)
+			.is_err());
+		assert_eq!(Assets::balance(ASSET, 3), 1_000);
+	});
+}
+
+#[test]
+fn insufficient_asset_balance_is_rejected() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(AssetTxPayment::set_conversion_rate(Origin::root(), ASSET, Some(FixedU128::from(100))));
+		assert!(ChargeAssetTxPayment::<Test>::from(0, Some(ASSET))
+			.pre_dispatch(&3, CALL, &info_from_weight(5), 10)
+			.is_err());
+		assert_eq!(Assets::balance(ASSET, 3), 1_000);
+	});
+}
+
+#[test]
+fn native_payment_is_unchanged() {
+	new_test_ext().execute_with(|| {
+		let len = 10;
+		let pre = ChargeAssetTxPayment::<Test>::from(0, None)
+			.pre_dispatch(&2, CALL, &info_from_weight(5), len)
+			.unwrap();
+		assert_eq!(Balances::free_balance(2), 1_000 - 16);
+
+		assert_ok!(ChargeAssetTxPayment::<Test>::post_dispatch(
+			pre,
+			&info_from_weight(5),
+			&post_info_from_weight(5),
+			len,
+			&Ok(()),
+		));
+		assert_eq!(Balances::free_balance(2), 1_000 - 16);
+		assert!(handled_fees().is_empty());
+	});
+}
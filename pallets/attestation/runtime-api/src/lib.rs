@@ -0,0 +1,36 @@
+// Copyright 2019 TrustBase Network
+// This file is part of TrustBase library.
+//
+// The TrustBase library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Lesser General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// The TrustBase library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU Lesser General Public License for more details.
+//
+// You should have received a copy of the GNU Lesser General Public License
+// along with the TrustBase library. If not, see <http://www.gnu.org/licenses/>.
+
+//! Runtime API definition for querying attestations between accounts.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::Codec;
+use sp_std::vec::Vec;
+
+sp_api::decl_runtime_apis! {
+	pub trait AttestationApi<AccountId, SchemaId, Attestation> where
+		AccountId: Codec,
+		SchemaId: Codec,
+		Attestation: Codec,
+	{
+		/// The unexpired attestations about `subject`, with their issuer and schema.
+		fn inbound(subject: AccountId) -> Vec<(AccountId, SchemaId, Attestation)>;
+
+		/// The unexpired attestations issued by `issuer`, with their subject and schema.
+		fn outbound(issuer: AccountId) -> Vec<(AccountId, SchemaId, Attestation)>;
+	}
+}
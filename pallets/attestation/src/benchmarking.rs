@@ -0,0 +1,97 @@
+// Copyright 2019 TrustBase Network
+// This file is part of TrustBase library.
+//
+// The TrustBase library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Lesser General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// The TrustBase library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU Lesser General Public License for more details.
+//
+// You should have received a copy of the GNU Lesser General Public License
+// along with the TrustBase library. If not, see <http://www.gnu.org/licenses/>.
+
+//! Benchmarks for the attestation pallet.
+
+use super::*;
+use frame_benchmarking::{account, benchmarks, impl_benchmark_test_suite, whitelisted_caller};
+use frame_system::RawOrigin;
+use sp_runtime::traits::Bounded;
+
+use crate::Pallet as Attestation;
+
+const SEED: u32 = 0;
+const SCHEMA: SchemaId = 0;
+
+fn funded<T: Config>(who: &T::AccountId) {
+	T::Currency::make_free_balance_be(who, BalanceOf::<T>::max_value() / 2u32.into());
+}
+
+fn attested<T: Config>(
+	issuer: &T::AccountId,
+	subject: &T::AccountId,
+	len: u32,
+	expiry: Option<T::BlockNumber>,
+) {
+	funded::<T>(issuer);
+	Attestation::<T>::attest(
+		RawOrigin::Signed(issuer.clone()).into(),
+		subject.clone(),
+		SCHEMA,
+		vec![1; len as usize],
+		expiry,
+	).expect("the issuer can afford the deposit");
+}
+
+benchmarks! {
+	attest {
+		let b in 0 .. T::MaxDataLength::get();
+		let issuer: T::AccountId = whitelisted_caller();
+		let subject: T::AccountId = account("subject", 0, SEED);
+		funded::<T>(&issuer);
+	}: _(RawOrigin::Signed(issuer.clone()), subject.clone(), SCHEMA, vec![1; b as usize], None)
+	verify {
+		assert!(Attestation::<T>::attestation(&issuer, &subject, SCHEMA).is_some());
+	}
+
+	update {
+		let b in 0 .. T::MaxDataLength::get();
+		let issuer: T::AccountId = whitelisted_caller();
+		let subject: T::AccountId = account("subject", 0, SEED);
+		attested::<T>(&issuer, &subject, 0, None);
+	}: _(RawOrigin::Signed(issuer.clone()), subject.clone(), SCHEMA, vec![1; b as usize], None)
+	verify {
+		let attestation = Attestation::<T>::attestation(&issuer, &subject, SCHEMA).unwrap();
+		assert_eq!(attestation.data.len(), b as usize);
+	}
+
+	revoke {
+		let issuer: T::AccountId = whitelisted_caller();
+		let subject: T::AccountId = account("subject", 0, SEED);
+		attested::<T>(&issuer, &subject, T::MaxDataLength::get(), None);
+	}: _(RawOrigin::Signed(issuer.clone()), subject.clone(), SCHEMA)
+	verify {
+		assert!(Attestation::<T>::attestation(&issuer, &subject, SCHEMA).is_none());
+	}
+
+	reap {
+		let issuer: T::AccountId = account("issuer", 0, SEED);
+		let subject: T::AccountId = account("subject", 0, SEED);
+		let caller: T::AccountId = whitelisted_caller();
+		let now = frame_system::Pallet::<T>::block_number();
+		attested::<T>(&issuer, &subject, T::MaxDataLength::get(), Some(now + 1u32.into()));
+		frame_system::Pallet::<T>::set_block_number(now + 1u32.into());
+	}: _(RawOrigin::Signed(caller), issuer.clone(), subject.clone(), SCHEMA)
+	verify {
+		assert!(Attestation::<T>::attestation(&issuer, &subject, SCHEMA).is_none());
+	}
+}
+
+impl_benchmark_test_suite!(
+	Attestation,
+	crate::mock::new_test_ext(),
+	crate::mock::Test,
+);
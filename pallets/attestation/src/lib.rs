@@ -0,0 +1,316 @@
+// Copyright 2019 TrustBase Network
+// This file is part of TrustBase library.
+//
+// The TrustBase library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Lesser General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// The TrustBase library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU Lesser General Public License for more details.
+//
+// You should have received a copy of the GNU Lesser General Public License
+// along with the TrustBase library. If not, see <http://www.gnu.org/licenses/>.
+
+//! # Attestation Pallet
+//!
+//! A registry of attestations that one account (the issuer) makes about another (the subject).
+//!
+//! Every attestation is typed by a [`SchemaId`], which tells off-chain readers how to interpret
+//! its opaque `data`. An issuer holds at most one attestation per subject and schema, and can
+//! update or revoke it at any time. An attestation may carry an expiry block, from which on it no
+//! longer holds; anyone may then remove it with [`Pallet::reap`].
+//!
+//! The issuer reserves [`Config::DepositBase`] plus [`Config::DepositPerByte`] for every byte of
+//! `data` for as long as the attestation is stored. The deposit is returned to the issuer when
+//! the attestation is revoked or reaped.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::{Encode, Decode};
+use frame_support::{
+	ensure,
+	dispatch::DispatchResult,
+	traits::{Currency, ReservableCurrency, Get},
+};
+use sp_runtime::{
+	RuntimeDebug,
+	traits::{Saturating, Zero},
+};
+use sp_std::prelude::*;
+
+pub use pallet::*;
+
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+#[cfg(test)]
+mod mock;
+#[cfg(test)]
+mod tests;
+pub mod weights;
+
+pub use weights::WeightInfo;
+
+/// Identifier of the schema an attestation follows.
+pub type SchemaId = u32;
+
+type BalanceOf<T> = <<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
+
+/// An attestation as stored by this pallet.
+pub type AttestationOf<T> = Attestation<<T as frame_system::Config>::BlockNumber, BalanceOf<T>>;
+
+/// A claim made by an issuer about a subject.
+#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug)]
+pub struct Attestation<BlockNumber, Balance> {
+	/// Schema-specific payload, opaque to the chain.
+	pub data: Vec<u8>,
+	/// The block from which on the attestation no longer holds, if any.
+	pub expiry: Option<BlockNumber>,
+	/// The amount reserved from the issuer.
+	pub deposit: Balance,
+}
+
+impl<BlockNumber: PartialOrd, Balance> Attestation<BlockNumber, Balance> {
+	/// Whether the attestation no longer holds at block `now`.
+	pub fn is_expired(&self, now: &BlockNumber) -> bool {
+		self.expiry.as_ref().map_or(false, |expiry| expiry <= now)
+	}
+}
+
+#[frame_support::pallet]
+pub mod pallet {
+	use frame_support::pallet_prelude::*;
+	use frame_system::pallet_prelude::*;
+	use super::*;
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		/// The overarching event type.
+		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+
+		/// The currency the attestation deposit is reserved in.
+		type Currency: ReservableCurrency<Self::AccountId>;
+
+		/// The base amount reserved for every attestation.
+		#[pallet::constant]
+		type DepositBase: Get<BalanceOf<Self>>;
+
+		/// The amount reserved for every byte of attestation data.
+		#[pallet::constant]
+		type DepositPerByte: Get<BalanceOf<Self>>;
+
+		/// The maximum length of attestation data.
+		#[pallet::constant]
+		type MaxDataLength: Get<u32>;
+
+		/// Weight information for extrinsics in this pallet.
+		type WeightInfo: WeightInfo;
+	}
+
+	#[pallet::pallet]
+	#[pallet::generate_store(pub(super) trait Store)]
+	pub struct Pallet<T>(_);
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {}
+
+	/// The attestations of every issuer, keyed by subject and schema.
+	#[pallet::storage]
+	pub type Attestations<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat, T::AccountId,
+		Blake2_128Concat, (T::AccountId, SchemaId),
+		AttestationOf<T>,
+	>;
+
+	/// Reverse index of [`Attestations`]: the issuers and schemas attesting to every subject.
+	#[pallet::storage]
+	pub type Inbound<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat, T::AccountId,
+		Blake2_128Concat, (T::AccountId, SchemaId),
+		(),
+	>;
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	#[pallet::metadata(T::AccountId = "AccountId")]
+	pub enum Event<T: Config> {
+		/// An attestation was issued. \[issuer, subject, schema\]
+		Attested(T::AccountId, T::AccountId, SchemaId),
+		/// An attestation was updated. \[issuer, subject, schema\]
+		Updated(T::AccountId, T::AccountId, SchemaId),
+		/// An attestation was revoked by its issuer. \[issuer, subject, schema\]
+		Revoked(T::AccountId, T::AccountId, SchemaId),
+		/// An expired attestation was removed. \[issuer, subject, schema\]
+		Reaped(T::AccountId, T::AccountId, SchemaId),
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// The issuer already attests to the subject under this schema.
+		AlreadyAttested,
+		/// No such attestation.
+		NotFound,
+		/// The attestation data is longer than `MaxDataLength`.
+		DataTooLong,
+		/// The expiry block has already been reached.
+		ExpiryInPast,
+		/// The attestation has not expired yet.
+		NotExpired,
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Attest to `subject` under `schema`, reserving the deposit for `data`.
+		///
+		/// The dispatch origin for this call must be _Signed_ and becomes the issuer.
+		#[pallet::weight(T::WeightInfo::attest(data.len() as u32))]
+		pub fn attest(
+			origin: OriginFor<T>,
+			subject: T::AccountId,
+			schema: SchemaId,
+			data: Vec<u8>,
+			expiry: Option<T::BlockNumber>,
+		) -> DispatchResultWithPostInfo {
+			let issuer = ensure_signed(origin)?;
+			let key = (subject.clone(), schema);
+			ensure!(!Attestations::<T>::contains_key(&issuer, &key), Error::<T>::AlreadyAttested);
+			Self::ensure_valid(&data, &expiry)?;
+
+			let deposit = Self::deposit_for(&data);
+			T::Currency::reserve(&issuer, deposit)?;
+
+			Attestations::<T>::insert(&issuer, &key, Attestation { data, expiry, deposit });
+			Inbound::<T>::insert(&subject, (issuer.clone(), schema), ());
+			Self::deposit_event(Event::Attested(issuer, subject, schema));
+			Ok(().into())
+		}
+
+		/// Replace the data and expiry of an existing attestation, adjusting the deposit.
+		///
+		/// The dispatch origin for this call must be _Signed_ by the issuer.
+		#[pallet::weight(T::WeightInfo::update(data.len() as u32))]
+		pub fn update(
+			origin: OriginFor<T>,
+			subject: T::AccountId,
+			schema: SchemaId,
+			data: Vec<u8>,
+			expiry: Option<T::BlockNumber>,
+		) -> DispatchResultWithPostInfo {
+			let issuer = ensure_signed(origin)?;
+			let key = (subject.clone(), schema);
+			let mut attestation = Attestations::<T>::get(&issuer, &key).ok_or(Error::<T>::NotFound)?;
+			Self::ensure_valid(&data, &expiry)?;
+
+			let deposit = Self::deposit_for(&data);
+			if deposit > attestation.deposit {
+				T::Currency::reserve(&issuer, deposit - attestation.deposit)?;
+			} else {
+				let err_amount = T::Currency::unreserve(&issuer, attestation.deposit - deposit);
+				debug_assert!(err_amount.is_zero());
+			}
+
+			attestation.data = data;
+			attestation.expiry = expiry;
+			attestation.deposit = deposit;
+			Attestations::<T>::insert(&issuer, &key, attestation);
+			Self::deposit_event(Event::Updated(issuer, subject, schema));
+			Ok(().into())
+		}
+
+		/// Revoke an attestation and return its deposit.
+		///
+		/// The dispatch origin for this call must be _Signed_ by the issuer.
+		#[pallet::weight(T::WeightInfo::revoke())]
+		pub fn revoke(
+			origin: OriginFor<T>,
+			subject: T::AccountId,
+			schema: SchemaId,
+		) -> DispatchResultWithPostInfo {
+			let issuer = ensure_signed(origin)?;
+			Self::remove(&issuer, &subject, schema)?;
+			Self::deposit_event(Event::Revoked(issuer, subject, schema));
+			Ok(().into())
+		}
+
+		/// Remove an expired attestation and return its deposit to the issuer.
+		///
+		/// The dispatch origin for this call must be _Signed_.
+		#[pallet::weight(T::WeightInfo::reap())]
+		pub fn reap(
+			origin: OriginFor<T>,
+			issuer: T::AccountId,
+			subject: T::AccountId,
+			schema: SchemaId,
+		) -> DispatchResultWithPostInfo {
+			ensure_signed(origin)?;
+			let attestation = Attestations::<T>::get(&issuer, (subject.clone(), schema))
+				.ok_or(Error::<T>::NotFound)?;
+			let now = frame_system::Pallet::<T>::block_number();
+			ensure!(attestation.is_expired(&now), Error::<T>::NotExpired);
+
+			Self::remove(&issuer, &subject, schema)?;
+			Self::deposit_event(Event::Reaped(issuer, subject, schema));
+			Ok(().into())
+		}
+	}
+}
+
+impl<T: Config> Pallet<T> {
+	/// The deposit reserved for an attestation carrying `data`.
+	pub fn deposit_for(data: &[u8]) -> BalanceOf<T> {
+		T::DepositPerByte::get()
+			.saturating_mul((data.len() as u32).into())
+			.saturating_add(T::DepositBase::get())
+	}
+
+	/// The attestation `issuer` holds about `subject` under `schema`, expired or not.
+	pub fn attestation(
+		issuer: &T::AccountId,
+		subject: &T::AccountId,
+		schema: SchemaId,
+	) -> Option<AttestationOf<T>> {
+		Attestations::<T>::get(issuer, (subject.clone(), schema))
+	}
+
+	/// The unexpired attestations about `subject`, with their issuer and schema.
+	pub fn inbound(subject: T::AccountId) -> Vec<(T::AccountId, SchemaId, AttestationOf<T>)> {
+		let now = frame_system::Pallet::<T>::block_number();
+		Inbound::<T>::iter_prefix(&subject)
+			.filter_map(|((issuer, schema), ())| {
+				Attestations::<T>::get(&issuer, (subject.clone(), schema))
+					.filter(|a| !a.is_expired(&now))
+					.map(|a| (issuer, schema, a))
+			})
+			.collect()
+	}
+
+	/// The unexpired attestations issued by `issuer`, with their subject and schema.
+	pub fn outbound(issuer: T::AccountId) -> Vec<(T::AccountId, SchemaId, AttestationOf<T>)> {
+		let now = frame_system::Pallet::<T>::block_number();
+		Attestations::<T>::iter_prefix(&issuer)
+			.filter(|(_, a)| !a.is_expired(&now))
+			.map(|((subject, schema), a)| (subject, schema, a))
+			.collect()
+	}
+
+	fn ensure_valid(data: &[u8], expiry: &Option<T::BlockNumber>) -> DispatchResult {
+		ensure!(data.len() <= T::MaxDataLength::get() as usize, Error::<T>::DataTooLong);
+		if let Some(expiry) = expiry {
+			ensure!(*expiry > frame_system::Pallet::<T>::block_number(), Error::<T>::ExpiryInPast);
+		}
+		Ok(())
+	}
+
+	fn remove(issuer: &T::AccountId, subject: &T::AccountId, schema: SchemaId) -> DispatchResult {
+		let attestation = Attestations::<T>::take(issuer, (subject.clone(), schema))
+			.ok_or(Error::<T>::NotFound)?;
+		Inbound::<T>::remove(subject, (issuer.clone(), schema));
+		let err_amount = T::Currency::unreserve(issuer, attestation.deposit);
+		debug_assert!(err_amount.is_zero());
+		Ok(())
+	}
+}
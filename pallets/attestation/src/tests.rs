@@ -0,0 +1,122 @@
+// Copyright 2019 TrustBase Network
+// This file is part of TrustBase library.
+//
+// The TrustBase library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Lesser General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// The TrustBase library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU Lesser General Public License for more details.
+//
+// You should have received a copy of the GNU Lesser General Public License
+// along with the TrustBase library. If not, see <http://www.gnu.org/licenses/>.
+
+//! Tests for the attestation pallet.
+
+use crate::{Attestation, Error, SchemaId, mock::*};
+use frame_support::{assert_noop, assert_ok};
+use pallet_balances::Error as BalancesError;
+
+const SCHEMA: SchemaId = 7;
+
+#[test]
+fn attest_reserves_deposit_and_indexes_both_ways() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Attestations::attest(Origin::signed(1), 2, SCHEMA, b"kyc".to_vec(), Some(10)));
+		assert_eq!(Balances::reserved_balance(1), 13);
+		assert_eq!(
+			Attestations::attestation(&1, &2, SCHEMA),
+			Some(Attestation { data: b"kyc".to_vec(), expiry: Some(10), deposit: 13 }),
+		);
+		assert_eq!(Attestations::outbound(1).len(), 1);
+		assert_eq!(
+			Attestations::inbound(2).into_iter().map(|(i, s, _)| (i, s)).collect::<Vec<_>>(),
+			vec![(1, SCHEMA)],
+		);
+		assert!(Attestations::inbound(1).is_empty());
+		assert_eq!(last_event(), Event::Attestations(crate::Event::Attested(1, 2, SCHEMA)));
+
+		assert_noop!(
+			Attestations::attest(Origin::signed(1), 2, SCHEMA, vec![], None),
+			Error::<Test>::AlreadyAttested,
+		);
+		// the same pair may attest under another schema.
+		assert_ok!(Attestations::attest(Origin::signed(1), 2, SCHEMA + 1, vec![], None));
+		assert_eq!(Attestations::inbound(2).len(), 2);
+	});
+}
+
+#[test]
+fn attest_checks_data_expiry_and_funds() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			Attestations::attest(Origin::signed(1), 2, SCHEMA, vec![0; 17], None),
+			Error::<Test>::DataTooLong,
+		);
+		assert_noop!(
+			Attestations::attest(Origin::signed(1), 2, SCHEMA, vec![], Some(1)),
+			Error::<Test>::ExpiryInPast,
+		);
+		assert_noop!(
+			Attestations::attest(Origin::signed(4), 2, SCHEMA, vec![], None),
+			BalancesError::<Test, _>::InsufficientBalance,
+		);
+	});
+}
+
+#[test]
+fn update_adjusts_deposit() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			Attestations::update(Origin::signed(1), 2, SCHEMA, vec![], None),
+			Error::<Test>::NotFound,
+		);
+		assert_ok!(Attestations::attest(Origin::signed(1), 2, SCHEMA, vec![0; 4], None));
+		assert_eq!(Balances::reserved_balance(1), 14);
+
+		assert_ok!(Attestations::update(Origin::signed(1), 2, SCHEMA, vec![0; 16], Some(5)));
+		assert_eq!(Balances::reserved_balance(1), 26);
+		assert_eq!(Attestations::attestation(&1, &2, SCHEMA).unwrap().expiry, Some(5));
+
+		assert_ok!(Attestations::update(Origin::signed(1), 2, SCHEMA, vec![], None));
+		assert_eq!(Balances::reserved_balance(1), 10);
+		assert_eq!(last_event(), Event::Attestations(crate::Event::Updated(1, 2, SCHEMA)));
+	});
+}
+
+#[test]
+fn revoke_returns_deposit_and_clears_index() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Attestations::attest(Origin::signed(1), 2, SCHEMA, b"kyc".to_vec(), None));
+		// only the issuer can revoke.
+		assert_noop!(Attestations::revoke(Origin::signed(2), 2, SCHEMA), Error::<Test>::NotFound);
+
+		assert_ok!(Attestations::revoke(Origin::signed(1), 2, SCHEMA));
+		assert_eq!(Balances::reserved_balance(1), 0);
+		assert_eq!(Attestations::attestation(&1, &2, SCHEMA), None);
+		assert!(Attestations::inbound(2).is_empty());
+		assert!(Attestations::outbound(1).is_empty());
+		assert_eq!(last_event(), Event::Attestations(crate::Event::Revoked(1, 2, SCHEMA)));
+	});
+}
+
+#[test]
+fn expired_attestations_are_hidden_and_can_be_reaped() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Attestations::attest(Origin::signed(1), 2, SCHEMA, vec![], Some(5)));
+		assert_noop!(Attestations::reap(Origin::signed(3), 1, 2, SCHEMA), Error::<Test>::NotExpired);
+
+		System::set_block_number(5);
+		assert!(Attestations::inbound(2).is_empty());
+		assert!(Attestations::outbound(1).is_empty());
+		assert!(Attestations::attestation(&1, &2, SCHEMA).is_some());
+
+		assert_ok!(Attestations::reap(Origin::signed(3), 1, 2, SCHEMA));
+		assert_eq!(Balances::reserved_balance(1), 0);
+		assert_eq!(Attestations::attestation(&1, &2, SCHEMA), None);
+		assert_eq!(last_event(), Event::Attestations(crate::Event::Reaped(1, 2, SCHEMA)));
+	});
+}
@@ -0,0 +1,60 @@
+// Copyright 2019 TrustBase Network
+// This file is part of TrustBase library.
+//
+// The TrustBase library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Lesser General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// The TrustBase library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU Lesser General Public License for more details.
+//
+// You should have received a copy of the GNU Lesser General Public License
+// along with the TrustBase library. If not, see <http://www.gnu.org/licenses/>.
+
+//! Benchmarks for the authorized upgrade pallet.
+
+use super::*;
+use frame_benchmarking::{benchmarks, impl_benchmark_test_suite};
+use frame_support::traits::EnsureOrigin;
+use frame_support::storage::unhashed;
+use sp_core::storage::well_known_keys;
+
+use crate::Pallet as AuthorizedUpgrade;
+
+const MAX_CODE_SIZE: u32 = 4 * 1024 * 1024;
+
+benchmarks! {
+	authorize_upgrade {
+		let origin = T::AuthorizeOrigin::successful_origin();
+		let code_hash = T::Hashing::hash(&[1u8; 32]);
+	}: _<T::Origin>(origin, code_hash)
+	verify {
+		assert_eq!(AuthorizedCodeHash::<T>::get(), Some(code_hash));
+	}
+
+	// `can_set_code` instantiates the code to read its version, which only succeeds for a runtime
+	// newer than the benchmarked one. Reading the version of the current code costs as much, so the
+	// benchmark does that instead and enacts `c` bytes of unchecked code.
+	enact_authorized_upgrade {
+		let c in 1 .. MAX_CODE_SIZE;
+		let code = vec![1u8; c as usize];
+		AuthorizedCodeHash::<T>::put(T::Hashing::hash(&code));
+		let current = unhashed::get_raw(well_known_keys::CODE).unwrap_or_default();
+	}: {
+		let code_hash = AuthorizedUpgrade::<T>::authorized_hash_of(&code)?;
+		let _ = frame_system::Pallet::<T>::can_set_code(&current);
+		AuthorizedUpgrade::<T>::enact(code_hash, code)?;
+	}
+	verify {
+		assert!(AuthorizedCodeHash::<T>::get().is_none());
+	}
+}
+
+impl_benchmark_test_suite!(
+	AuthorizedUpgrade,
+	crate::mock::new_test_ext(),
+	crate::mock::Test,
+);
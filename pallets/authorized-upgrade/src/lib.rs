@@ -0,0 +1,167 @@
+// Copyright 2019 TrustBase Network
+// This file is part of TrustBase library.
+//
+// The TrustBase library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Lesser General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// The TrustBase library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU Lesser General Public License for more details.
+//
+// You should have received a copy of the GNU Lesser General Public License
+// along with the TrustBase library. If not, see <http://www.gnu.org/licenses/>.
+
+//! # Authorized Upgrade Pallet
+//!
+//! Splits a runtime upgrade into two steps, so that governance does not have to pass the whole
+//! code through a proposal:
+//!
+//! - [`Config::AuthorizeOrigin`] authorizes the upgrade to the code with a given hash.
+//! - anyone can then enact it by submitting that code. The code must raise the `spec_version`
+//!   of the runtime. A successful enactment pays no fee. The enactment is weighted by the length
+//!   of the code, and never weighs more than an operational extrinsic may, so that it can be
+//!   included as a signed extrinsic.
+//!
+//! The pallet is meant to be the `frame_system::Config::OnSetCode` of the runtime, so that any
+//! change of the code, including through `System::set_code`, drops a pending authorization.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use frame_support::{
+	dispatch::{DispatchError, DispatchResult},
+	ensure,
+	traits::Get,
+	weights::{DispatchClass, Weight},
+};
+use frame_system::SetCode;
+use sp_runtime::traits::Hash;
+use sp_std::prelude::*;
+
+pub use pallet::*;
+
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+#[cfg(test)]
+mod mock;
+#[cfg(test)]
+mod tests;
+pub mod weights;
+
+pub use weights::WeightInfo;
+
+#[frame_support::pallet]
+pub mod pallet {
+	use frame_support::pallet_prelude::*;
+	use frame_system::pallet_prelude::*;
+	use super::*;
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		/// The overarching event type.
+		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+
+		/// Origin allowed to authorize upgrades.
+		type AuthorizeOrigin: EnsureOrigin<Self::Origin>;
+
+		/// Weight information for extrinsics in this pallet.
+		type WeightInfo: WeightInfo;
+	}
+
+	#[pallet::pallet]
+	#[pallet::generate_store(pub(super) trait Store)]
+	pub struct Pallet<T>(_);
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {}
+
+	/// The hash of the code of the authorized upgrade.
+	#[pallet::storage]
+	#[pallet::getter(fn authorized_code_hash)]
+	pub type AuthorizedCodeHash<T: Config> = StorageValue<_, T::Hash>;
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	#[pallet::metadata(T::Hash = "Hash")]
+	pub enum Event<T: Config> {
+		/// An upgrade was authorized. \[code_hash\]
+		UpgradeAuthorized(T::Hash),
+		/// The authorized upgrade was enacted. \[code_hash\]
+		UpgradeEnacted(T::Hash),
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// No upgrade is authorized.
+		NothingAuthorized,
+		/// The code does not have the authorized hash.
+		Unauthorized,
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Authorize the upgrade to the code with hash `code_hash`, replacing any previous
+		/// authorization.
+		///
+		/// The dispatch origin for this call must be `AuthorizeOrigin`.
+		#[pallet::weight(T::WeightInfo::authorize_upgrade())]
+		pub fn authorize_upgrade(origin: OriginFor<T>, code_hash: T::Hash) -> DispatchResultWithPostInfo {
+			T::AuthorizeOrigin::ensure_origin(origin)?;
+			AuthorizedCodeHash::<T>::put(code_hash);
+			Self::deposit_event(Event::UpgradeAuthorized(code_hash));
+			Ok(().into())
+		}
+
+		/// Enact the authorized upgrade with its `code`, which must raise the `spec_version` of
+		/// the runtime. No fee is paid if it succeeds.
+		///
+		/// The dispatch origin for this call must be _Signed_.
+		#[pallet::weight((Pallet::<T>::enact_weight(code.len()), DispatchClass::Operational))]
+		pub fn enact_authorized_upgrade(origin: OriginFor<T>, code: Vec<u8>) -> DispatchResultWithPostInfo {
+			ensure_signed(origin)?;
+			let code_hash = Self::authorized_hash_of(&code)?;
+			frame_system::Pallet::<T>::can_set_code(&code)?;
+			Self::enact(code_hash, code)?;
+			Ok(Pays::No.into())
+		}
+	}
+}
+
+impl<T: Config> Pallet<T> {
+	/// The weight of enacting `len` bytes of code, capped to what an operational extrinsic may
+	/// weigh besides its base weight.
+	pub fn enact_weight(len: usize) -> Weight {
+		let block_weights = T::BlockWeights::get();
+		let operational = block_weights.get(DispatchClass::Operational);
+		let max = operational.max_extrinsic
+			.unwrap_or(block_weights.max_block)
+			.saturating_sub(operational.base_extrinsic);
+		T::WeightInfo::enact_authorized_upgrade(len as u32).min(max)
+	}
+
+	/// The hash of `code`, if it is the code of the authorized upgrade.
+	fn authorized_hash_of(code: &[u8]) -> Result<T::Hash, DispatchError> {
+		let code_hash = AuthorizedCodeHash::<T>::get().ok_or(Error::<T>::NothingAuthorized)?;
+		ensure!(T::Hashing::hash(code) == code_hash, Error::<T>::Unauthorized);
+		Ok(code_hash)
+	}
+
+	/// Sets `code`, whose version was checked, as the code of the runtime.
+	fn enact(code_hash: T::Hash, code: Vec<u8>) -> DispatchResult {
+		AuthorizedCodeHash::<T>::kill();
+		<T as frame_system::Config>::OnSetCode::set_code(code)?;
+		Self::deposit_event(Event::UpgradeEnacted(code_hash));
+		Ok(())
+	}
+}
+
+/// Drops the authorized upgrade on every change of the code.
+impl<T: Config> frame_system::SetCode<T> for Pallet<T> {
+	fn set_code(code: Vec<u8>) -> DispatchResult {
+		AuthorizedCodeHash::<T>::kill();
+		frame_system::Pallet::<T>::update_code_in_storage(&code)?;
+		Ok(())
+	}
+}
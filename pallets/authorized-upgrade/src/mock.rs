@@ -0,0 +1,112 @@
+// Copyright 2019 TrustBase Network
+// This file is part of TrustBase library.
+//
+// The TrustBase library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Lesser General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// The TrustBase library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU Lesser General Public License for more details.
+//
+// You should have received a copy of the GNU Lesser General Public License
+// along with the TrustBase library. If not, see <http://www.gnu.org/licenses/>.
+
+//! Test utilities.
+
+use super::*;
+use crate as pallet_authorized_upgrade;
+use codec::Encode;
+use frame_support::{ord_parameter_types, parameter_types};
+use frame_system::EnsureSignedBy;
+use sp_core::H256;
+use sp_runtime::traits::{BlakeTwo256, IdentityLookup};
+use sp_version::RuntimeVersion;
+
+type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Test>;
+type Block = frame_system::mocking::MockBlock<Test>;
+
+frame_support::construct_runtime!(
+	pub enum Test where
+		Block = Block,
+		NodeBlock = Block,
+		UncheckedExtrinsic = UncheckedExtrinsic,
+	{
+		System: frame_system::{Pallet, Call, Config, Storage, Event<T>},
+		AuthorizedUpgrade: pallet_authorized_upgrade::{Pallet, Call, Storage, Event<T>},
+	}
+);
+
+parameter_types! {
+	pub const BlockHashCount: u64 = 250;
+	pub Version: RuntimeVersion = version(1);
+}
+
+impl frame_system::Config for Test {
+	type BaseCallFilter = ();
+	type BlockWeights = ();
+	type BlockLength = ();
+	type DbWeight = ();
+	type Origin = Origin;
+	type Index = u64;
+	type BlockNumber = u64;
+	type Call = Call;
+	type Hash = H256;
+	type Hashing = BlakeTwo256;
+	type AccountId = u64;
+	type Lookup = IdentityLookup<Self::AccountId>;
+	type Header = sp_runtime::testing::Header;
+	type Event = Event;
+	type BlockHashCount = BlockHashCount;
+	type Version = Version;
+	type PalletInfo = PalletInfo;
+	type AccountData = ();
+	type OnNewAccount = ();
+	type OnKilledAccount = ();
+	type SystemWeightInfo = ();
+	type SS58Prefix = ();
+	type OnSetCode = AuthorizedUpgrade;
+}
+
+ord_parameter_types! {
+	pub const Governance: u64 = 1;
+}
+
+impl Config for Test {
+	type Event = Event;
+	type AuthorizeOrigin = EnsureSignedBy<Governance, u64>;
+	type WeightInfo = ();
+}
+
+fn version(spec_version: u32) -> RuntimeVersion {
+	RuntimeVersion { spec_name: "test".into(), spec_version, ..Default::default() }
+}
+
+/// Code of the runtime with `spec_version`. The test externalities read the version of a
+/// runtime from its code, see [`ReadVersionFromCode`].
+pub fn code(spec_version: u32) -> Vec<u8> {
+	version(spec_version).encode()
+}
+
+/// Reads code as the encoded version of the runtime.
+struct ReadVersionFromCode;
+
+impl sp_core::traits::ReadRuntimeVersion for ReadVersionFromCode {
+	fn read_runtime_version(
+		&self,
+		wasm_code: &[u8],
+		_ext: &mut dyn sp_externalities::Externalities,
+	) -> Result<Vec<u8>, String> {
+		Ok(wasm_code.to_vec())
+	}
+}
+
+pub fn new_test_ext() -> sp_io::TestExternalities {
+	let t = frame_system::GenesisConfig::default().build_storage::<Test>().unwrap();
+	let mut ext = sp_io::TestExternalities::new(t);
+	ext.register_extension(sp_core::traits::ReadRuntimeVersionExt::new(ReadVersionFromCode));
+	ext.execute_with(|| System::set_block_number(1));
+	ext
+}
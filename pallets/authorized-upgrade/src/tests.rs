@@ -0,0 +1,118 @@
+// Copyright 2019 TrustBase Network
+// This file is part of TrustBase library.
+//
+// The TrustBase library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Lesser General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// The TrustBase library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU Lesser General Public License for more details.
+//
+// You should have received a copy of the GNU Lesser General Public License
+// along with the TrustBase library. If not, see <http://www.gnu.org/licenses/>.
+
+//! Tests for the authorized upgrade pallet.
+
+use crate::{Error, mock::*};
+use frame_support::{
+	assert_noop, assert_ok,
+	traits::Get,
+	weights::{DispatchClass, GetDispatchInfo, Pays},
+};
+use sp_core::storage::well_known_keys::CODE;
+use sp_runtime::traits::{BadOrigin, BlakeTwo256, Hash};
+
+fn authorize(code: &[u8]) {
+	assert_ok!(AuthorizedUpgrade::authorize_upgrade(Origin::signed(1), BlakeTwo256::hash(code)));
+}
+
+#[test]
+fn only_authorize_origin_can_authorize() {
+	new_test_ext().execute_with(|| {
+		let hash = BlakeTwo256::hash(&code(2));
+		assert_noop!(AuthorizedUpgrade::authorize_upgrade(Origin::signed(2), hash), BadOrigin);
+		assert_ok!(AuthorizedUpgrade::authorize_upgrade(Origin::signed(1), hash));
+		assert_eq!(AuthorizedUpgrade::authorized_code_hash(), Some(hash));
+		assert_eq!(
+			System::events().last().unwrap().event,
+			Event::AuthorizedUpgrade(crate::Event::UpgradeAuthorized(hash)),
+		);
+	});
+}
+
+#[test]
+fn anyone_can_enact_the_authorized_upgrade_for_free() {
+	new_test_ext().execute_with(|| {
+		authorize(&code(2));
+
+		let post_info = AuthorizedUpgrade::enact_authorized_upgrade(Origin::signed(3), code(2)).unwrap();
+		assert_eq!(post_info.pays_fee, Pays::No);
+		assert_eq!(sp_io::storage::get(CODE), Some(code(2)));
+		assert_eq!(AuthorizedUpgrade::authorized_code_hash(), None);
+		assert!(System::events().iter().any(|r| r.event ==
+			Event::AuthorizedUpgrade(crate::Event::UpgradeEnacted(BlakeTwo256::hash(&code(2))))
+		));
+
+		// an authorization is enacted only once.
+		assert_noop!(
+			AuthorizedUpgrade::enact_authorized_upgrade(Origin::signed(3), code(2)),
+			Error::<Test>::NothingAuthorized,
+		);
+	});
+}
+
+#[test]
+fn only_the_authorized_code_can_be_enacted() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			AuthorizedUpgrade::enact_authorized_upgrade(Origin::signed(3), code(2)),
+			Error::<Test>::NothingAuthorized,
+		);
+
+		authorize(&code(2));
+		assert_noop!(
+			AuthorizedUpgrade::enact_authorized_upgrade(Origin::signed(3), code(3)),
+			Error::<Test>::Unauthorized,
+		);
+		assert_noop!(AuthorizedUpgrade::enact_authorized_upgrade(Origin::root(), code(2)), BadOrigin);
+	});
+}
+
+#[test]
+fn enacted_upgrade_must_raise_the_spec_version() {
+	new_test_ext().execute_with(|| {
+		authorize(&code(1));
+		assert_noop!(
+			AuthorizedUpgrade::enact_authorized_upgrade(Origin::signed(3), code(1)),
+			frame_system::Error::<Test>::SpecVersionNeedsToIncrease,
+		);
+		assert!(AuthorizedUpgrade::authorized_code_hash().is_some());
+	});
+}
+
+#[test]
+fn any_code_change_drops_the_authorization() {
+	new_test_ext().execute_with(|| {
+		authorize(&code(3));
+		assert_ok!(System::set_code(Origin::root(), code(2)));
+		assert_eq!(sp_io::storage::get(CODE), Some(code(2)));
+		assert_eq!(AuthorizedUpgrade::authorized_code_hash(), None);
+	});
+}
+
+#[test]
+fn enactment_fits_in_an_operational_extrinsic() {
+	let enact = |len| Call::AuthorizedUpgrade(crate::Call::enact_authorized_upgrade(vec![0; len]))
+		.get_dispatch_info();
+	let block_weights = <Test as frame_system::Config>::BlockWeights::get();
+	let operational = block_weights.get(DispatchClass::Operational);
+	let max = operational.max_extrinsic.unwrap_or(block_weights.max_block);
+
+	let small = enact(1_000);
+	assert_eq!(small.class, DispatchClass::Operational);
+	assert!(small.weight < enact(100_000).weight);
+	assert!(enact(10_000_000).weight + operational.base_extrinsic <= max);
+}
@@ -0,0 +1,52 @@
+// Copyright 2019 TrustBase Network
+// This file is part of TrustBase library.
+//
+// The TrustBase library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Lesser General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// The TrustBase library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU Lesser General Public License for more details.
+//
+// You should have received a copy of the GNU Lesser General Public License
+// along with the TrustBase library. If not, see <http://www.gnu.org/licenses/>.
+
+//! Benchmarks for the fee split pallet.
+
+use super::*;
+use frame_benchmarking::{account, benchmarks, impl_benchmark_test_suite};
+use frame_support::traits::EnsureOrigin;
+
+use crate::Pallet as FeeSplit;
+
+benchmarks! {
+	set_split {
+		let origin = T::SplitOrigin::successful_origin();
+		let split = Split {
+			treasury: Permill::from_percent(40),
+			author: Permill::from_percent(30),
+			burn: Permill::from_percent(20),
+			ecosystem: Permill::from_percent(10),
+		};
+	}: _<T::Origin>(origin, ImbalanceKind::Fee, split)
+	verify {
+		assert_eq!(FeeSplit::<T>::splits().get(ImbalanceKind::Fee), split);
+	}
+
+	set_ecosystem_account {
+		let origin = T::SplitOrigin::successful_origin();
+		let ecosystem: T::AccountId = account("ecosystem", 0, 0);
+	}: _<T::Origin>(origin, Some(ecosystem.clone()))
+	verify {
+		assert_eq!(FeeSplit::<T>::ecosystem_account(), Some(ecosystem));
+	}
+}
+
+impl_benchmark_test_suite!(
+	FeeSplit,
+	crate::mock::new_test_ext(),
+	crate::mock::Test,
+);
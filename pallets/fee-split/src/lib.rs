@@ -0,0 +1,242 @@
+// Copyright 2019 TrustBase Network
+// This file is part of TrustBase library.
+//
+// The TrustBase library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Lesser General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// The TrustBase library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU Lesser General Public License for more details.
+//
+// You should have received a copy of the GNU Lesser General Public License
+// along with the TrustBase library. If not, see <http://www.gnu.org/licenses/>.
+
+//! # Fee Split Pallet
+//!
+//! Governance-set shares in which the runtime hands out the funds it takes out of accounts:
+//! transaction fees, tips, the funds the treasury burns and the dust of reaped accounts. Each
+//! [`ImbalanceKind`] has a [`Split`] between the treasury, the block author, burning and an
+//! ecosystem account.
+//!
+//! [`Config::SplitOrigin`] sets the splits and the ecosystem account. Until it sets them for the
+//! first time, [`Config::DefaultSplits`] apply. While there is no ecosystem account, its share
+//! goes to the treasury. The treasury also receives what rounding leaves of the other shares.
+//!
+//! The runtime passes the funds to [`Pallet::distribute`], e.g. from the fee handler of
+//! `pallet_transaction_payment`, the `BurnDestination` of `pallet_treasury` and the
+//! `DustRemoval` of `pallet_balances`.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::{Encode, Decode};
+use frame_support::traits::{Currency, Imbalance, OnUnbalanced, Get};
+use sp_runtime::{Permill, PerThing, RuntimeDebug};
+
+pub use pallet::*;
+
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+#[cfg(test)]
+mod mock;
+#[cfg(test)]
+mod tests;
+pub mod weights;
+
+pub use weights::WeightInfo;
+
+type NegativeImbalanceOf<T> = <<T as Config>::Currency as Currency<
+	<T as frame_system::Config>::AccountId,
+>>::NegativeImbalance;
+
+/// The kinds of funds whose distribution is set by governance.
+#[derive(Clone, Copy, Encode, Decode, Eq, PartialEq, RuntimeDebug)]
+pub enum ImbalanceKind {
+	/// Transaction fees, without tips.
+	Fee,
+	/// Transaction tips.
+	Tip,
+	/// The funds the treasury burns at the end of a spend period.
+	Burn,
+	/// The balance left in accounts that are reaped.
+	Dust,
+}
+
+/// The shares in which some funds are distributed. They add up to the whole.
+#[derive(Clone, Copy, Encode, Decode, Eq, PartialEq, RuntimeDebug, Default)]
+pub struct Split {
+	/// The share that goes to the treasury.
+	pub treasury: Permill,
+	/// The share that goes to the author of the block.
+	pub author: Permill,
+	/// The share that is burnt.
+	pub burn: Permill,
+	/// The share that goes to the ecosystem account, or to the treasury while there is none.
+	pub ecosystem: Permill,
+}
+
+impl Split {
+	/// Whether the shares add up to the whole.
+	pub fn is_complete(&self) -> bool {
+		let total: u64 = [self.treasury, self.author, self.burn, self.ecosystem].iter()
+			.map(|share| share.deconstruct() as u64)
+			.sum();
+		total == Permill::one().deconstruct() as u64
+	}
+}
+
+/// The split of every [`ImbalanceKind`].
+#[derive(Clone, Copy, Encode, Decode, Eq, PartialEq, RuntimeDebug, Default)]
+pub struct Splits {
+	pub fee: Split,
+	pub tip: Split,
+	pub burn: Split,
+	pub dust: Split,
+}
+
+impl Splits {
+	/// The split of `kind`.
+	pub fn get(&self, kind: ImbalanceKind) -> Split {
+		match kind {
+			ImbalanceKind::Fee => self.fee,
+			ImbalanceKind::Tip => self.tip,
+			ImbalanceKind::Burn => self.burn,
+			ImbalanceKind::Dust => self.dust,
+		}
+	}
+
+	fn get_mut(&mut self, kind: ImbalanceKind) -> &mut Split {
+		match kind {
+			ImbalanceKind::Fee => &mut self.fee,
+			ImbalanceKind::Tip => &mut self.tip,
+			ImbalanceKind::Burn => &mut self.burn,
+			ImbalanceKind::Dust => &mut self.dust,
+		}
+	}
+}
+
+#[frame_support::pallet]
+pub mod pallet {
+	use frame_support::pallet_prelude::*;
+	use frame_system::pallet_prelude::*;
+	use super::*;
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		/// The overarching event type.
+		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+
+		/// The currency the distributed funds are in.
+		type Currency: Currency<Self::AccountId>;
+
+		/// Origin allowed to set the splits and the ecosystem account.
+		type SplitOrigin: EnsureOrigin<Self::Origin>;
+
+		/// Receives the treasury share.
+		type Treasury: OnUnbalanced<NegativeImbalanceOf<Self>>;
+
+		/// Receives the block author share.
+		type Author: OnUnbalanced<NegativeImbalanceOf<Self>>;
+
+		/// The splits that apply until `SplitOrigin` sets them.
+		type DefaultSplits: Get<Splits>;
+
+		/// Weight information for extrinsics in this pallet.
+		type WeightInfo: WeightInfo;
+	}
+
+	#[pallet::pallet]
+	#[pallet::generate_store(pub(super) trait Store)]
+	pub struct Pallet<T>(_);
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {}
+
+	/// The splits set by `SplitOrigin`, if it has set them.
+	#[pallet::storage]
+	pub type CurrentSplits<T> = StorageValue<_, Splits>;
+
+	/// The account that receives the ecosystem share.
+	#[pallet::storage]
+	#[pallet::getter(fn ecosystem_account)]
+	pub type EcosystemAccount<T: Config> = StorageValue<_, T::AccountId>;
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	#[pallet::metadata(T::AccountId = "AccountId")]
+	pub enum Event<T: Config> {
+		/// The split of a kind of funds was set. \[kind, split\]
+		SplitSet(ImbalanceKind, Split),
+		/// The ecosystem account was set or cleared. \[account\]
+		EcosystemAccountSet(Option<T::AccountId>),
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// The shares of the split do not add up to the whole.
+		IncompleteSplit,
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Set the split in which funds of `kind` are distributed.
+		///
+		/// The dispatch origin for this call must be `SplitOrigin`.
+		#[pallet::weight(T::WeightInfo::set_split())]
+		pub fn set_split(
+			origin: OriginFor<T>,
+			kind: ImbalanceKind,
+			split: Split,
+		) -> DispatchResultWithPostInfo {
+			T::SplitOrigin::ensure_origin(origin)?;
+			ensure!(split.is_complete(), Error::<T>::IncompleteSplit);
+
+			let mut splits = Self::splits();
+			*splits.get_mut(kind) = split;
+			CurrentSplits::<T>::put(splits);
+			Self::deposit_event(Event::SplitSet(kind, split));
+			Ok(().into())
+		}
+
+		/// Set the account that receives the ecosystem share, or clear it to send that share to
+		/// the treasury.
+		///
+		/// The dispatch origin for this call must be `SplitOrigin`.
+		#[pallet::weight(T::WeightInfo::set_ecosystem_account())]
+		pub fn set_ecosystem_account(
+			origin: OriginFor<T>,
+			account: Option<T::AccountId>,
+		) -> DispatchResultWithPostInfo {
+			T::SplitOrigin::ensure_origin(origin)?;
+			EcosystemAccount::<T>::set(account.clone());
+			Self::deposit_event(Event::EcosystemAccountSet(account));
+			Ok(().into())
+		}
+	}
+}
+
+impl<T: Config> Pallet<T> {
+	/// The splits in effect.
+	pub fn splits() -> Splits {
+		CurrentSplits::<T>::get().unwrap_or_else(T::DefaultSplits::get)
+	}
+
+	/// Distribute `amount` according to the split of `kind`.
+	pub fn distribute(kind: ImbalanceKind, amount: NegativeImbalanceOf<T>) {
+		let split = Self::splits().get(kind);
+		let total = amount.peek();
+		let (author, rest) = amount.split(split.author * total);
+		let (ecosystem, rest) = rest.split(split.ecosystem * total);
+		// what is left after burning is the treasury share, including rounding.
+		let (_burnt, mut treasury) = rest.split(split.burn * total);
+
+		T::Author::on_unbalanced(author);
+		match EcosystemAccount::<T>::get() {
+			Some(account) => T::Currency::resolve_creating(&account, ecosystem),
+			None => treasury.subsume(ecosystem),
+		}
+		T::Treasury::on_unbalanced(treasury);
+	}
+}
@@ -0,0 +1,104 @@
+// Copyright 2019 TrustBase Network
+// This file is part of TrustBase library.
+//
+// The TrustBase library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Lesser General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// The TrustBase library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU Lesser General Public License for more details.
+//
+// You should have received a copy of the GNU Lesser General Public License
+// along with the TrustBase library. If not, see <http://www.gnu.org/licenses/>.
+
+//! Tests for the fee split pallet.
+
+use crate::{Error, ImbalanceKind, Split, mock::*};
+use frame_support::{assert_noop, assert_ok, traits::Currency};
+use sp_runtime::{Permill, PerThing, traits::BadOrigin};
+
+fn issue(amount: u64) -> NegativeImbalance {
+	Balances::issue(amount)
+}
+
+fn split(treasury: u32, author: u32, burn: u32, ecosystem: u32) -> Split {
+	Split {
+		treasury: Permill::from_percent(treasury),
+		author: Permill::from_percent(author),
+		burn: Permill::from_percent(burn),
+		ecosystem: Permill::from_percent(ecosystem),
+	}
+}
+
+#[test]
+fn default_splits_apply_until_set() {
+	new_test_ext().execute_with(|| {
+		FeeSplit::distribute(ImbalanceKind::Fee, issue(100));
+		FeeSplit::distribute(ImbalanceKind::Tip, issue(10));
+		assert_eq!(Balances::free_balance(TREASURY), 80);
+		assert_eq!(Balances::free_balance(AUTHOR), 20 + 10);
+
+		FeeSplit::distribute(ImbalanceKind::Dust, issue(50));
+		assert_eq!(Balances::total_issuance(), 1_000 + 110);
+	});
+}
+
+#[test]
+fn only_split_origin_can_set_complete_splits() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(FeeSplit::set_split(Origin::signed(1), ImbalanceKind::Fee, split(100, 0, 0, 0)), BadOrigin);
+		assert_noop!(
+			FeeSplit::set_split(Origin::root(), ImbalanceKind::Fee, split(50, 20, 20, 0)),
+			Error::<Test>::IncompleteSplit,
+		);
+		assert_noop!(
+			FeeSplit::set_split(Origin::root(), ImbalanceKind::Fee, split(50, 30, 20, 10)),
+			Error::<Test>::IncompleteSplit,
+		);
+		assert_noop!(FeeSplit::set_ecosystem_account(Origin::signed(1), Some(ECOSYSTEM)), BadOrigin);
+
+		assert_ok!(FeeSplit::set_split(Origin::root(), ImbalanceKind::Dust, split(100, 0, 0, 0)));
+		assert_eq!(FeeSplit::splits().dust, split(100, 0, 0, 0));
+		// the other splits keep their defaults.
+		assert_eq!(FeeSplit::splits().tip.author, Permill::one());
+		assert_eq!(
+			System::events().last().map(|r| r.event.clone()),
+			Some(Event::FeeSplit(crate::Event::SplitSet(ImbalanceKind::Dust, split(100, 0, 0, 0)))),
+		);
+	});
+}
+
+#[test]
+fn funds_are_distributed_by_the_split() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(FeeSplit::set_split(Origin::root(), ImbalanceKind::Fee, split(40, 10, 30, 20)));
+
+		// the ecosystem share goes to the treasury while there is no ecosystem account.
+		FeeSplit::distribute(ImbalanceKind::Fee, issue(1_000));
+		assert_eq!(Balances::free_balance(TREASURY), 400 + 200);
+		assert_eq!(Balances::free_balance(AUTHOR), 100);
+		assert_eq!(Balances::total_issuance(), 1_000 + 700);
+
+		assert_ok!(FeeSplit::set_ecosystem_account(Origin::root(), Some(ECOSYSTEM)));
+		FeeSplit::distribute(ImbalanceKind::Fee, issue(1_000));
+		assert_eq!(Balances::free_balance(TREASURY), 600 + 400);
+		assert_eq!(Balances::free_balance(AUTHOR), 200);
+		assert_eq!(Balances::free_balance(ECOSYSTEM), 200);
+		assert_eq!(Balances::total_issuance(), 1_000 + 700 + 800);
+	});
+}
+
+#[test]
+fn treasury_receives_rounding_remainder() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(FeeSplit::set_split(Origin::root(), ImbalanceKind::Fee, split(34, 33, 33, 0)));
+		FeeSplit::distribute(ImbalanceKind::Fee, issue(10));
+		// author and burn take 3 each, the treasury takes the rest.
+		assert_eq!(Balances::free_balance(AUTHOR), 3);
+		assert_eq!(Balances::free_balance(TREASURY), 4);
+		assert_eq!(Balances::total_issuance(), 1_000 + 7);
+	});
+}
@@ -0,0 +1,233 @@
+// Copyright 2019 TrustBase Network
+// This file is part of TrustBase library.
+//
+// The TrustBase library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Lesser General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// The TrustBase library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU Lesser General Public License for more details.
+//
+// You should have received a copy of the GNU Lesser General Public License
+// along with the TrustBase library. If not, see <http://www.gnu.org/licenses/>.
+
+//! Runtime API definition for summarizing the open governance business.
+//!
+//! The summary covers referenda, public proposals, council and technical committee motions,
+//! treasury proposals, bounties and scheduled calls, with every deadline given as a block number
+//! and the time the block is expected at.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::{Codec, Decode, Encode};
+use sp_runtime::RuntimeDebug;
+use sp_std::vec::Vec;
+
+/// A block, with the time it was produced or is expected to be, in milliseconds since the epoch.
+#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug)]
+pub struct BlockTime<BlockNumber> {
+	/// The block number.
+	pub block: BlockNumber,
+	/// The timestamp of the block, estimated from the expected block time if it is in the future.
+	pub timestamp: u64,
+}
+
+/// The pallet and the function of a call.
+#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug)]
+pub struct CallName {
+	/// The name of the pallet, as in `construct_runtime!`.
+	pub pallet: Vec<u8>,
+	/// The name of the function.
+	pub function: Vec<u8>,
+}
+
+/// How a referendum passes.
+#[derive(Clone, Copy, Encode, Decode, Eq, PartialEq, RuntimeDebug)]
+pub enum VoteThreshold {
+	/// A positive turnout bias, for referenda proposed by the public.
+	SuperMajorityApprove,
+	/// A negative turnout bias, for referenda that default to passing.
+	SuperMajorityAgainst,
+	/// More ayes than nays.
+	SimpleMajority,
+}
+
+/// A referendum that is being voted on.
+#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug)]
+pub struct Referendum<Hash, Balance, BlockNumber> {
+	/// The index of the referendum.
+	pub index: u32,
+	/// The hash of the proposal.
+	pub proposal_hash: Hash,
+	/// The proposed call, if its preimage is noted.
+	pub call: Option<CallName>,
+	/// How the referendum passes.
+	pub threshold: VoteThreshold,
+	/// The conviction weighted ayes.
+	pub ayes: Balance,
+	/// The conviction weighted nays.
+	pub nays: Balance,
+	/// The stake that voted, without conviction.
+	pub turnout: Balance,
+	/// When voting ends.
+	pub end: BlockTime<BlockNumber>,
+	/// The number of blocks between the end of voting and the enactment.
+	pub delay: BlockNumber,
+}
+
+/// A proposal waiting to be launched as a referendum.
+#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug)]
+pub struct PublicProposal<AccountId, Hash, Balance, BlockNumber> {
+	/// The index of the proposal.
+	pub index: u32,
+	/// The hash of the proposal.
+	pub proposal_hash: Hash,
+	/// The proposed call, if its preimage is noted.
+	pub call: Option<CallName>,
+	/// Who made the proposal.
+	pub proposer: AccountId,
+	/// The deposit backing the proposal, per seconder.
+	pub deposit: Balance,
+	/// When the proposal is launched, if it is the most backed one.
+	pub launch: Option<BlockTime<BlockNumber>>,
+}
+
+/// A motion of a collective that is being voted on.
+#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug)]
+pub struct Motion<Hash, BlockNumber> {
+	/// The index of the motion.
+	pub index: u32,
+	/// The hash of the proposal.
+	pub proposal_hash: Hash,
+	/// The proposed call.
+	pub call: CallName,
+	/// The number of ayes needed to pass.
+	pub threshold: u32,
+	/// The number of members voting aye.
+	pub ayes: u32,
+	/// The number of members voting nay.
+	pub nays: u32,
+	/// When the motion can be closed regardless of the votes.
+	pub end: BlockTime<BlockNumber>,
+}
+
+/// A proposal to spend from the treasury.
+#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug)]
+pub struct TreasuryProposal<AccountId, Balance, BlockNumber> {
+	/// The index of the proposal.
+	pub index: u32,
+	/// Who made the proposal.
+	pub proposer: AccountId,
+	/// Who is paid if the proposal is approved.
+	pub beneficiary: AccountId,
+	/// The amount to pay.
+	pub value: Balance,
+	/// The bond of the proposer.
+	pub bond: Balance,
+	/// The next spend period, if the proposal is approved.
+	pub spend: Option<BlockTime<BlockNumber>>,
+}
+
+/// The stage a bounty is at.
+#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug)]
+pub enum BountyStatus<AccountId> {
+	/// Waiting to be approved.
+	Proposed,
+	/// Approved, waiting to be funded.
+	Approved,
+	/// Funded, waiting for a curator.
+	Funded,
+	/// A curator is proposed and has to accept.
+	CuratorProposed {
+		/// The proposed curator.
+		curator: AccountId,
+	},
+	/// A curator works on the bounty.
+	Active {
+		/// The curator.
+		curator: AccountId,
+	},
+	/// The bounty is awarded and waits to be paid out.
+	PendingPayout {
+		/// The curator.
+		curator: AccountId,
+		/// Who is paid.
+		beneficiary: AccountId,
+	},
+}
+
+/// A bounty that isn't paid out yet.
+#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug)]
+pub struct Bounty<AccountId, Balance, BlockNumber> {
+	/// The index of the bounty.
+	pub index: u32,
+	/// The description of the bounty.
+	pub description: Vec<u8>,
+	/// Who proposed the bounty.
+	pub proposer: AccountId,
+	/// The amount paid out, the fee included.
+	pub value: Balance,
+	/// The fee of the curator.
+	pub fee: Balance,
+	/// The bond of the proposer.
+	pub bond: Balance,
+	/// The stage of the bounty.
+	pub status: BountyStatus<AccountId>,
+	/// The next spend period for an approved bounty, the update due of an active one, or the
+	/// payout of an awarded one.
+	pub deadline: Option<BlockTime<BlockNumber>>,
+}
+
+/// A call that is scheduled to be dispatched.
+#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug)]
+pub struct Scheduled<BlockNumber> {
+	/// When the call is dispatched.
+	pub when: BlockTime<BlockNumber>,
+	/// The name of the task, if it is named.
+	pub id: Option<Vec<u8>>,
+	/// The priority of the call within its block, lower first.
+	pub priority: u8,
+	/// The scheduled call.
+	pub call: CallName,
+	/// The period and the number of repetitions left, if the call repeats.
+	pub periodic: Option<(BlockNumber, u32)>,
+}
+
+/// The open governance business, ordered by index or block.
+#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug)]
+pub struct GovernanceOverview<AccountId, Balance, BlockNumber, Hash> {
+	/// The block the overview is taken at.
+	pub now: BlockTime<BlockNumber>,
+	/// The referenda being voted on.
+	pub referenda: Vec<Referendum<Hash, Balance, BlockNumber>>,
+	/// The proposals waiting to be launched.
+	pub public_proposals: Vec<PublicProposal<AccountId, Hash, Balance, BlockNumber>>,
+	/// The motions of the council.
+	pub council_motions: Vec<Motion<Hash, BlockNumber>>,
+	/// The motions of the technical committee.
+	pub technical_committee_motions: Vec<Motion<Hash, BlockNumber>>,
+	/// The treasury proposals, approved or not.
+	pub treasury_proposals: Vec<TreasuryProposal<AccountId, Balance, BlockNumber>>,
+	/// The bounties that aren't paid out.
+	pub bounties: Vec<Bounty<AccountId, Balance, BlockNumber>>,
+	/// The scheduled calls, enactments of referenda included.
+	pub scheduled: Vec<Scheduled<BlockNumber>>,
+	/// The number of stored items left out as the runtime could not decode them, which means
+	/// that its copies of the storage types of the governance pallets are out of date.
+	pub undecodable: u32,
+}
+
+sp_api::decl_runtime_apis! {
+	pub trait GovernanceApi<AccountId, Balance, BlockNumber, Hash> where
+		AccountId: Codec,
+		Balance: Codec,
+		BlockNumber: Codec,
+		Hash: Codec,
+	{
+		/// The open governance business.
+		fn overview() -> GovernanceOverview<AccountId, Balance, BlockNumber, Hash>;
+	}
+}
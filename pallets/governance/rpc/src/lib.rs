@@ -0,0 +1,377 @@
+// Copyright 2019 TrustBase Network
+// This file is part of TrustBase library.
+//
+// The TrustBase library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Lesser General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// The TrustBase library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU Lesser General Public License for more details.
+//
+// You should have received a copy of the GNU Lesser General Public License
+// along with the TrustBase library. If not, see <http://www.gnu.org/licenses/>.
+
+//! RPC interface for summarizing the open governance business.
+
+use std::sync::Arc;
+use std::convert::TryInto;
+use codec::Codec;
+use serde::{Deserialize, Serialize};
+use sp_blockchain::HeaderBackend;
+use jsonrpc_core::{Error as RpcError, ErrorCode, Result};
+use jsonrpc_derive::rpc;
+use sp_core::Bytes;
+use sp_runtime::{generic::BlockId, traits::{Block as BlockT, MaybeDisplay}};
+use sp_api::ProvideRuntimeApi;
+use sp_rpc::number::NumberOrHex;
+pub use pallet_governance_rpc_runtime_api::{
+	BlockTime, GovernanceApi as GovernanceRuntimeApi, VoteThreshold,
+};
+use pallet_governance_rpc_runtime_api as runtime;
+
+/// The pallet and the function of a call.
+#[derive(Clone, Eq, PartialEq, Debug, Serialize, Deserialize)]
+pub struct CallName {
+	/// The name of the pallet.
+	pub pallet: String,
+	/// The name of the function.
+	pub function: String,
+}
+
+impl From<runtime::CallName> for CallName {
+	fn from(call: runtime::CallName) -> Self {
+		CallName {
+			pallet: String::from_utf8_lossy(&call.pallet).into_owned(),
+			function: String::from_utf8_lossy(&call.function).into_owned(),
+		}
+	}
+}
+
+/// The block of a deadline, with the time it is expected at in milliseconds since the epoch.
+#[derive(Clone, Eq, PartialEq, Debug, Serialize, Deserialize)]
+pub struct Deadline<BlockNumber> {
+	/// The block number.
+	pub block: BlockNumber,
+	/// The timestamp of the block.
+	pub timestamp: u64,
+}
+
+impl<BlockNumber> From<BlockTime<BlockNumber>> for Deadline<BlockNumber> {
+	fn from(time: BlockTime<BlockNumber>) -> Self {
+		Deadline { block: time.block, timestamp: time.timestamp }
+	}
+}
+
+/// A referendum that is being voted on.
+#[derive(Clone, Eq, PartialEq, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Referendum<Hash, BlockNumber> {
+	/// The index of the referendum.
+	pub index: u32,
+	/// The hash of the proposal.
+	pub proposal_hash: Hash,
+	/// The proposed call, if its preimage is noted.
+	pub call: Option<CallName>,
+	/// How the referendum passes: `superMajorityApprove`, `superMajorityAgainst` or
+	/// `simpleMajority`.
+	pub threshold: String,
+	/// The conviction weighted ayes.
+	pub ayes: NumberOrHex,
+	/// The conviction weighted nays.
+	pub nays: NumberOrHex,
+	/// The stake that voted, without conviction.
+	pub turnout: NumberOrHex,
+	/// When voting ends.
+	pub end: Deadline<BlockNumber>,
+	/// The number of blocks between the end of voting and the enactment.
+	pub delay: BlockNumber,
+}
+
+/// A proposal waiting to be launched as a referendum.
+#[derive(Clone, Eq, PartialEq, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PublicProposal<AccountId, Hash, BlockNumber> {
+	/// The index of the proposal.
+	pub index: u32,
+	/// The hash of the proposal.
+	pub proposal_hash: Hash,
+	/// The proposed call, if its preimage is noted.
+	pub call: Option<CallName>,
+	/// Who made the proposal.
+	pub proposer: AccountId,
+	/// The deposit backing the proposal, per seconder.
+	pub deposit: NumberOrHex,
+	/// When the proposal is launched, if it is the most backed one.
+	pub launch: Option<Deadline<BlockNumber>>,
+}
+
+/// A motion of a collective that is being voted on.
+#[derive(Clone, Eq, PartialEq, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Motion<Hash, BlockNumber> {
+	/// The index of the motion.
+	pub index: u32,
+	/// The hash of the proposal.
+	pub proposal_hash: Hash,
+	/// The proposed call.
+	pub call: CallName,
+	/// The number of ayes needed to pass.
+	pub threshold: u32,
+	/// The number of members voting aye.
+	pub ayes: u32,
+	/// The number of members voting nay.
+	pub nays: u32,
+	/// When the motion can be closed regardless of the votes.
+	pub end: Deadline<BlockNumber>,
+}
+
+/// A proposal to spend from the treasury.
+#[derive(Clone, Eq, PartialEq, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TreasuryProposal<AccountId, BlockNumber> {
+	/// The index of the proposal.
+	pub index: u32,
+	/// Who made the proposal.
+	pub proposer: AccountId,
+	/// Who is paid if the proposal is approved.
+	pub beneficiary: AccountId,
+	/// The amount to pay.
+	pub value: NumberOrHex,
+	/// The bond of the proposer.
+	pub bond: NumberOrHex,
+	/// The next spend period, if the proposal is approved.
+	pub spend: Option<Deadline<BlockNumber>>,
+}
+
+/// A bounty that isn't paid out yet.
+#[derive(Clone, Eq, PartialEq, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Bounty<AccountId, BlockNumber> {
+	/// The index of the bounty.
+	pub index: u32,
+	/// The description of the bounty.
+	pub description: String,
+	/// Who proposed the bounty.
+	pub proposer: AccountId,
+	/// The amount paid out, the fee included.
+	pub value: NumberOrHex,
+	/// The fee of the curator.
+	pub fee: NumberOrHex,
+	/// The bond of the proposer.
+	pub bond: NumberOrHex,
+	/// The stage of the bounty: `proposed`, `approved`, `funded`, `curatorProposed`, `active` or
+	/// `pendingPayout`.
+	pub status: String,
+	/// The curator, once one is proposed.
+	pub curator: Option<AccountId>,
+	/// Who is paid, once the bounty is awarded.
+	pub beneficiary: Option<AccountId>,
+	/// The next spend period for an approved bounty, the update due of an active one, or the
+	/// payout of an awarded one.
+	pub deadline: Option<Deadline<BlockNumber>>,
+}
+
+/// A call that is scheduled to be dispatched.
+#[derive(Clone, Eq, PartialEq, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Scheduled<BlockNumber> {
+	/// When the call is dispatched.
+	pub when: Deadline<BlockNumber>,
+	/// The name of the task, if it is named.
+	pub id: Option<Bytes>,
+	/// The priority of the call within its block, lower first.
+	pub priority: u8,
+	/// The scheduled call.
+	pub call: CallName,
+	/// The period and the number of repetitions left, if the call repeats.
+	pub periodic: Option<(BlockNumber, u32)>,
+}
+
+/// The open governance business, ordered by index or block.
+#[derive(Clone, Eq, PartialEq, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Overview<AccountId, BlockNumber, Hash> {
+	/// The block the overview is taken at.
+	pub now: Deadline<BlockNumber>,
+	/// The referenda being voted on.
+	pub referenda: Vec<Referendum<Hash, BlockNumber>>,
+	/// The proposals waiting to be launched.
+	pub public_proposals: Vec<PublicProposal<AccountId, Hash, BlockNumber>>,
+	/// The motions of the council.
+	pub council_motions: Vec<Motion<Hash, BlockNumber>>,
+	/// The motions of the technical committee.
+	pub technical_committee_motions: Vec<Motion<Hash, BlockNumber>>,
+	/// The treasury proposals, approved or not.
+	pub treasury_proposals: Vec<TreasuryProposal<AccountId, BlockNumber>>,
+	/// The bounties that aren't paid out.
+	pub bounties: Vec<Bounty<AccountId, BlockNumber>>,
+	/// The scheduled calls, enactments of referenda included.
+	pub scheduled: Vec<Scheduled<BlockNumber>>,
+	/// The number of stored items left out as the runtime could not decode them.
+	pub undecodable: u32,
+}
+
+/// Governance RPC methods.
+#[rpc]
+pub trait GovernanceApi<BlockHash, AccountId, BlockNumber, Hash> {
+	/// The open referenda, proposals, motions, bounties and scheduled calls.
+	#[rpc(name = "governance_overview")]
+	fn overview(&self, at: Option<BlockHash>) -> Result<Overview<AccountId, BlockNumber, Hash>>;
+}
+
+/// A struct that implements the [`GovernanceApi`].
+pub struct Governance<C, P> {
+	client: Arc<C>,
+	_marker: std::marker::PhantomData<P>,
+}
+
+impl<C, P> Governance<C, P> {
+	/// Create new `Governance` with the given reference to the client.
+	pub fn new(client: Arc<C>) -> Self {
+		Self { client, _marker: Default::default() }
+	}
+}
+
+/// Error type of this RPC api.
+pub enum Error {
+	/// The call to runtime failed.
+	RuntimeError,
+}
+
+impl From<Error> for i64 {
+	fn from(e: Error) -> i64 {
+		match e {
+			Error::RuntimeError => 1,
+		}
+	}
+}
+
+fn number<Balance>(value: Balance) -> Result<NumberOrHex> where
+	Balance: MaybeDisplay + Copy + TryInto<NumberOrHex>,
+{
+	value.try_into().map_err(|_| RpcError {
+		code: ErrorCode::InvalidParams,
+		message: format!("{} doesn't fit in NumberOrHex representation", value),
+		data: None,
+	})
+}
+
+fn threshold(threshold: VoteThreshold) -> String {
+	match threshold {
+		VoteThreshold::SuperMajorityApprove => "superMajorityApprove",
+		VoteThreshold::SuperMajorityAgainst => "superMajorityAgainst",
+		VoteThreshold::SimpleMajority => "simpleMajority",
+	}.into()
+}
+
+fn motion<Hash, BlockNumber>(motion: runtime::Motion<Hash, BlockNumber>) -> Motion<Hash, BlockNumber> {
+	Motion {
+		index: motion.index,
+		proposal_hash: motion.proposal_hash,
+		call: motion.call.into(),
+		threshold: motion.threshold,
+		ayes: motion.ayes,
+		nays: motion.nays,
+		end: motion.end.into(),
+	}
+}
+
+fn bounty<AccountId, Balance, BlockNumber>(
+	bounty: runtime::Bounty<AccountId, Balance, BlockNumber>,
+) -> Result<Bounty<AccountId, BlockNumber>> where
+	Balance: MaybeDisplay + Copy + TryInto<NumberOrHex>,
+{
+	use runtime::BountyStatus::*;
+	let (status, curator, beneficiary) = match bounty.status {
+		Proposed => ("proposed", None, None),
+		Approved => ("approved", None, None),
+		Funded => ("funded", None, None),
+		CuratorProposed { curator } => ("curatorProposed", Some(curator), None),
+		Active { curator } => ("active", Some(curator), None),
+		PendingPayout { curator, beneficiary } => ("pendingPayout", Some(curator), Some(beneficiary)),
+	};
+
+	Ok(Bounty {
+		index: bounty.index,
+		description: String::from_utf8_lossy(&bounty.description).into_owned(),
+		proposer: bounty.proposer,
+		value: number(bounty.value)?,
+		fee: number(bounty.fee)?,
+		bond: number(bounty.bond)?,
+		status: status.into(),
+		curator,
+		beneficiary,
+		deadline: bounty.deadline.map(Into::into),
+	})
+}
+
+impl<C, Block, AccountId, Balance, BlockNumber, Hash>
+	GovernanceApi<<Block as BlockT>::Hash, AccountId, BlockNumber, Hash> for Governance<C, Block>
+where
+	Block: BlockT,
+	C: 'static + ProvideRuntimeApi<Block> + HeaderBackend<Block>,
+	C::Api: GovernanceRuntimeApi<Block, AccountId, Balance, BlockNumber, Hash>,
+	AccountId: Codec + Send + Sync + 'static,
+	Balance: Codec + MaybeDisplay + Copy + TryInto<NumberOrHex>,
+	BlockNumber: Codec + Send + Sync + 'static,
+	Hash: Codec + Send + Sync + 'static,
+{
+	fn overview(&self, at: Option<<Block as BlockT>::Hash>) -> Result<Overview<AccountId, BlockNumber, Hash>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(||
+			// If the block hash is not supplied assume the best block.
+			self.client.info().best_hash
+		));
+
+		let overview = api.overview(&at).map_err(|e| RpcError {
+			code: ErrorCode::ServerError(Error::RuntimeError.into()),
+			message: "Unable to query the governance overview.".into(),
+			data: Some(format!("{:?}", e).into()),
+		})?;
+
+		Ok(Overview {
+			now: overview.now.into(),
+			referenda: overview.referenda.into_iter().map(|referendum| Ok(Referendum {
+				index: referendum.index,
+				proposal_hash: referendum.proposal_hash,
+				call: referendum.call.map(Into::into),
+				threshold: threshold(referendum.threshold),
+				ayes: number(referendum.ayes)?,
+				nays: number(referendum.nays)?,
+				turnout: number(referendum.turnout)?,
+				end: referendum.end.into(),
+				delay: referendum.delay,
+			})).collect::<Result<_>>()?,
+			public_proposals: overview.public_proposals.into_iter().map(|proposal| Ok(PublicProposal {
+				index: proposal.index,
+				proposal_hash: proposal.proposal_hash,
+				call: proposal.call.map(Into::into),
+				proposer: proposal.proposer,
+				deposit: number(proposal.deposit)?,
+				launch: proposal.launch.map(Into::into),
+			})).collect::<Result<_>>()?,
+			council_motions: overview.council_motions.into_iter().map(motion).collect(),
+			technical_committee_motions: overview.technical_committee_motions.into_iter().map(motion).collect(),
+			treasury_proposals: overview.treasury_proposals.into_iter().map(|proposal| Ok(TreasuryProposal {
+				index: proposal.index,
+				proposer: proposal.proposer,
+				beneficiary: proposal.beneficiary,
+				value: number(proposal.value)?,
+				bond: number(proposal.bond)?,
+				spend: proposal.spend.map(Into::into),
+			})).collect::<Result<_>>()?,
+			bounties: overview.bounties.into_iter().map(bounty).collect::<Result<_>>()?,
+			scheduled: overview.scheduled.into_iter().map(|scheduled| Scheduled {
+				when: scheduled.when.into(),
+				id: scheduled.id.map(Into::into),
+				priority: scheduled.priority,
+				call: scheduled.call.into(),
+				periodic: scheduled.periodic,
+			}).collect(),
+			undecodable: overview.undecodable,
+		})
+	}
+}
@@ -0,0 +1,36 @@
+// Copyright 2019 TrustBase Network
+// This file is part of TrustBase library.
+//
+// The TrustBase library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Lesser General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// The TrustBase library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU Lesser General Public License for more details.
+//
+// You should have received a copy of the GNU Lesser General Public License
+// along with the TrustBase library. If not, see <http://www.gnu.org/licenses/>.
+
+//! Tests for the migrations pallet.
+
+use crate::{GenesisConfig, mock::*};
+use frame_support::traits::GenesisBuild;
+
+#[test]
+fn genesis_records_the_spec_version_of_the_genesis_runtime() {
+	let mut t = frame_system::GenesisConfig::default().build_storage::<Test>().unwrap();
+	GenesisBuild::<Test>::assimilate_storage(&GenesisConfig::default(), &mut t).unwrap();
+	sp_io::TestExternalities::new(t).execute_with(|| {
+		assert_eq!(Migrations::last_applied(), Some(7));
+	});
+}
+
+#[test]
+fn nothing_is_applied_without_genesis() {
+	sp_io::TestExternalities::default().execute_with(|| {
+		assert_eq!(Migrations::last_applied(), None);
+	});
+}
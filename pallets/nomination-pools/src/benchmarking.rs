@@ -0,0 +1,132 @@
+// Copyright 2019 TrustBase Network
+// This file is part of TrustBase library.
+//
+// The TrustBase library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Lesser General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// The TrustBase library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU Lesser General Public License for more details.
+//
+// You should have received a copy of the GNU Lesser General Public License
+// along with the TrustBase library. If not, see <http://www.gnu.org/licenses/>.
+
+//! Benchmarks for the nomination pools pallet.
+
+use super::*;
+use frame_benchmarking::{account, benchmarks, impl_benchmark_test_suite};
+use frame_system::RawOrigin;
+use sp_runtime::traits::Bounded;
+
+use crate::Pallet as Pools;
+
+const SEED: u32 = 0;
+
+fn funded<T: Config>(name: &'static str, index: u32) -> T::AccountId {
+	let who = account(name, index, SEED);
+	T::Currency::make_free_balance_be(&who, BalanceOf::<T>::max_value() / 1_000_000u32.into());
+	who
+}
+
+fn targets<T: Config>(n: u32) -> Vec<T::AccountId> {
+	(0 .. n).map(|i| account("target", i, SEED)).collect()
+}
+
+/// A pool whose depositor bonds twice the minimum, so that others can unbond alongside.
+fn pool<T: Config>() -> Result<(T::AccountId, PoolId), &'static str> {
+	let depositor = funded::<T>("depositor", 0);
+	let id = NextPoolId::<T>::get();
+	let amount = T::MinCreateBond::get().saturating_mul(2u32.into());
+	Pools::<T>::create(RawOrigin::Signed(depositor.clone()).into(), amount, targets::<T>(1))?;
+	Ok((depositor, id))
+}
+
+/// The amount a member joins with, which it may unbond at once.
+fn join_amount<T: Config>() -> BalanceOf<T> {
+	T::MinJoinBond::get().max(T::MinUnbond::get())
+}
+
+fn member<T: Config>(id: PoolId, index: u32) -> Result<T::AccountId, &'static str> {
+	let member = funded::<T>("member", index);
+	Pools::<T>::join(RawOrigin::Signed(member.clone()).into(), id, join_amount::<T>())?;
+	Ok(member)
+}
+
+/// Pay rewards into the reward account of pool `id`, to be accounted for by the next call.
+fn reward<T: Config>(id: PoolId) {
+	let amount = T::Currency::minimum_balance().max(1u32.into()).saturating_mul(100u32.into());
+	let _ = T::Currency::deposit_creating(&Pools::<T>::reward_account(id), amount);
+}
+
+benchmarks! {
+	create {
+		let n in 1 .. T::Staking::max_nominations();
+		let depositor = funded::<T>("depositor", 0);
+		let id = NextPoolId::<T>::get();
+	}: _(RawOrigin::Signed(depositor), T::MinCreateBond::get(), targets::<T>(n))
+	verify {
+		assert!(Pools::<T>::pool(id).is_some());
+	}
+
+	join {
+		let (_, id) = pool::<T>()?;
+		reward::<T>(id);
+		let joiner = funded::<T>("member", 0);
+	}: _(RawOrigin::Signed(joiner.clone()), id, join_amount::<T>())
+	verify {
+		assert!(Pools::<T>::member(&joiner).is_some());
+	}
+
+	claim_rewards {
+		let (_, id) = pool::<T>()?;
+		let member = member::<T>(id, 0)?;
+		reward::<T>(id);
+	}: _(RawOrigin::Signed(member.clone()))
+	verify {
+		assert!(Pools::<T>::pending_rewards(&member).is_zero());
+	}
+
+	// The era already has stake unbonding, which is rebonded and unbonded again with the new
+	// amount, and the member has rewards to be paid out.
+	unbond {
+		let (_, id) = pool::<T>()?;
+		let member = member::<T>(id, 0)?;
+		let other = member::<T>(id, 1)?;
+		let points = Pools::<T>::member(&other).unwrap().points;
+		Pools::<T>::unbond(RawOrigin::Signed(other).into(), points)?;
+		reward::<T>(id);
+		let points = Pools::<T>::member(&member).unwrap().points;
+	}: _(RawOrigin::Signed(member.clone()), points)
+	verify {
+		assert_eq!(Pools::<T>::member(&member).unwrap().unbonding.len(), 1);
+	}
+
+	// The member withdraws all its unbonded stake and leaves the pool.
+	withdraw_unbonded {
+		let (_, id) = pool::<T>()?;
+		let member = member::<T>(id, 0)?;
+		let points = Pools::<T>::member(&member).unwrap().points;
+		Pools::<T>::unbond(RawOrigin::Signed(member.clone()).into(), points)?;
+		T::Staking::set_current_era(T::Staking::current_era() + T::Staking::bonding_duration());
+	}: _(RawOrigin::Signed(member.clone()))
+	verify {
+		assert!(Pools::<T>::member(&member).is_none());
+	}
+
+	nominate {
+		let n in 1 .. T::Staking::max_nominations();
+		let (depositor, id) = pool::<T>()?;
+	}: _(RawOrigin::Signed(depositor), id, targets::<T>(n))
+	verify {
+		assert_eq!(Pools::<T>::pool(id).unwrap().member_count, 1);
+	}
+}
+
+impl_benchmark_test_suite!(
+	Pools,
+	crate::mock::new_test_ext(),
+	crate::mock::Test,
+);
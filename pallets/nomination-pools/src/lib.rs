@@ -0,0 +1,839 @@
+// Copyright 2019 TrustBase Network
+// This file is part of TrustBase library.
+//
+// The TrustBase library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Lesser General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// The TrustBase library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU Lesser General Public License for more details.
+//
+// You should have received a copy of the GNU Lesser General Public License
+// along with the TrustBase library. If not, see <http://www.gnu.org/licenses/>.
+
+//! # Nomination Pools Pallet
+//!
+//! Pools that bond and nominate with the combined funds of their members, so that accounts too
+//! small to nominate on their own still earn staking rewards.
+//!
+//! Every pool has two keyless accounts: a bonded account, which is the stash and controller of
+//! the pool's stake and nominates on its behalf, and a reward account, which is the payee of that
+//! stake. An account creates a pool with [`Pallet::create`], becoming its depositor: it bonds at
+//! least [`Config::MinCreateBond`], picks the nominations, and may change them later with
+//! [`Pallet::nominate`]. Others join with [`Pallet::join`] and bond at least
+//! [`Config::MinJoinBond`].
+//!
+//! Members hold points in their pool. Joining gives points in proportion to the pool's active
+//! stake, so a slash of the pool lowers the worth of every point alike. Rewards paid into the
+//! reward account are shared by points: each member may claim its share with
+//! [`Pallet::claim_rewards`], and [`Pallet::pending_rewards`] reports it. A member's pending
+//! rewards are also paid out whenever its points change.
+//!
+//! A member leaves by unbonding its points with [`Pallet::unbond`] and withdrawing them with
+//! [`Pallet::withdraw_unbonded`] once the bonding duration has passed. The depositor must keep
+//! the worth of `MinCreateBond` bonded, so a pool is never left without stake, unless it is the
+//! only member left. It may then unbond all its points, which stops the pool's nominations, and
+//! once it withdraws them the pool is destroyed: the depositor gets back the pool deposit and
+//! whatever is left in the accounts of the pool.
+//!
+//! Staking keeps only a few unlocking chunks per stash, so a pool unbonds in one chunk per era:
+//! the stake its members unbond in the same era goes to an unbonding pool of that era, in which
+//! they get points in turn. A slash of the stake unbonding in an era is thus shared by the
+//! members unbonding in that era only. Once the pool unbonds in as many eras as staking keeps
+//! chunks, members can only unbond in the era of the latest chunk, until the earliest one is
+//! done. A member unbonds at least [`Config::MinUnbond`] at once, unless it unbonds all its
+//! points, so that filling the chunks takes a stake in every one of them.
+//!
+//! The creator of a pool reserves [`Config::PoolDeposit`], and every member, the creator
+//! included, reserves [`Config::MemberDeposit`] for as long as it is in a pool.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::{Encode, Decode};
+use frame_support::{
+	dispatch::DispatchResult,
+	traits::{Currency, ExistenceRequirement, Get, ReservableCurrency},
+	PalletId,
+};
+use sp_runtime::{
+	FixedPointNumber, FixedU128, RuntimeDebug, SaturatedConversion,
+	traits::{AccountIdConversion, Saturating, Zero},
+};
+use sp_std::prelude::*;
+
+pub use pallet::*;
+
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+#[cfg(test)]
+mod mock;
+#[cfg(test)]
+mod tests;
+pub mod weights;
+
+pub use weights::WeightInfo;
+
+/// Identifier of a pool.
+pub type PoolId = u32;
+
+/// Counter for the number of eras that have passed.
+pub type EraIndex = u32;
+
+type BalanceOf<T> = <<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
+
+/// A pool as stored by this pallet.
+pub type PoolOf<T> = Pool<<T as frame_system::Config>::AccountId, BalanceOf<T>>;
+
+/// A member as stored by this pallet.
+pub type MemberOf<T> = Member<BalanceOf<T>>;
+
+/// An unbonding pool as stored by this pallet.
+pub type UnbondPoolOf<T> = UnbondPool<BalanceOf<T>>;
+
+/// A nomination pool.
+#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug)]
+pub struct Pool<AccountId, Balance> {
+	/// The account that created the pool, and that nominates for it.
+	pub depositor: AccountId,
+	/// The amount reserved from the depositor for the pool.
+	pub deposit: Balance,
+	/// The points of all members together.
+	pub points: Balance,
+	/// The number of members, the depositor included.
+	pub member_count: u32,
+	/// The rewards earned per point since the pool was created.
+	pub reward_counter: FixedU128,
+	/// The unclaimed rewards in the reward account that `reward_counter` accounts for.
+	pub reward_balance: Balance,
+	/// The eras in which stake of the pool is still unbonding in staking, oldest first.
+	pub unbonding_eras: Vec<EraIndex>,
+}
+
+/// The stake a pool unbonds in one era, shared by points like the pool's active stake.
+#[derive(Clone, Default, Encode, Decode, Eq, PartialEq, RuntimeDebug)]
+pub struct UnbondPool<Balance> {
+	/// The points of all members unbonding in the era.
+	pub points: Balance,
+	/// The stake unbonding in the era. Slashes of it are accounted for whenever more is unbonded
+	/// in the era, and when it is unlocked.
+	pub balance: Balance,
+}
+
+/// A member of a nomination pool.
+#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug)]
+pub struct Member<Balance> {
+	/// The pool the member is in.
+	pub pool_id: PoolId,
+	/// The member's points in the pool.
+	pub points: Balance,
+	/// The pool's `reward_counter` when the member's rewards were last paid out.
+	pub reward_counter: FixedU128,
+	/// The member's points in the unbonding pools, by the era in which they may be withdrawn.
+	pub unbonding: Vec<(EraIndex, Balance)>,
+	/// The amount reserved from the member.
+	pub deposit: Balance,
+}
+
+/// The staking operations a pool performs with its bonded account.
+///
+/// The bonded account is both the stash and the controller of the pool's stake.
+pub trait StakingInterface {
+	/// The account type of the staking system.
+	type AccountId;
+	/// The balance type of the staking system.
+	type Balance;
+
+	/// The number of eras an unbonded amount stays locked.
+	fn bonding_duration() -> EraIndex;
+	/// The current era.
+	fn current_era() -> EraIndex;
+	/// The stake of `stash` that is bonded and not unbonding.
+	fn active_stake(stash: &Self::AccountId) -> Option<Self::Balance>;
+	/// The stake of `stash` that is bonded or unbonding.
+	fn total_stake(stash: &Self::AccountId) -> Option<Self::Balance>;
+	/// The stake of `stash` that is unbonding until `era`.
+	fn unbonding(stash: &Self::AccountId, era: EraIndex) -> Self::Balance;
+	/// Bond `value` of `stash`, paying rewards to `payee`.
+	fn bond(stash: &Self::AccountId, value: Self::Balance, payee: &Self::AccountId) -> DispatchResult;
+	/// Bond `extra` more of `stash`.
+	fn bond_extra(stash: &Self::AccountId, extra: Self::Balance) -> DispatchResult;
+	/// Start unbonding `value` of `stash`.
+	fn unbond(stash: &Self::AccountId, value: Self::Balance) -> DispatchResult;
+	/// Bond `value` of the unbonding stake of `stash` again, latest unbonded first.
+	fn rebond(stash: &Self::AccountId, value: Self::Balance) -> DispatchResult;
+	/// Unlock the stake of `stash` that is done unbonding.
+	fn withdraw_unbonded(stash: &Self::AccountId) -> DispatchResult;
+	/// The number of unlocking chunks a stash may have.
+	fn max_unlocking_chunks() -> u32;
+	/// Nominate `targets` with the stake of `stash`.
+	fn nominate(stash: &Self::AccountId, targets: Vec<Self::AccountId>) -> DispatchResult;
+	/// Stop nominating with the stake of `stash`.
+	fn chill(stash: &Self::AccountId) -> DispatchResult;
+	/// The maximum number of targets a stash may nominate.
+	fn max_nominations() -> u32;
+	/// Make `era` the current era.
+	#[cfg(feature = "runtime-benchmarks")]
+	fn set_current_era(era: EraIndex);
+}
+
+/// The accounts of a pool.
+#[derive(Encode)]
+enum AccountType {
+	Bonded,
+	Reward,
+}
+
+#[frame_support::pallet]
+pub mod pallet {
+	use frame_support::{pallet_prelude::*, transactional};
+	use frame_system::pallet_prelude::*;
+	use super::*;
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		/// The overarching event type.
+		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+
+		/// The currency that is bonded, paid out and reserved.
+		type Currency: ReservableCurrency<Self::AccountId>;
+
+		/// The staking system the pools bond and nominate in.
+		type Staking: StakingInterface<AccountId = Self::AccountId, Balance = BalanceOf<Self>>;
+
+		/// The id from which the accounts of the pools are derived.
+		#[pallet::constant]
+		type PalletId: Get<PalletId>;
+
+		/// The amount reserved from the creator of a pool.
+		#[pallet::constant]
+		type PoolDeposit: Get<BalanceOf<Self>>;
+
+		/// The amount reserved from every member of a pool.
+		#[pallet::constant]
+		type MemberDeposit: Get<BalanceOf<Self>>;
+
+		/// The minimum amount the depositor bonds, and keeps bonded.
+		#[pallet::constant]
+		type MinCreateBond: Get<BalanceOf<Self>>;
+
+		/// The minimum amount a member bonds when joining a pool.
+		#[pallet::constant]
+		type MinJoinBond: Get<BalanceOf<Self>>;
+
+		/// The minimum amount a member unbonds at once, unless it unbonds all its points.
+		#[pallet::constant]
+		type MinUnbond: Get<BalanceOf<Self>>;
+
+		/// The maximum number of eras in which a member may have amounts unbonding.
+		#[pallet::constant]
+		type MaxUnbonding: Get<u32>;
+
+		/// Weight information for extrinsics in this pallet.
+		type WeightInfo: WeightInfo;
+	}
+
+	#[pallet::pallet]
+	#[pallet::generate_store(pub(super) trait Store)]
+	pub struct Pallet<T>(_);
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {}
+
+	/// The id of the next pool.
+	#[pallet::storage]
+	pub type NextPoolId<T> = StorageValue<_, PoolId, ValueQuery>;
+
+	/// The pools, by id.
+	#[pallet::storage]
+	#[pallet::getter(fn pool)]
+	pub type Pools<T: Config> = StorageMap<_, Twox64Concat, PoolId, PoolOf<T>>;
+
+	/// The members of all pools. An account is a member of at most one pool.
+	#[pallet::storage]
+	#[pallet::getter(fn member)]
+	pub type Members<T: Config> = StorageMap<_, Blake2_128Concat, T::AccountId, MemberOf<T>>;
+
+	/// The unbonding pools of the pools, by pool and by the era in which they may be withdrawn.
+	#[pallet::storage]
+	#[pallet::getter(fn unbond_pool)]
+	pub type UnbondPools<T: Config> =
+		StorageDoubleMap<_, Twox64Concat, PoolId, Twox64Concat, EraIndex, UnbondPoolOf<T>>;
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	#[pallet::metadata(T::AccountId = "AccountId", BalanceOf<T> = "Balance")]
+	pub enum Event<T: Config> {
+		/// A pool was created. \[pool, depositor, bonded\]
+		Created(PoolId, T::AccountId, BalanceOf<T>),
+		/// An account joined a pool. \[member, pool, bonded, points\]
+		Joined(T::AccountId, PoolId, BalanceOf<T>, BalanceOf<T>),
+		/// A member was paid out its rewards. \[member, pool, amount\]
+		RewardPaid(T::AccountId, PoolId, BalanceOf<T>),
+		/// A member unbonded some of its points. \[member, pool, amount, withdrawable era\]
+		Unbonded(T::AccountId, PoolId, BalanceOf<T>, EraIndex),
+		/// A member withdrew unbonded funds. \[member, pool, amount\]
+		Withdrawn(T::AccountId, PoolId, BalanceOf<T>),
+		/// A member left its pool. \[member, pool\]
+		Left(T::AccountId, PoolId),
+		/// A pool was destroyed as its depositor left it last. \[pool\]
+		Destroyed(PoolId),
+		/// The depositor of a pool changed its nominations. \[pool\]
+		Nominated(PoolId),
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// There is no pool with this id.
+		PoolNotFound,
+		/// The account is already a member of a pool.
+		AlreadyMember,
+		/// The account is not a member of a pool.
+		NotMember,
+		/// The sender is not the depositor of the pool.
+		NotDepositor,
+		/// The amount is below the minimum bond.
+		MinimumBondNotMet,
+		/// The amount is below the minimum unbond, and not all of the member's points.
+		MinimumUnbondNotMet,
+		/// The pool has no active stake left to join.
+		PoolNotBonded,
+		/// The member does not have that many points.
+		NotEnoughPoints,
+		/// The member already has amounts unbonding in the maximum number of eras.
+		TooManyUnbonding,
+		/// The pool already unbonds in as many eras as staking keeps unlocking chunks. Unbonding
+		/// is possible again once the earliest of them is done.
+		NoMoreChunks,
+		/// None of the member's unbonded amounts may be withdrawn yet.
+		NothingToWithdraw,
+		/// All pool ids are taken.
+		NoAvailableId,
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Create a pool that bonds `amount` of the sender's funds and nominates `targets`.
+		///
+		/// The sender becomes the depositor of the pool, and its first member. It reserves
+		/// `PoolDeposit` and `MemberDeposit`, and also funds the reward account with the
+		/// existential deposit.
+		///
+		/// Emits `Created` with the id of the pool.
+		#[pallet::weight(T::WeightInfo::create(targets.len() as u32))]
+		#[transactional]
+		pub fn create(
+			origin: OriginFor<T>,
+			#[pallet::compact] amount: BalanceOf<T>,
+			targets: Vec<T::AccountId>,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			ensure!(!Members::<T>::contains_key(&who), Error::<T>::AlreadyMember);
+			ensure!(amount >= T::MinCreateBond::get(), Error::<T>::MinimumBondNotMet);
+
+			let id = NextPoolId::<T>::get();
+			let next = id.checked_add(1).ok_or(Error::<T>::NoAvailableId)?;
+			let (bonded, reward) = (Self::bonded_account(id), Self::reward_account(id));
+			let pool_deposit = T::PoolDeposit::get();
+			let member_deposit = T::MemberDeposit::get();
+			T::Currency::reserve(&who, pool_deposit.saturating_add(member_deposit))?;
+			T::Currency::transfer(&who, &bonded, amount, ExistenceRequirement::KeepAlive)?;
+			T::Currency::transfer(
+				&who,
+				&reward,
+				T::Currency::minimum_balance(),
+				ExistenceRequirement::KeepAlive,
+			)?;
+			T::Staking::bond(&bonded, amount, &reward)?;
+			T::Staking::nominate(&bonded, targets)?;
+
+			Pools::<T>::insert(id, Pool {
+				depositor: who.clone(),
+				deposit: pool_deposit,
+				points: amount,
+				member_count: 1,
+				reward_counter: FixedU128::zero(),
+				reward_balance: Zero::zero(),
+				unbonding_eras: Vec::new(),
+			});
+			Members::<T>::insert(&who, Member {
+				pool_id: id,
+				points: amount,
+				reward_counter: FixedU128::zero(),
+				unbonding: Vec::new(),
+				deposit: member_deposit,
+			});
+			NextPoolId::<T>::put(next);
+			Self::deposit_event(Event::Created(id, who, amount));
+			Ok(().into())
+		}
+
+		/// Join pool `pool_id` by bonding `amount` of the sender's funds.
+		///
+		/// The sender reserves `MemberDeposit` and gets points in proportion to the pool's active
+		/// stake.
+		///
+		/// Emits `Joined`.
+		#[pallet::weight(T::WeightInfo::join())]
+		#[transactional]
+		pub fn join(
+			origin: OriginFor<T>,
+			pool_id: PoolId,
+			#[pallet::compact] amount: BalanceOf<T>,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			ensure!(!Members::<T>::contains_key(&who), Error::<T>::AlreadyMember);
+			ensure!(amount >= T::MinJoinBond::get(), Error::<T>::MinimumBondNotMet);
+			let mut pool = Pools::<T>::get(pool_id).ok_or(Error::<T>::PoolNotFound)?;
+			let bonded = Self::bonded_account(pool_id);
+			let active = T::Staking::active_stake(&bonded).unwrap_or_else(Zero::zero);
+			ensure!(!active.is_zero(), Error::<T>::PoolNotBonded);
+
+			// rewards paid so far belong to the existing members.
+			Self::accrue_rewards(pool_id, &mut pool);
+			let points = Self::points_for(&pool, active, amount);
+			let deposit = T::MemberDeposit::get();
+			T::Currency::reserve(&who, deposit)?;
+			T::Currency::transfer(&who, &bonded, amount, ExistenceRequirement::KeepAlive)?;
+			T::Staking::bond_extra(&bonded, amount)?;
+
+			pool.points = pool.points.saturating_add(points);
+			pool.member_count = pool.member_count.saturating_add(1);
+			Members::<T>::insert(&who, Member {
+				pool_id,
+				points,
+				reward_counter: pool.reward_counter,
+				unbonding: Vec::new(),
+				deposit,
+			});
+			Pools::<T>::insert(pool_id, pool);
+			Self::deposit_event(Event::Joined(who, pool_id, amount, points));
+			Ok(().into())
+		}
+
+		/// Pay out the sender's share of the rewards of its pool.
+		///
+		/// Emits `RewardPaid` if there was anything to pay out.
+		#[pallet::weight(T::WeightInfo::claim_rewards())]
+		#[transactional]
+		pub fn claim_rewards(origin: OriginFor<T>) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			let mut member = Members::<T>::get(&who).ok_or(Error::<T>::NotMember)?;
+			let mut pool = Pools::<T>::get(member.pool_id).ok_or(Error::<T>::PoolNotFound)?;
+
+			Self::accrue_rewards(member.pool_id, &mut pool);
+			Self::pay_out(&who, &mut pool, &mut member)?;
+			Pools::<T>::insert(member.pool_id, pool);
+			Members::<T>::insert(&who, member);
+			Ok(().into())
+		}
+
+		/// Unbond `points` of the sender's points, after paying out its rewards.
+		///
+		/// The amount the points are worth goes to the unbonding pool of the era in which it may
+		/// be withdrawn with `withdraw_unbonded`, once the bonding duration has passed. The
+		/// points must be worth `MinUnbond`, or be all of the sender's points. The depositor must
+		/// keep points worth `MinCreateBond`, unless it is the last member: it may then unbond all
+		/// its points, which stops the pool's nominations.
+		///
+		/// Fails with `NoMoreChunks` if the pool can not unbond in another era yet.
+		///
+		/// Emits `Unbonded`.
+		#[pallet::weight(T::WeightInfo::unbond())]
+		#[transactional]
+		pub fn unbond(
+			origin: OriginFor<T>,
+			#[pallet::compact] points: BalanceOf<T>,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			let mut member = Members::<T>::get(&who).ok_or(Error::<T>::NotMember)?;
+			ensure!(points <= member.points, Error::<T>::NotEnoughPoints);
+			let pool_id = member.pool_id;
+			let mut pool = Pools::<T>::get(pool_id).ok_or(Error::<T>::PoolNotFound)?;
+			let bonded = Self::bonded_account(pool_id);
+			let active = T::Staking::active_stake(&bonded).unwrap_or_else(Zero::zero);
+			let amount = Self::balance_for(&pool, active, points);
+			ensure!(
+				amount >= T::MinUnbond::get() || points == member.points,
+				Error::<T>::MinimumUnbondNotMet,
+			);
+			// the depositor may only unbond all its points as the last member.
+			let chills = who == pool.depositor && points == member.points && pool.member_count == 1;
+			if who == pool.depositor && !chills {
+				let remaining = Self::balance_for(&pool, active, member.points.saturating_sub(points));
+				ensure!(remaining >= T::MinCreateBond::get(), Error::<T>::MinimumBondNotMet);
+			}
+
+			Self::accrue_rewards(pool_id, &mut pool);
+			Self::pay_out(&who, &mut pool, &mut member)?;
+
+			let era = T::Staking::current_era().saturating_add(T::Staking::bonding_duration());
+			// the stake already unbonding in the era is rebonded, and unbonded again along with
+			// `amount`, so that the pool has a single unlocking chunk per era.
+			Self::unlock(pool_id, &mut pool, &bonded)?;
+			ensure!(
+				pool.unbonding_eras.last() == Some(&era) ||
+					(pool.unbonding_eras.len() as u32) < T::Staking::max_unlocking_chunks(),
+				Error::<T>::NoMoreChunks,
+			);
+			let unbonding = T::Staking::unbonding(&bonded, era);
+			if !unbonding.is_zero() {
+				T::Staking::rebond(&bonded, unbonding)?;
+			}
+			if chills {
+				T::Staking::chill(&bonded)?;
+			}
+			T::Staking::unbond(&bonded, unbonding.saturating_add(amount))?;
+
+			// a slash of the stake unbonding in the era lowers the worth of its points.
+			let mut unbond_pool = UnbondPools::<T>::get(pool_id, era).unwrap_or_default();
+			unbond_pool.balance = unbonding;
+			let unbond_points = Self::unbond_points_for(&unbond_pool, amount);
+			unbond_pool.points = unbond_pool.points.saturating_add(unbond_points);
+			unbond_pool.balance = unbond_pool.balance.saturating_add(amount);
+			match member.unbonding.last_mut() {
+				Some((last, unbonding)) if *last == era =>
+					*unbonding = unbonding.saturating_add(unbond_points),
+				_ => {
+					ensure!(
+						(member.unbonding.len() as u32) < T::MaxUnbonding::get(),
+						Error::<T>::TooManyUnbonding,
+					);
+					member.unbonding.push((era, unbond_points));
+				},
+			}
+			if pool.unbonding_eras.last() != Some(&era) {
+				pool.unbonding_eras.push(era);
+			}
+
+			member.points = member.points.saturating_sub(points);
+			pool.points = pool.points.saturating_sub(points);
+			UnbondPools::<T>::insert(pool_id, era, unbond_pool);
+			Pools::<T>::insert(pool_id, pool);
+			Members::<T>::insert(&who, member);
+			Self::deposit_event(Event::Unbonded(who, pool_id, amount, era));
+			Ok(().into())
+		}
+
+		/// Withdraw the sender's share of the unbonding pools that are done unbonding.
+		///
+		/// A member without points and without amounts still unbonding leaves its pool, and gets
+		/// its `MemberDeposit` back. When the depositor leaves, which it does last, the pool is
+		/// destroyed: the depositor also gets the `PoolDeposit` back, and all that is left in the
+		/// accounts of the pool.
+		///
+		/// Emits `Withdrawn`, `Left` if the member left, and `Destroyed` if the pool was
+		/// destroyed.
+		#[pallet::weight(T::WeightInfo::withdraw_unbonded())]
+		#[transactional]
+		pub fn withdraw_unbonded(origin: OriginFor<T>) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			let mut member = Members::<T>::get(&who).ok_or(Error::<T>::NotMember)?;
+			let pool_id = member.pool_id;
+			let mut pool = Pools::<T>::get(pool_id).ok_or(Error::<T>::PoolNotFound)?;
+
+			let current_era = T::Staking::current_era();
+			let (withdrawable, unbonding): (Vec<_>, Vec<_>) = member.unbonding
+				.into_iter()
+				.partition(|(era, _)| *era <= current_era);
+			ensure!(!withdrawable.is_empty(), Error::<T>::NothingToWithdraw);
+
+			let bonded = Self::bonded_account(pool_id);
+			Self::unlock(pool_id, &mut pool, &bonded)?;
+			let mut amount: BalanceOf<T> = Zero::zero();
+			for (era, points) in withdrawable {
+				UnbondPools::<T>::mutate_exists(pool_id, era, |maybe_unbond_pool| {
+					if let Some(unbond_pool) = maybe_unbond_pool {
+						let share = Self::unbond_balance_for(unbond_pool, points);
+						unbond_pool.points = unbond_pool.points.saturating_sub(points);
+						unbond_pool.balance = unbond_pool.balance.saturating_sub(share);
+						amount = amount.saturating_add(share);
+						if unbond_pool.points.is_zero() {
+							*maybe_unbond_pool = None;
+						}
+					}
+				});
+			}
+			member.unbonding = unbonding;
+			let leaves = member.points.is_zero() && member.unbonding.is_empty();
+			let destroys = leaves && who == pool.depositor;
+			let existence = if destroys {
+				// including what rounding left of the shares of the other members.
+				amount = T::Currency::free_balance(&bonded);
+				ExistenceRequirement::AllowDeath
+			} else {
+				ExistenceRequirement::KeepAlive
+			};
+			if !amount.is_zero() {
+				T::Currency::transfer(&bonded, &who, amount, existence)?;
+			}
+			Self::deposit_event(Event::Withdrawn(who.clone(), pool_id, amount));
+
+			if leaves {
+				T::Currency::unreserve(&who, member.deposit);
+				pool.member_count = pool.member_count.saturating_sub(1);
+				Members::<T>::remove(&who);
+				Self::deposit_event(Event::Left(who.clone(), pool_id));
+			} else {
+				Members::<T>::insert(&who, member);
+			}
+			if destroys {
+				Self::destroy(pool_id, pool, &who)?;
+			} else {
+				Pools::<T>::insert(pool_id, pool);
+			}
+			Ok(().into())
+		}
+
+		/// Nominate `targets` with the stake of pool `pool_id`.
+		///
+		/// The dispatch origin for this call must be _Signed_ by the depositor of the pool.
+		///
+		/// Emits `Nominated`.
+		#[pallet::weight(T::WeightInfo::nominate(targets.len() as u32))]
+		pub fn nominate(
+			origin: OriginFor<T>,
+			pool_id: PoolId,
+			targets: Vec<T::AccountId>,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			let pool = Pools::<T>::get(pool_id).ok_or(Error::<T>::PoolNotFound)?;
+			ensure!(pool.depositor == who, Error::<T>::NotDepositor);
+
+			T::Staking::nominate(&Self::bonded_account(pool_id), targets)?;
+			Self::deposit_event(Event::Nominated(pool_id));
+			Ok(().into())
+		}
+	}
+}
+
+impl<T: Config> Pallet<T> {
+	/// The account that bonds and nominates for pool `id`.
+	pub fn bonded_account(id: PoolId) -> T::AccountId {
+		T::PalletId::get().into_sub_account((AccountType::Bonded, id))
+	}
+
+	/// The account that receives the staking rewards of pool `id`.
+	pub fn reward_account(id: PoolId) -> T::AccountId {
+		T::PalletId::get().into_sub_account((AccountType::Reward, id))
+	}
+
+	/// The rewards `who` may claim from its pool, or zero if it is not in a pool.
+	pub fn pending_rewards(who: &T::AccountId) -> BalanceOf<T> {
+		Members::<T>::get(who)
+			.and_then(|member| {
+				let mut pool = Pools::<T>::get(member.pool_id)?;
+				Self::accrue_rewards(member.pool_id, &mut pool);
+				Some(Self::pending(&pool, &member))
+			})
+			.unwrap_or_else(Zero::zero)
+	}
+
+	/// Account for the rewards paid into the reward account of `pool` since it was last called.
+	fn accrue_rewards(id: PoolId, pool: &mut PoolOf<T>) {
+		// the existential deposit of the reward account is not a reward.
+		let balance = T::Currency::free_balance(&Self::reward_account(id))
+			.saturating_sub(T::Currency::minimum_balance());
+		let earned = balance.saturating_sub(pool.reward_balance);
+		if !earned.is_zero() && !pool.points.is_zero() {
+			pool.reward_counter = pool.reward_counter.saturating_add(FixedU128::saturating_from_rational(
+				earned.saturated_into::<u128>(),
+				pool.points.saturated_into::<u128>(),
+			));
+		}
+		pool.reward_balance = balance;
+	}
+
+	/// The rewards `member` earned in `pool` since they were last paid out.
+	fn pending(pool: &PoolOf<T>, member: &MemberOf<T>) -> BalanceOf<T> {
+		pool.reward_counter
+			.saturating_sub(member.reward_counter)
+			.saturating_mul_int(member.points.saturated_into::<u128>())
+			.saturated_into::<BalanceOf<T>>()
+			.min(pool.reward_balance)
+	}
+
+	/// Pay out the pending rewards of `member`, who is `who`.
+	fn pay_out(who: &T::AccountId, pool: &mut PoolOf<T>, member: &mut MemberOf<T>) -> DispatchResult {
+		let amount = Self::pending(pool, member);
+		member.reward_counter = pool.reward_counter;
+		if amount.is_zero() {
+			return Ok(())
+		}
+
+		T::Currency::transfer(
+			&Self::reward_account(member.pool_id),
+			who,
+			amount,
+			ExistenceRequirement::KeepAlive,
+		)?;
+		pool.reward_balance = pool.reward_balance.saturating_sub(amount);
+		Self::deposit_event(Event::RewardPaid(who.clone(), member.pool_id, amount));
+		Ok(())
+	}
+
+	/// Unlock the stake of `pool` that is done unbonding, which is whatever its slashes left of
+	/// the stake of its unbonding pools.
+	fn unlock(id: PoolId, pool: &mut PoolOf<T>, bonded: &T::AccountId) -> DispatchResult {
+		let current_era = T::Staking::current_era();
+		let (unlocked, unbonding): (Vec<_>, Vec<_>) = pool.unbonding_eras
+			.drain(..)
+			.partition(|era| *era <= current_era);
+		for era in unlocked {
+			let balance = T::Staking::unbonding(bonded, era);
+			UnbondPools::<T>::mutate(id, era, |unbond_pool| {
+				if let Some(unbond_pool) = unbond_pool {
+					unbond_pool.balance = balance;
+				}
+			});
+		}
+		pool.unbonding_eras = unbonding;
+		T::Staking::withdraw_unbonded(bonded)
+	}
+
+	/// Remove pool `id`, whose depositor just left it as the last member, paying the depositor
+	/// the funds of the reward account and returning the pool deposit. The unbonding pools are
+	/// gone already, as every member withdrew its share of them.
+	fn destroy(id: PoolId, pool: PoolOf<T>, depositor: &T::AccountId) -> DispatchResult {
+		let reward = Self::reward_account(id);
+		T::Currency::transfer(
+			&reward,
+			depositor,
+			T::Currency::free_balance(&reward),
+			ExistenceRequirement::AllowDeath,
+		)?;
+		T::Currency::unreserve(depositor, pool.deposit);
+		Pools::<T>::remove(id);
+		Self::deposit_event(Event::Destroyed(id));
+		Ok(())
+	}
+
+	/// The points that unbonding `amount` in `unbond_pool` is worth.
+	fn unbond_points_for(unbond_pool: &UnbondPoolOf<T>, amount: BalanceOf<T>) -> BalanceOf<T> {
+		if unbond_pool.points.is_zero() || unbond_pool.balance.is_zero() {
+			return amount
+		}
+		FixedU128::saturating_from_rational(
+			unbond_pool.points.saturated_into::<u128>(),
+			unbond_pool.balance.saturated_into::<u128>(),
+		).saturating_mul_int(amount.saturated_into::<u128>()).saturated_into()
+	}
+
+	/// The amount `points` in `unbond_pool` are worth.
+	fn unbond_balance_for(unbond_pool: &UnbondPoolOf<T>, points: BalanceOf<T>) -> BalanceOf<T> {
+		if unbond_pool.points.is_zero() {
+			return Zero::zero()
+		}
+		FixedU128::saturating_from_rational(
+			unbond_pool.balance.saturated_into::<u128>(),
+			unbond_pool.points.saturated_into::<u128>(),
+		).saturating_mul_int(points.saturated_into::<u128>()).saturated_into::<BalanceOf<T>>()
+			.min(unbond_pool.balance)
+	}
+
+	/// The points that bonding `amount` in `pool`, with `active` stake, is worth.
+	fn points_for(pool: &PoolOf<T>, active: BalanceOf<T>, amount: BalanceOf<T>) -> BalanceOf<T> {
+		FixedU128::saturating_from_rational(
+			pool.points.saturated_into::<u128>(),
+			active.saturated_into::<u128>(),
+		).saturating_mul_int(amount.saturated_into::<u128>()).saturated_into()
+	}
+
+	/// The amount `points` in `pool`, with `active` stake, are worth.
+	fn balance_for(pool: &PoolOf<T>, active: BalanceOf<T>, points: BalanceOf<T>) -> BalanceOf<T> {
+		if pool.points.is_zero() {
+			return Zero::zero()
+		}
+		FixedU128::saturating_from_rational(
+			active.saturated_into::<u128>(),
+			pool.points.saturated_into::<u128>(),
+		).saturating_mul_int(points.saturated_into::<u128>()).saturated_into()
+	}
+}
+
+impl<T: pallet_staking::Config> StakingInterface for pallet_staking::Pallet<T> {
+	type AccountId = T::AccountId;
+	type Balance = pallet_staking::BalanceOf<T>;
+
+	fn bonding_duration() -> EraIndex {
+		T::BondingDuration::get()
+	}
+
+	fn current_era() -> EraIndex {
+		Self::current_era().unwrap_or(0)
+	}
+
+	fn active_stake(stash: &T::AccountId) -> Option<Self::Balance> {
+		Self::ledger(stash).map(|ledger| ledger.active)
+	}
+
+	fn total_stake(stash: &T::AccountId) -> Option<Self::Balance> {
+		Self::ledger(stash).map(|ledger| ledger.total)
+	}
+
+	fn unbonding(stash: &T::AccountId, era: EraIndex) -> Self::Balance {
+		Self::ledger(stash)
+			.map(|ledger| ledger.unlocking.iter()
+				.filter(|chunk| chunk.era == era)
+				.fold(Zero::zero(), |total: Self::Balance, chunk| total.saturating_add(chunk.value)))
+			.unwrap_or_else(Zero::zero)
+	}
+
+	fn bond(stash: &T::AccountId, value: Self::Balance, payee: &T::AccountId) -> DispatchResult {
+		use sp_runtime::traits::StaticLookup;
+		Self::bond(
+			frame_system::RawOrigin::Signed(stash.clone()).into(),
+			T::Lookup::unlookup(stash.clone()),
+			value,
+			pallet_staking::RewardDestination::Account(payee.clone()),
+		)
+	}
+
+	fn bond_extra(stash: &T::AccountId, extra: Self::Balance) -> DispatchResult {
+		Self::bond_extra(frame_system::RawOrigin::Signed(stash.clone()).into(), extra)
+	}
+
+	fn unbond(stash: &T::AccountId, value: Self::Balance) -> DispatchResult {
+		Self::unbond(frame_system::RawOrigin::Signed(stash.clone()).into(), value)
+	}
+
+	fn rebond(stash: &T::AccountId, value: Self::Balance) -> DispatchResult {
+		Self::rebond(frame_system::RawOrigin::Signed(stash.clone()).into(), value)
+			.map(|_| ())
+			.map_err(|e| e.error)
+	}
+
+	fn withdraw_unbonded(stash: &T::AccountId) -> DispatchResult {
+		// the spans only matter once the whole stake is withdrawn, i.e. when the pool is destroyed.
+		Self::withdraw_unbonded(frame_system::RawOrigin::Signed(stash.clone()).into(), u32::max_value())
+			.map(|_| ())
+			.map_err(|e| e.error)
+	}
+
+	fn max_unlocking_chunks() -> u32 {
+		pallet_staking::MAX_UNLOCKING_CHUNKS as u32
+	}
+
+	fn nominate(stash: &T::AccountId, targets: Vec<T::AccountId>) -> DispatchResult {
+		use sp_runtime::traits::StaticLookup;
+		Self::nominate(
+			frame_system::RawOrigin::Signed(stash.clone()).into(),
+			targets.into_iter().map(T::Lookup::unlookup).collect(),
+		)
+	}
+
+	fn chill(stash: &T::AccountId) -> DispatchResult {
+		Self::chill(frame_system::RawOrigin::Signed(stash.clone()).into())
+	}
+
+	fn max_nominations() -> u32 {
+		T::MAX_NOMINATIONS
+	}
+
+	#[cfg(feature = "runtime-benchmarks")]
+	fn set_current_era(era: EraIndex) {
+		pallet_staking::CurrentEra::<T>::put(era);
+	}
+}
@@ -0,0 +1,299 @@
+// Copyright 2019 TrustBase Network
+// This file is part of TrustBase library.
+//
+// The TrustBase library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Lesser General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// The TrustBase library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU Lesser General Public License for more details.
+//
+// You should have received a copy of the GNU Lesser General Public License
+// along with the TrustBase library. If not, see <http://www.gnu.org/licenses/>.
+
+//! Test utilities.
+
+use super::*;
+use crate as pallet_nomination_pools;
+use frame_support::{ensure, parameter_types};
+use sp_core::H256;
+use sp_runtime::traits::{BlakeTwo256, IdentityLookup};
+use std::{cell::RefCell, collections::BTreeMap};
+
+type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Test>;
+type Block = frame_system::mocking::MockBlock<Test>;
+
+frame_support::construct_runtime!(
+	pub enum Test where
+		Block = Block,
+		NodeBlock = Block,
+		UncheckedExtrinsic = UncheckedExtrinsic,
+	{
+		System: frame_system::{Pallet, Call, Config, Storage, Event<T>},
+		Balances: pallet_balances::{Pallet, Call, Storage, Config<T>, Event<T>},
+		Pools: pallet_nomination_pools::{Pallet, Call, Storage, Event<T>},
+	}
+);
+
+parameter_types! {
+	pub const BlockHashCount: u64 = 250;
+}
+
+impl frame_system::Config for Test {
+	type BaseCallFilter = ();
+	type BlockWeights = ();
+	type BlockLength = ();
+	type DbWeight = ();
+	type Origin = Origin;
+	type Index = u64;
+	type BlockNumber = u64;
+	type Call = Call;
+	type Hash = H256;
+	type Hashing = BlakeTwo256;
+	type AccountId = u64;
+	type Lookup = IdentityLookup<Self::AccountId>;
+	type Header = sp_runtime::testing::Header;
+	type Event = Event;
+	type BlockHashCount = BlockHashCount;
+	type Version = ();
+	type PalletInfo = PalletInfo;
+	type AccountData = pallet_balances::AccountData<u64>;
+	type OnNewAccount = ();
+	type OnKilledAccount = ();
+	type SystemWeightInfo = ();
+	type SS58Prefix = ();
+	type OnSetCode = ();
+}
+
+parameter_types! {
+	pub const ExistentialDeposit: u64 = 1;
+}
+
+impl pallet_balances::Config for Test {
+	type MaxLocks = ();
+	type MaxReserves = ();
+	type ReserveIdentifier = [u8; 8];
+	type Balance = u64;
+	type DustRemoval = ();
+	type Event = Event;
+	type ExistentialDeposit = ExistentialDeposit;
+	type AccountStore = System;
+	type WeightInfo = ();
+}
+
+/// The stake of a bonded account in [`TestStaking`].
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Ledger {
+	pub active: u64,
+	pub unlocking: Vec<(EraIndex, u64)>,
+	pub payee: u64,
+	pub targets: Vec<u64>,
+}
+
+thread_local! {
+	pub static LEDGERS: RefCell<BTreeMap<u64, Ledger>> = RefCell::new(BTreeMap::new());
+	pub static CURRENT_ERA: RefCell<EraIndex> = RefCell::new(0);
+	pub static BONDING_DURATION_OVERRIDE: RefCell<Option<EraIndex>> = RefCell::new(None);
+	pub static MIN_UNBOND: RefCell<u64> = RefCell::new(1);
+}
+
+pub const BONDING_DURATION: EraIndex = 3;
+
+/// The number of unlocking chunks a ledger may have, as in staking.
+pub const MAX_UNLOCKING_CHUNKS: usize = 32;
+
+/// A staking system that only keeps the ledgers of the bonded accounts, with one unlocking chunk
+/// per unbond like staking.
+pub struct TestStaking;
+impl StakingInterface for TestStaking {
+	type AccountId = u64;
+	type Balance = u64;
+
+	fn bonding_duration() -> EraIndex {
+		BONDING_DURATION_OVERRIDE.with(|d| *d.borrow()).unwrap_or(BONDING_DURATION)
+	}
+
+	fn current_era() -> EraIndex {
+		CURRENT_ERA.with(|era| *era.borrow())
+	}
+
+	fn active_stake(stash: &u64) -> Option<u64> {
+		ledger(*stash).map(|ledger| ledger.active)
+	}
+
+	fn total_stake(stash: &u64) -> Option<u64> {
+		ledger(*stash).map(|ledger| ledger.active + ledger.unlocking.iter().map(|(_, v)| v).sum::<u64>())
+	}
+
+	fn unbonding(stash: &u64, era: EraIndex) -> u64 {
+		ledger(*stash).map_or(0, |ledger| ledger.unlocking.iter().filter(|(e, _)| *e == era).map(|(_, v)| v).sum())
+	}
+
+	fn bond(stash: &u64, value: u64, payee: &u64) -> DispatchResult {
+		ensure!(ledger(*stash).is_none(), "already bonded");
+		ensure!(Balances::free_balance(stash) >= value, "insufficient balance");
+		LEDGERS.with(|l| l.borrow_mut().insert(*stash, Ledger { active: value, payee: *payee, ..Default::default() }));
+		Ok(())
+	}
+
+	fn bond_extra(stash: &u64, extra: u64) -> DispatchResult {
+		let total = Self::total_stake(stash).ok_or("not bonded")?;
+		ensure!(Balances::free_balance(stash) >= total + extra, "insufficient balance");
+		mutate_ledger(*stash, |ledger| ledger.active += extra);
+		Ok(())
+	}
+
+	fn unbond(stash: &u64, value: u64) -> DispatchResult {
+		ensure!(Self::active_stake(stash).ok_or("not bonded")? >= value, "insufficient bond");
+		ensure!(ledger(*stash).unwrap().unlocking.len() < MAX_UNLOCKING_CHUNKS, "no more chunks");
+		let era = Self::current_era() + Self::bonding_duration();
+		mutate_ledger(*stash, |ledger| {
+			ledger.active -= value;
+			ledger.unlocking.push((era, value));
+		});
+		Ok(())
+	}
+
+	fn rebond(stash: &u64, value: u64) -> DispatchResult {
+		ensure!(!ledger(*stash).ok_or("not bonded")?.unlocking.is_empty(), "no unlock chunk");
+		mutate_ledger(*stash, |ledger| {
+			let mut value = value;
+			while let Some((_, chunk)) = ledger.unlocking.last_mut() {
+				let rebonded = value.min(*chunk);
+				*chunk -= rebonded;
+				ledger.active += rebonded;
+				value -= rebonded;
+				if *chunk == 0 {
+					ledger.unlocking.pop();
+				}
+				if value == 0 {
+					break
+				}
+			}
+		});
+		Ok(())
+	}
+
+	fn withdraw_unbonded(stash: &u64) -> DispatchResult {
+		let current_era = Self::current_era();
+		mutate_ledger(*stash, |ledger| ledger.unlocking.retain(|(era, _)| *era > current_era));
+		Ok(())
+	}
+
+	fn max_unlocking_chunks() -> u32 {
+		MAX_UNLOCKING_CHUNKS as u32
+	}
+
+	fn nominate(stash: &u64, targets: Vec<u64>) -> DispatchResult {
+		ensure!(ledger(*stash).is_some(), "not bonded");
+		mutate_ledger(*stash, |ledger| ledger.targets = targets);
+		Ok(())
+	}
+
+	fn chill(stash: &u64) -> DispatchResult {
+		Self::nominate(stash, Vec::new())
+	}
+
+	fn max_nominations() -> u32 {
+		16
+	}
+
+	#[cfg(feature = "runtime-benchmarks")]
+	fn set_current_era(era: EraIndex) {
+		start_era(era);
+	}
+}
+
+pub fn ledger(stash: u64) -> Option<Ledger> {
+	LEDGERS.with(|l| l.borrow().get(&stash).cloned())
+}
+
+fn mutate_ledger(stash: u64, f: impl FnOnce(&mut Ledger)) {
+	LEDGERS.with(|l| l.borrow_mut().get_mut(&stash).map(f));
+}
+
+/// Slash `amount` of the active stake of pool `id`.
+pub fn slash(id: PoolId, amount: u64) {
+	let bonded = Pools::bonded_account(id);
+	mutate_ledger(bonded, |ledger| ledger.active -= amount);
+	let _ = <Balances as Currency<_>>::slash(&bonded, amount);
+}
+
+/// Slash `amount` of the stake of pool `id` that unbonds until `era`.
+pub fn slash_unbonding(id: PoolId, era: EraIndex, amount: u64) {
+	let bonded = Pools::bonded_account(id);
+	mutate_ledger(bonded, |ledger| {
+		let (_, chunk) = ledger.unlocking.iter_mut().find(|(e, _)| *e == era).unwrap();
+		*chunk -= amount;
+	});
+	let _ = <Balances as Currency<_>>::slash(&bonded, amount);
+}
+
+/// Pay `amount` of staking rewards to pool `id`.
+pub fn reward(id: PoolId, amount: u64) {
+	let _ = Balances::deposit_creating(&Pools::reward_account(id), amount);
+}
+
+pub fn start_era(era: EraIndex) {
+	CURRENT_ERA.with(|e| *e.borrow_mut() = era);
+}
+
+/// Let stake unbond for `eras` instead of [`BONDING_DURATION`].
+pub fn set_bonding_duration(eras: EraIndex) {
+	BONDING_DURATION_OVERRIDE.with(|d| *d.borrow_mut() = Some(eras));
+}
+
+pub struct MinUnbond;
+impl Get<u64> for MinUnbond {
+	fn get() -> u64 {
+		MIN_UNBOND.with(|m| *m.borrow())
+	}
+}
+
+pub fn set_min_unbond(amount: u64) {
+	MIN_UNBOND.with(|m| *m.borrow_mut() = amount);
+}
+
+parameter_types! {
+	pub const PoolsPalletId: PalletId = PalletId(*b"py/nopls");
+	pub const PoolDeposit: u64 = 5;
+	pub const MemberDeposit: u64 = 2;
+	pub const MinCreateBond: u64 = 10;
+	pub const MinJoinBond: u64 = 2;
+	pub const MaxUnbonding: u32 = 2;
+}
+
+impl Config for Test {
+	type Event = Event;
+	type Currency = Balances;
+	type Staking = TestStaking;
+	type PalletId = PoolsPalletId;
+	type PoolDeposit = PoolDeposit;
+	type MemberDeposit = MemberDeposit;
+	type MinCreateBond = MinCreateBond;
+	type MinJoinBond = MinJoinBond;
+	type MinUnbond = MinUnbond;
+	type MaxUnbonding = MaxUnbonding;
+	type WeightInfo = ();
+}
+
+pub fn new_test_ext() -> sp_io::TestExternalities {
+	let mut t = frame_system::GenesisConfig::default().build_storage::<Test>().unwrap();
+	pallet_balances::GenesisConfig::<Test> {
+		balances: vec![(1, 100), (2, 100), (3, 100)],
+	}.assimilate_storage(&mut t).unwrap();
+	LEDGERS.with(|l| l.borrow_mut().clear());
+	BONDING_DURATION_OVERRIDE.with(|d| *d.borrow_mut() = None);
+	set_min_unbond(1);
+	start_era(0);
+	let mut ext = sp_io::TestExternalities::new(t);
+	ext.execute_with(|| System::set_block_number(1));
+	ext
+}
+
+pub fn events() -> Vec<Event> {
+	System::events().into_iter().map(|r| r.event).collect()
+}
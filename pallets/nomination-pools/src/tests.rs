@@ -0,0 +1,326 @@
+// Copyright 2019 TrustBase Network
+// This file is part of TrustBase library.
+//
+// The TrustBase library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Lesser General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// The TrustBase library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU Lesser General Public License for more details.
+//
+// You should have received a copy of the GNU Lesser General Public License
+// along with the TrustBase library. If not, see <http://www.gnu.org/licenses/>.
+
+//! Tests for the nomination pools pallet.
+
+use crate::{EraIndex, Error, Member, Pool, UnbondPool, mock::*};
+use frame_support::{assert_noop, assert_ok, traits::Currency};
+use sp_runtime::{FixedU128, traits::Zero};
+
+#[test]
+fn creating_a_pool_bonds_and_nominates_with_deposits() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(Pools::create(Origin::signed(1), 9, vec![10]), Error::<Test>::MinimumBondNotMet);
+		assert_ok!(Pools::create(Origin::signed(1), 20, vec![10, 11]));
+		assert_noop!(Pools::create(Origin::signed(1), 20, vec![10]), Error::<Test>::AlreadyMember);
+
+		assert_eq!(Pools::pool(0), Some(Pool {
+			depositor: 1,
+			deposit: 5,
+			points: 20,
+			member_count: 1,
+			reward_counter: FixedU128::zero(),
+			reward_balance: 0,
+			unbonding_eras: vec![],
+		}));
+		assert_eq!(Pools::member(1), Some(Member {
+			pool_id: 0,
+			points: 20,
+			reward_counter: FixedU128::zero(),
+			unbonding: vec![],
+			deposit: 2,
+		}));
+		assert_eq!(ledger(Pools::bonded_account(0)), Some(Ledger {
+			active: 20,
+			unlocking: vec![],
+			payee: Pools::reward_account(0),
+			targets: vec![10, 11],
+		}));
+		// the pool and member deposits, and the existential deposit of the reward account.
+		assert_eq!(Balances::reserved_balance(1), 5 + 2);
+		assert_eq!(Balances::free_balance(1), 100 - 7 - 20 - 1);
+		assert_eq!(events().last(), Some(&Event::Pools(crate::Event::Created(0, 1, 20))));
+	});
+}
+
+#[test]
+fn members_join_with_points_in_proportion_to_the_active_stake() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Pools::create(Origin::signed(1), 20, vec![10]));
+		assert_noop!(Pools::join(Origin::signed(2), 1, 10), Error::<Test>::PoolNotFound);
+		assert_noop!(Pools::join(Origin::signed(2), 0, 1), Error::<Test>::MinimumBondNotMet);
+		assert_ok!(Pools::join(Origin::signed(2), 0, 10));
+		assert_noop!(Pools::join(Origin::signed(2), 0, 10), Error::<Test>::AlreadyMember);
+		assert_eq!(Pools::member(2).unwrap().points, 10);
+		assert_eq!(Balances::reserved_balance(2), 2);
+
+		// after a slash of half the stake, every point is worth half as much.
+		slash(0, 15);
+		assert_ok!(Pools::join(Origin::signed(3), 0, 10));
+		assert_eq!(Pools::member(3).unwrap().points, 20);
+		assert_eq!(Pools::pool(0).unwrap().points, 50);
+		assert_eq!(Pools::pool(0).unwrap().member_count, 3);
+		assert_eq!(ledger(Pools::bonded_account(0)).unwrap().active, 25);
+		assert_eq!(events().last(), Some(&Event::Pools(crate::Event::Joined(3, 0, 10, 20))));
+	});
+}
+
+#[test]
+fn rewards_are_shared_by_points() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Pools::create(Origin::signed(1), 30, vec![10]));
+		assert_ok!(Pools::join(Origin::signed(2), 0, 10));
+		reward(0, 40);
+		assert_eq!(Pools::pending_rewards(&1), 30);
+		assert_eq!(Pools::pending_rewards(&2), 10);
+
+		assert_ok!(Pools::claim_rewards(Origin::signed(2)));
+		assert_eq!(Balances::free_balance(2), 100 - 2 - 10 + 10);
+		assert_eq!(Pools::pending_rewards(&2), 0);
+		assert_eq!(events().last(), Some(&Event::Pools(crate::Event::RewardPaid(2, 0, 10))));
+
+		// a new member has no share of the rewards paid before it joined.
+		assert_ok!(Pools::join(Origin::signed(3), 0, 20));
+		assert_eq!(Pools::pending_rewards(&3), 0);
+		reward(0, 60);
+		assert_eq!(Pools::pending_rewards(&1), 30 + 30);
+		assert_eq!(Pools::pending_rewards(&2), 10);
+		assert_eq!(Pools::pending_rewards(&3), 20);
+		assert_eq!(Pools::pending_rewards(&4), 0);
+		assert_noop!(Pools::claim_rewards(Origin::signed(4)), Error::<Test>::NotMember);
+	});
+}
+
+#[test]
+fn members_withdraw_after_the_bonding_duration_and_leave() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Pools::create(Origin::signed(1), 20, vec![10]));
+		assert_ok!(Pools::join(Origin::signed(2), 0, 10));
+		reward(0, 30);
+		assert_noop!(Pools::unbond(Origin::signed(2), 11), Error::<Test>::NotEnoughPoints);
+
+		// unbonding pays out the pending rewards first.
+		assert_ok!(Pools::unbond(Origin::signed(2), 10));
+		assert!(events().contains(&Event::Pools(crate::Event::RewardPaid(2, 0, 10))));
+		assert_eq!(events().last(), Some(&Event::Pools(crate::Event::Unbonded(2, 0, 10, BONDING_DURATION))));
+		assert_eq!(Pools::member(2).unwrap().unbonding, vec![(BONDING_DURATION, 10)]);
+		assert_eq!(ledger(Pools::bonded_account(0)).unwrap().unlocking, vec![(BONDING_DURATION, 10)]);
+		assert_noop!(Pools::withdraw_unbonded(Origin::signed(2)), Error::<Test>::NothingToWithdraw);
+
+		start_era(BONDING_DURATION);
+		assert_ok!(Pools::withdraw_unbonded(Origin::signed(2)));
+		assert_eq!(Pools::member(2), None);
+		assert_eq!(Pools::pool(0).unwrap().member_count, 1);
+		assert_eq!(Balances::reserved_balance(2), 0);
+		assert_eq!(Balances::free_balance(2), 100 + 10);
+		assert_eq!(Balances::free_balance(Pools::bonded_account(0)), 20);
+		assert_eq!(events().last(), Some(&Event::Pools(crate::Event::Left(2, 0))));
+	});
+}
+
+#[test]
+fn the_depositor_keeps_the_minimum_bond_and_unbonds_in_few_eras() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Pools::create(Origin::signed(1), 20, vec![10]));
+		assert_noop!(Pools::unbond(Origin::signed(1), 11), Error::<Test>::MinimumBondNotMet);
+		assert_ok!(Pools::unbond(Origin::signed(1), 5));
+		assert_ok!(Pools::unbond(Origin::signed(1), 2));
+		assert_eq!(Pools::member(1).unwrap().unbonding, vec![(3, 7)]);
+		assert_eq!(ledger(Pools::bonded_account(0)).unwrap().unlocking, vec![(3, 7)]);
+
+		start_era(1);
+		assert_ok!(Pools::unbond(Origin::signed(1), 1));
+		assert_eq!(Pools::member(1).unwrap().unbonding, vec![(3, 7), (4, 1)]);
+		start_era(2);
+		assert_noop!(Pools::unbond(Origin::signed(1), 1), Error::<Test>::TooManyUnbonding);
+
+		start_era(3);
+		assert_ok!(Pools::withdraw_unbonded(Origin::signed(1)));
+		assert_eq!(Pools::member(1).unwrap().unbonding, vec![(4, 1)]);
+		assert_eq!(events().last(), Some(&Event::Pools(crate::Event::Withdrawn(1, 0, 7))));
+	});
+}
+
+#[test]
+fn the_depositor_destroys_the_pool_by_leaving_it_last() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Pools::create(Origin::signed(1), 20, vec![10]));
+		assert_ok!(Pools::join(Origin::signed(2), 0, 10));
+		assert_noop!(Pools::unbond(Origin::signed(1), 20), Error::<Test>::MinimumBondNotMet);
+		assert_ok!(Pools::unbond(Origin::signed(2), 10));
+		start_era(3);
+		assert_ok!(Pools::withdraw_unbonded(Origin::signed(2)));
+
+		// as the last member, the depositor unbonds all and stops nominating.
+		reward(0, 4);
+		assert_ok!(Pools::unbond(Origin::signed(1), 20));
+		assert_eq!(ledger(Pools::bonded_account(0)).unwrap().targets, Vec::<u64>::new());
+		assert_noop!(Pools::join(Origin::signed(3), 0, 10), Error::<Test>::PoolNotBonded);
+
+		start_era(6);
+		assert_ok!(Pools::withdraw_unbonded(Origin::signed(1)));
+		assert_eq!(Pools::pool(0), None);
+		assert_eq!(Pools::member(1), None);
+		assert_eq!(Balances::reserved_balance(1), 0);
+		assert_eq!(Balances::free_balance(1), 100 + 4);
+		assert_eq!(Balances::free_balance(Pools::bonded_account(0)), 0);
+		assert_eq!(Balances::free_balance(Pools::reward_account(0)), 0);
+		assert!(events().contains(&Event::Pools(crate::Event::Left(1, 0))));
+		assert_eq!(events().last(), Some(&Event::Pools(crate::Event::Destroyed(0))));
+	});
+}
+
+#[test]
+fn a_pool_unbonds_in_one_chunk_per_era() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Pools::create(Origin::signed(1), 80, vec![10]));
+		assert_ok!(Pools::join(Origin::signed(2), 0, 40));
+		// more unbonds in one era than staking keeps unlocking chunks.
+		for _ in 0..MAX_UNLOCKING_CHUNKS + 8 {
+			assert_ok!(Pools::unbond(Origin::signed(1), 1));
+			assert_ok!(Pools::unbond(Origin::signed(2), 1));
+		}
+		assert_eq!(ledger(Pools::bonded_account(0)).unwrap().unlocking, vec![(3, 80)]);
+		assert_eq!(Pools::unbond_pool(0, 3), Some(UnbondPool { points: 80, balance: 80 }));
+		assert_eq!(Pools::member(1).unwrap().unbonding, vec![(3, 40)]);
+		assert_eq!(Pools::member(2).unwrap().unbonding, vec![(3, 40)]);
+		assert_eq!(Pools::pool(0).unwrap().unbonding_eras, vec![3]);
+
+		start_era(1);
+		assert_ok!(Pools::unbond(Origin::signed(1), 1));
+		assert_eq!(ledger(Pools::bonded_account(0)).unwrap().unlocking, vec![(3, 80), (4, 1)]);
+		assert_eq!(Pools::pool(0).unwrap().unbonding_eras, vec![3, 4]);
+
+		start_era(3);
+		assert_ok!(Pools::withdraw_unbonded(Origin::signed(2)));
+		assert_eq!(Balances::free_balance(2), 100);
+		assert_eq!(ledger(Pools::bonded_account(0)).unwrap().unlocking, vec![(4, 1)]);
+		assert_eq!(Pools::pool(0).unwrap().unbonding_eras, vec![4]);
+		assert_eq!(Pools::unbond_pool(0, 3), Some(UnbondPool { points: 40, balance: 40 }));
+	});
+}
+
+#[test]
+fn a_pool_unbonds_in_at_most_as_many_eras_as_staking_keeps_chunks() {
+	new_test_ext().execute_with(|| {
+		set_bonding_duration(40);
+		assert_ok!(Pools::create(Origin::signed(1), 20, vec![10]));
+		assert_ok!(Pools::join(Origin::signed(2), 0, 10));
+		// every member unbonds in at most `MaxUnbonding` eras, so filling the chunks takes many.
+		let members: Vec<u64> = (10..10 + MAX_UNLOCKING_CHUNKS as u64 / 2).collect();
+		for member in &members {
+			let _ = Balances::deposit_creating(member, 100);
+			assert_ok!(Pools::join(Origin::signed(*member), 0, 10));
+		}
+		for era in 0..MAX_UNLOCKING_CHUNKS as EraIndex {
+			start_era(era);
+			assert_ok!(Pools::unbond(Origin::signed(members[era as usize / 2]), 1));
+		}
+		let bonded = Pools::bonded_account(0);
+		assert_eq!(ledger(bonded).unwrap().unlocking.len(), MAX_UNLOCKING_CHUNKS);
+
+		// the era of the latest chunk still takes unbonds, a new era does not.
+		assert_ok!(Pools::unbond(Origin::signed(2), 1));
+		let latest = MAX_UNLOCKING_CHUNKS as EraIndex - 1;
+		start_era(latest + 1);
+		assert_noop!(Pools::unbond(Origin::signed(2), 1), Error::<Test>::NoMoreChunks);
+
+		// once the earliest chunk is done, its place is free again.
+		start_era(40);
+		assert_ok!(Pools::unbond(Origin::signed(2), 1));
+		assert_eq!(ledger(bonded).unwrap().unlocking.len(), MAX_UNLOCKING_CHUNKS);
+		assert_eq!(Pools::pool(0).unwrap().unbonding_eras.first(), Some(&41));
+		assert_eq!(Pools::pool(0).unwrap().unbonding_eras.last(), Some(&80));
+	});
+}
+
+#[test]
+fn members_unbond_at_least_the_minimum_or_all_their_points() {
+	new_test_ext().execute_with(|| {
+		set_min_unbond(5);
+		assert_ok!(Pools::create(Origin::signed(1), 20, vec![10]));
+		assert_ok!(Pools::join(Origin::signed(2), 0, 12));
+		assert_noop!(Pools::unbond(Origin::signed(2), 4), Error::<Test>::MinimumUnbondNotMet);
+		assert_ok!(Pools::unbond(Origin::signed(2), 9));
+		assert_noop!(Pools::unbond(Origin::signed(2), 2), Error::<Test>::MinimumUnbondNotMet);
+		assert_ok!(Pools::unbond(Origin::signed(2), 3));
+		assert_eq!(Pools::member(2).unwrap().points, 0);
+		assert_eq!(Pools::member(2).unwrap().unbonding, vec![(BONDING_DURATION, 12)]);
+	});
+}
+
+#[test]
+fn slashes_of_unbonding_stake_are_shared_by_the_members_unbonding_in_that_era() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Pools::create(Origin::signed(1), 20, vec![10]));
+		assert_ok!(Pools::join(Origin::signed(2), 0, 10));
+		assert_ok!(Pools::join(Origin::signed(3), 0, 10));
+		assert_ok!(Pools::unbond(Origin::signed(2), 10));
+		start_era(1);
+		assert_ok!(Pools::unbond(Origin::signed(3), 10));
+
+		// half the stake unbonding in the first era is slashed.
+		slash_unbonding(0, 3, 5);
+		start_era(4);
+		assert_ok!(Pools::withdraw_unbonded(Origin::signed(2)));
+		assert!(events().contains(&Event::Pools(crate::Event::Withdrawn(2, 0, 5))));
+		assert_ok!(Pools::withdraw_unbonded(Origin::signed(3)));
+		assert!(events().contains(&Event::Pools(crate::Event::Withdrawn(3, 0, 10))));
+
+		assert_eq!(Balances::free_balance(2), 100 - 10 + 5);
+		assert_eq!(Balances::free_balance(3), 100);
+		assert_eq!(Balances::free_balance(Pools::bonded_account(0)), 20);
+		assert_eq!(Pools::unbond_pool(0, 3), None);
+		assert_eq!(Pools::unbond_pool(0, 4), None);
+	});
+}
+
+#[test]
+fn points_unbonded_after_a_slash_of_the_era_are_worth_their_amount() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Pools::create(Origin::signed(1), 20, vec![10]));
+		assert_ok!(Pools::join(Origin::signed(2), 0, 10));
+		assert_ok!(Pools::join(Origin::signed(3), 0, 10));
+		assert_ok!(Pools::unbond(Origin::signed(2), 10));
+		slash_unbonding(0, 3, 5);
+
+		// the stake unbonding in the era is worth half its points by now.
+		assert_ok!(Pools::unbond(Origin::signed(3), 10));
+		assert_eq!(Pools::member(3).unwrap().unbonding, vec![(3, 20)]);
+		assert_eq!(Pools::unbond_pool(0, 3), Some(UnbondPool { points: 30, balance: 15 }));
+		assert_eq!(ledger(Pools::bonded_account(0)).unwrap().unlocking, vec![(3, 15)]);
+
+		start_era(3);
+		assert_ok!(Pools::withdraw_unbonded(Origin::signed(3)));
+		assert_ok!(Pools::withdraw_unbonded(Origin::signed(2)));
+		assert_eq!(Balances::free_balance(2), 100 - 10 + 5);
+		assert_eq!(Balances::free_balance(3), 100);
+	});
+}
+
+#[test]
+fn only_the_depositor_nominates() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Pools::create(Origin::signed(1), 20, vec![10]));
+		assert_ok!(Pools::join(Origin::signed(2), 0, 10));
+		assert_noop!(Pools::nominate(Origin::signed(2), 0, vec![12]), Error::<Test>::NotDepositor);
+		assert_noop!(Pools::nominate(Origin::signed(1), 1, vec![12]), Error::<Test>::PoolNotFound);
+
+		assert_ok!(Pools::nominate(Origin::signed(1), 0, vec![12]));
+		assert_eq!(ledger(Pools::bonded_account(0)).unwrap().targets, vec![12]);
+		assert_eq!(events().last(), Some(&Event::Pools(crate::Event::Nominated(0))));
+	});
+}
@@ -0,0 +1,41 @@
+// Copyright 2019 TrustBase Network
+// This file is part of TrustBase library.
+//
+// The TrustBase library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Lesser General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// The TrustBase library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU Lesser General Public License for more details.
+//
+// You should have received a copy of the GNU Lesser General Public License
+// along with the TrustBase library. If not, see <http://www.gnu.org/licenses/>.
+
+//! Runtime API definition for listing the runtime parameters.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::{Decode, Encode};
+use sp_runtime::RuntimeDebug;
+use sp_std::vec::Vec;
+
+/// A runtime parameter with its value in effect.
+#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug)]
+pub struct ParameterInfo {
+	/// The name of the parameter.
+	pub name: Vec<u8>,
+	/// The SCALE encoded key of the parameter.
+	pub key: Vec<u8>,
+	/// The SCALE encoded value of the parameter.
+	pub value: Vec<u8>,
+}
+
+sp_api::decl_runtime_apis! {
+	pub trait ParametersApi {
+		/// Every parameter with its value in effect, in declaration order.
+		fn parameters() -> Vec<ParameterInfo>;
+	}
+}
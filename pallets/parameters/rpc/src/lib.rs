@@ -0,0 +1,106 @@
+// Copyright 2019 TrustBase Network
+// This file is part of TrustBase library.
+//
+// The TrustBase library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Lesser General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// The TrustBase library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU Lesser General Public License for more details.
+//
+// You should have received a copy of the GNU Lesser General Public License
+// along with the TrustBase library. If not, see <http://www.gnu.org/licenses/>.
+
+//! RPC interface for listing the runtime parameters.
+
+use std::sync::Arc;
+use jsonrpc_core::{Error as RpcError, ErrorCode, Result};
+use jsonrpc_derive::rpc;
+use serde::{Deserialize, Serialize};
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_core::Bytes;
+use sp_runtime::{generic::BlockId, traits::Block as BlockT};
+pub use pallet_parameters_rpc_runtime_api::{ParameterInfo, ParametersApi as ParametersRuntimeApi};
+
+/// A runtime parameter with its value in effect.
+#[derive(Clone, Eq, PartialEq, Debug, Serialize, Deserialize)]
+pub struct Parameter {
+	/// The name of the parameter.
+	pub name: String,
+	/// The SCALE encoded key of the parameter.
+	pub key: Bytes,
+	/// The SCALE encoded value of the parameter.
+	pub value: Bytes,
+}
+
+impl From<ParameterInfo> for Parameter {
+	fn from(info: ParameterInfo) -> Self {
+		Parameter {
+			name: String::from_utf8_lossy(&info.name).into_owned(),
+			key: info.key.into(),
+			value: info.value.into(),
+		}
+	}
+}
+
+/// Parameters RPC methods.
+#[rpc]
+pub trait ParametersApi<BlockHash> {
+	/// Every runtime parameter with its value in effect, in declaration order.
+	#[rpc(name = "parameters_list")]
+	fn list(&self, at: Option<BlockHash>) -> Result<Vec<Parameter>>;
+}
+
+/// A struct that implements the [`ParametersApi`].
+pub struct Parameters<C, P> {
+	client: Arc<C>,
+	_marker: std::marker::PhantomData<P>,
+}
+
+impl<C, P> Parameters<C, P> {
+	/// Create new `Parameters` with the given reference to the client.
+	pub fn new(client: Arc<C>) -> Self {
+		Self { client, _marker: Default::default() }
+	}
+}
+
+/// Error type of this RPC api.
+pub enum Error {
+	/// The call to runtime failed.
+	RuntimeError,
+}
+
+impl From<Error> for i64 {
+	fn from(e: Error) -> i64 {
+		match e {
+			Error::RuntimeError => 1,
+		}
+	}
+}
+
+impl<C, Block> ParametersApi<<Block as BlockT>::Hash> for Parameters<C, Block>
+where
+	Block: BlockT,
+	C: 'static + ProvideRuntimeApi<Block> + HeaderBackend<Block>,
+	C::Api: ParametersRuntimeApi<Block>,
+{
+	fn list(&self, at: Option<<Block as BlockT>::Hash>) -> Result<Vec<Parameter>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(||
+			// If the block hash is not supplied assume the best block.
+			self.client.info().best_hash
+		));
+
+		let parameters = api.parameters(&at).map_err(|e| RpcError {
+			code: ErrorCode::ServerError(Error::RuntimeError.into()),
+			message: "Unable to query the runtime parameters.".into(),
+			data: Some(format!("{:?}", e).into()),
+		})?;
+
+		Ok(parameters.into_iter().map(Into::into).collect())
+	}
+}
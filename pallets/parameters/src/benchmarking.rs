@@ -0,0 +1,53 @@
+// Copyright 2019 TrustBase Network
+// This file is part of TrustBase library.
+//
+// The TrustBase library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Lesser General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// The TrustBase library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU Lesser General Public License for more details.
+//
+// You should have received a copy of the GNU Lesser General Public License
+// along with the TrustBase library. If not, see <http://www.gnu.org/licenses/>.
+
+//! Benchmarks for the parameters pallet.
+
+use super::*;
+use frame_benchmarking::{benchmarks, impl_benchmark_test_suite};
+
+use crate::Pallet as Parameters;
+
+/// The first parameter, with its default as a valid value.
+fn parameter<T: Config>() -> Result<(T::Key, T::Value), &'static str> {
+	let key = T::Schema::keys().into_iter().next().ok_or("the runtime declares no parameters")?;
+	Ok((key, T::Schema::default_value(key)))
+}
+
+benchmarks! {
+	set_parameter {
+		let (key, value) = parameter::<T>()?;
+		let origin = T::Schema::successful_origin(key);
+	}: _<T::Origin>(origin, key, value.clone())
+	verify {
+		assert_eq!(Values::<T>::get(key), Some(value));
+	}
+
+	reset_parameter {
+		let (key, value) = parameter::<T>()?;
+		Values::<T>::insert(key, value);
+		let origin = T::Schema::successful_origin(key);
+	}: _<T::Origin>(origin, key)
+	verify {
+		assert!(!Values::<T>::contains_key(key));
+	}
+}
+
+impl_benchmark_test_suite!(
+	Parameters,
+	crate::mock::new_test_ext(),
+	crate::mock::Test,
+);
@@ -0,0 +1,300 @@
+// Copyright 2019 TrustBase Network
+// This file is part of TrustBase library.
+//
+// The TrustBase library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Lesser General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// The TrustBase library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU Lesser General Public License for more details.
+//
+// You should have received a copy of the GNU Lesser General Public License
+// along with the TrustBase library. If not, see <http://www.gnu.org/licenses/>.
+
+//! # Parameters Pallet
+//!
+//! Economic values of the runtime that governance can change without a runtime upgrade.
+//!
+//! The parameters are declared in the runtime with [`define_parameters`]. Every parameter has a
+//! typed value, a default, the range its value must lie in and the origin that may set it. The
+//! macro also declares a `Get` implementation per parameter reading its value in effect, which
+//! takes the place of a `parameter_types!` constant in the `Config` of other pallets.
+//!
+//! A parameter that was never set, or was reset, has its default value.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use sp_runtime::traits::BadOrigin;
+use sp_std::prelude::*;
+
+pub use pallet::*;
+
+#[doc(hidden)]
+pub use codec;
+#[doc(hidden)]
+pub use frame_support;
+#[doc(hidden)]
+pub use frame_system;
+#[doc(hidden)]
+pub use sp_runtime;
+#[doc(hidden)]
+pub use sp_std;
+
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+#[cfg(test)]
+mod mock;
+#[cfg(test)]
+mod tests;
+pub mod weights;
+
+pub use weights::WeightInfo;
+
+/// The declaration of the parameters of a runtime, usually implemented by [`define_parameters`].
+pub trait ParameterSchema<Origin, Key, Value> {
+	/// All keys, in declaration order.
+	fn keys() -> Vec<Key>;
+
+	/// The name of the parameter at `key`.
+	fn name(key: Key) -> &'static str;
+
+	/// The value of the parameter at `key` while it is not set.
+	fn default_value(key: Key) -> Value;
+
+	/// Whether `value` is of the type of the parameter at `key` and within its bounds.
+	fn is_valid(key: Key, value: &Value) -> bool;
+
+	/// Ensure that `origin` may set the parameter at `key`.
+	fn ensure_origin(key: Key, origin: Origin) -> Result<(), BadOrigin>;
+
+	/// An origin that may set the parameter at `key`.
+	#[cfg(feature = "runtime-benchmarks")]
+	fn successful_origin(key: Key) -> Origin;
+}
+
+#[frame_support::pallet]
+pub mod pallet {
+	use frame_support::pallet_prelude::*;
+	use frame_system::pallet_prelude::*;
+	use super::*;
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		/// The overarching event type.
+		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+
+		/// The key of a parameter.
+		type Key: Parameter + Member + Copy;
+
+		/// The value of a parameter.
+		type Value: Parameter + Member;
+
+		/// The defaults, bounds and origins of the parameters.
+		type Schema: ParameterSchema<Self::Origin, Self::Key, Self::Value>;
+
+		/// Weight information for extrinsics in this pallet.
+		type WeightInfo: WeightInfo;
+	}
+
+	#[pallet::pallet]
+	#[pallet::generate_store(pub(super) trait Store)]
+	pub struct Pallet<T>(_);
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {}
+
+	/// The values set by governance, by key.
+	#[pallet::storage]
+	pub type Values<T: Config> = StorageMap<_, Twox64Concat, T::Key, T::Value>;
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	#[pallet::metadata(T::Key = "ParameterKey", T::Value = "ParameterValue")]
+	pub enum Event<T: Config> {
+		/// A parameter was set. \[key, value\]
+		ParameterSet(T::Key, T::Value),
+		/// A parameter was reset to its default. \[key\]
+		ParameterReset(T::Key),
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// The value is not of the type of the parameter or out of its bounds.
+		InvalidValue,
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Set the parameter at `key` to `value`.
+		///
+		/// The dispatch origin for this call must be the origin of the parameter.
+		#[pallet::weight(T::WeightInfo::set_parameter())]
+		pub fn set_parameter(
+			origin: OriginFor<T>,
+			key: T::Key,
+			value: T::Value,
+		) -> DispatchResultWithPostInfo {
+			T::Schema::ensure_origin(key, origin)?;
+			ensure!(T::Schema::is_valid(key, &value), Error::<T>::InvalidValue);
+
+			Values::<T>::insert(key, &value);
+			Self::deposit_event(Event::ParameterSet(key, value));
+			Ok(().into())
+		}
+
+		/// Reset the parameter at `key` to its default.
+		///
+		/// The dispatch origin for this call must be the origin of the parameter.
+		#[pallet::weight(T::WeightInfo::reset_parameter())]
+		pub fn reset_parameter(origin: OriginFor<T>, key: T::Key) -> DispatchResultWithPostInfo {
+			T::Schema::ensure_origin(key, origin)?;
+
+			Values::<T>::remove(key);
+			Self::deposit_event(Event::ParameterReset(key));
+			Ok(().into())
+		}
+	}
+}
+
+impl<T: Config> Pallet<T> {
+	/// The value in effect of the parameter at `key`.
+	pub fn value(key: T::Key) -> T::Value {
+		Values::<T>::get(key).unwrap_or_else(|| T::Schema::default_value(key))
+	}
+
+	/// The value in effect of every parameter, in declaration order.
+	pub fn parameters() -> Vec<(T::Key, T::Value)> {
+		T::Schema::keys().into_iter().map(|key| (key, Self::value(key))).collect()
+	}
+}
+
+/// Declare the parameters of a runtime.
+///
+/// ```ignore
+/// define_parameters! {
+/// 	pub enum ParameterKey, ParameterValue, RuntimeParameters for Runtime {
+/// 		/// The fee per byte of a transaction.
+/// 		TransactionByteFee: Balance = 10 * MILLICENTS, MILLICENTS..=CENTS, EnsureRoot<AccountId>;
+/// 	}
+/// }
+/// ```
+///
+/// declares
+/// - the enum `ParameterKey`, with a variant per parameter.
+/// - the enum `ParameterValue`, with a variant per parameter holding a value of its type.
+/// - the struct `RuntimeParameters`, implementing [`ParameterSchema`] for them. A value must
+///   lie in the range of its parameter and can only be set by the origin of its parameter.
+/// - a struct per parameter, named like it, that implements `Get` of its value in effect. Its
+///   `DEFAULT` is the value while the parameter is not set.
+///
+/// `Runtime` must implement [`Config`] with these `Key`, `Value` and `Schema`.
+///
+/// Under the `runtime-benchmarks` feature of the invoking crate, the schema also provides an
+/// origin that may set every parameter. The crate must then enable the `runtime-benchmarks`
+/// feature of this pallet.
+#[macro_export]
+macro_rules! define_parameters {
+	(
+		pub enum $key:ident, $value:ident, $schema:ident for $runtime:ty {
+			$(
+				$( #[$attr:meta] )*
+				$name:ident: $type:ty = $default:expr, $range:expr, $origin:ty;
+			)*
+		}
+	) => {
+		/// The key of a runtime parameter.
+		#[derive(
+			Clone, Copy, Eq, PartialEq,
+			$crate::codec::Encode, $crate::codec::Decode, $crate::sp_runtime::RuntimeDebug,
+		)]
+		pub enum $key {
+			$( $( #[$attr] )* $name, )*
+		}
+
+		/// The value of a runtime parameter.
+		#[derive(
+			Clone, Eq, PartialEq,
+			$crate::codec::Encode, $crate::codec::Decode, $crate::sp_runtime::RuntimeDebug,
+		)]
+		pub enum $value {
+			$( $( #[$attr] )* $name($type), )*
+		}
+
+		/// The defaults, bounds and origins of the runtime parameters.
+		pub struct $schema;
+
+		impl $crate::ParameterSchema<<$runtime as $crate::frame_system::Config>::Origin, $key, $value>
+			for $schema
+		{
+			fn keys() -> $crate::sp_std::vec::Vec<$key> {
+				$crate::sp_std::vec![$( $key::$name ),*]
+			}
+
+			fn name(key: $key) -> &'static str {
+				match key {
+					$( $key::$name => stringify!($name), )*
+				}
+			}
+
+			fn default_value(key: $key) -> $value {
+				match key {
+					$( $key::$name => $value::$name($name::DEFAULT), )*
+				}
+			}
+
+			#[allow(unreachable_patterns)]
+			fn is_valid(key: $key, value: &$value) -> bool {
+				match (key, value) {
+					$( ($key::$name, $value::$name(value)) => ($range).contains(value), )*
+					_ => false,
+				}
+			}
+
+			fn ensure_origin(
+				key: $key,
+				origin: <$runtime as $crate::frame_system::Config>::Origin,
+			) -> Result<(), $crate::sp_runtime::traits::BadOrigin> {
+				use $crate::frame_support::traits::EnsureOrigin;
+				match key {
+					$( $key::$name => <$origin>::ensure_origin(origin).map(|_| ()), )*
+				}
+			}
+
+			#[cfg(feature = "runtime-benchmarks")]
+			fn successful_origin(key: $key) -> <$runtime as $crate::frame_system::Config>::Origin {
+				use $crate::frame_support::traits::EnsureOrigin;
+				match key {
+					$( $key::$name => <$origin>::successful_origin(), )*
+				}
+			}
+		}
+
+		$(
+			$( #[$attr] )*
+			pub struct $name;
+
+			impl $name {
+				/// The value while the parameter is not set.
+				pub const DEFAULT: $type = $default;
+
+				/// The value in effect.
+				#[allow(unreachable_patterns)]
+				pub fn get() -> $type {
+					match $crate::Pallet::<$runtime>::value($key::$name) {
+						$value::$name(value) => value,
+						_ => Self::DEFAULT,
+					}
+				}
+			}
+
+			impl<I: From<$type>> $crate::frame_support::traits::Get<I> for $name {
+				fn get() -> I {
+					I::from(Self::get())
+				}
+			}
+		)*
+	}
+}
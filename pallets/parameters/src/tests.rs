@@ -0,0 +1,103 @@
+// Copyright 2019 TrustBase Network
+// This file is part of TrustBase library.
+//
+// The TrustBase library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Lesser General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// The TrustBase library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU Lesser General Public License for more details.
+//
+// You should have received a copy of the GNU Lesser General Public License
+// along with the TrustBase library. If not, see <http://www.gnu.org/licenses/>.
+
+//! Tests for the parameters pallet.
+
+use crate::{Error, ParameterSchema, mock::*};
+use frame_support::{assert_noop, assert_ok, traits::Get};
+use sp_runtime::traits::BadOrigin;
+
+#[test]
+fn parameters_have_their_default_until_set() {
+	new_test_ext().execute_with(|| {
+		assert_eq!(Fee::get(), 10);
+		assert_eq!(<Period as Get<u64>>::get(), 5);
+		assert_eq!(Parameters::parameters(), vec![
+			(ParameterKey::Fee, ParameterValue::Fee(10)),
+			(ParameterKey::Period, ParameterValue::Period(5)),
+		]);
+
+		assert_ok!(Parameters::set_parameter(Origin::root(), ParameterKey::Fee, ParameterValue::Fee(20)));
+		assert_eq!(Fee::get(), 20);
+		assert_eq!(Period::get(), 5);
+		assert_eq!(
+			System::events().last().unwrap().event,
+			Event::Parameters(crate::Event::ParameterSet(ParameterKey::Fee, ParameterValue::Fee(20))),
+		);
+
+		assert_ok!(Parameters::reset_parameter(Origin::root(), ParameterKey::Fee));
+		assert_eq!(Fee::get(), 10);
+		assert_eq!(
+			System::events().last().unwrap().event,
+			Event::Parameters(crate::Event::ParameterReset(ParameterKey::Fee)),
+		);
+	});
+}
+
+#[test]
+fn every_parameter_has_its_own_origin() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			Parameters::set_parameter(Origin::signed(1), ParameterKey::Fee, ParameterValue::Fee(20)),
+			BadOrigin,
+		);
+		assert_noop!(
+			Parameters::set_parameter(Origin::root(), ParameterKey::Period, ParameterValue::Period(6)),
+			BadOrigin,
+		);
+		assert_noop!(
+			Parameters::set_parameter(Origin::signed(2), ParameterKey::Period, ParameterValue::Period(6)),
+			BadOrigin,
+		);
+		assert_noop!(Parameters::reset_parameter(Origin::signed(1), ParameterKey::Fee), BadOrigin);
+
+		assert_ok!(Parameters::set_parameter(Origin::signed(1), ParameterKey::Period, ParameterValue::Period(6)));
+		assert_eq!(Period::get(), 6);
+		assert_ok!(Parameters::reset_parameter(Origin::signed(1), ParameterKey::Period));
+		assert_eq!(Period::get(), 5);
+	});
+}
+
+#[test]
+fn values_must_be_typed_and_in_bounds() {
+	new_test_ext().execute_with(|| {
+		// the value of another parameter.
+		assert_noop!(
+			Parameters::set_parameter(Origin::root(), ParameterKey::Fee, ParameterValue::Period(20)),
+			Error::<Test>::InvalidValue,
+		);
+		assert_noop!(
+			Parameters::set_parameter(Origin::root(), ParameterKey::Fee, ParameterValue::Fee(0)),
+			Error::<Test>::InvalidValue,
+		);
+		assert_noop!(
+			Parameters::set_parameter(Origin::root(), ParameterKey::Fee, ParameterValue::Fee(101)),
+			Error::<Test>::InvalidValue,
+		);
+
+		// the bounds are inclusive.
+		assert_ok!(Parameters::set_parameter(Origin::root(), ParameterKey::Fee, ParameterValue::Fee(1)));
+		assert_ok!(Parameters::set_parameter(Origin::root(), ParameterKey::Fee, ParameterValue::Fee(100)));
+	});
+}
+
+#[test]
+fn schema_is_derived_from_the_declaration() {
+	assert_eq!(TestParameters::keys(), vec![ParameterKey::Fee, ParameterKey::Period]);
+	assert_eq!(TestParameters::name(ParameterKey::Fee), "Fee");
+	assert_eq!(TestParameters::name(ParameterKey::Period), "Period");
+	assert_eq!(TestParameters::default_value(ParameterKey::Period), ParameterValue::Period(Period::DEFAULT));
+}
@@ -0,0 +1,75 @@
+// Copyright 2019 TrustBase Network
+// This file is part of TrustBase library.
+//
+// The TrustBase library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Lesser General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// The TrustBase library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU Lesser General Public License for more details.
+//
+// You should have received a copy of the GNU Lesser General Public License
+// along with the TrustBase library. If not, see <http://www.gnu.org/licenses/>.
+
+//! Benchmarks for the safe mode pallet.
+
+use super::*;
+use frame_benchmarking::{benchmarks, impl_benchmark_test_suite};
+use frame_support::traits::EnsureOrigin;
+
+use crate::Pallet as SafeMode;
+
+/// A name of the longest length, which is not the name of an unpausable pallet.
+fn name<T: Config>() -> Vec<u8> {
+	let name = vec![b'z'; T::MaxNameLen::get() as usize];
+	assert!(!T::UnpausablePallets::get().contains(&name));
+	name
+}
+
+benchmarks! {
+	pause_pallet {
+		let origin = T::PauseOrigin::successful_origin();
+		let pallet = name::<T>();
+	}: _<T::Origin>(origin, pallet.clone())
+	verify {
+		assert!(PausedPallets::<T>::contains_key(&pallet));
+	}
+
+	pause_call {
+		let origin = T::PauseOrigin::successful_origin();
+		let pallet = name::<T>();
+		let call = name::<T>();
+	}: _<T::Origin>(origin, pallet.clone(), call.clone())
+	verify {
+		assert!(PausedCalls::<T>::contains_key(&pallet, &call));
+	}
+
+	unpause_pallet {
+		let origin = T::UnpauseOrigin::successful_origin();
+		let pallet = name::<T>();
+		SafeMode::<T>::pause_pallet(T::PauseOrigin::successful_origin(), pallet.clone())?;
+	}: _<T::Origin>(origin, pallet.clone())
+	verify {
+		assert!(!PausedPallets::<T>::contains_key(&pallet));
+	}
+
+	unpause_call {
+		let origin = T::UnpauseOrigin::successful_origin();
+		let pallet = name::<T>();
+		let call = name::<T>();
+		let pause_origin = T::PauseOrigin::successful_origin();
+		SafeMode::<T>::pause_call(pause_origin, pallet.clone(), call.clone())?;
+	}: _<T::Origin>(origin, pallet.clone(), call.clone())
+	verify {
+		assert!(!PausedCalls::<T>::contains_key(&pallet, &call));
+	}
+}
+
+impl_benchmark_test_suite!(
+	SafeMode,
+	crate::mock::new_test_ext(),
+	crate::mock::Test,
+);
@@ -0,0 +1,225 @@
+// Copyright 2019 TrustBase Network
+// This file is part of TrustBase library.
+//
+// The TrustBase library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Lesser General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// The TrustBase library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU Lesser General Public License for more details.
+//
+// You should have received a copy of the GNU Lesser General Public License
+// along with the TrustBase library. If not, see <http://www.gnu.org/licenses/>.
+
+//! # Safe Mode Pallet
+//!
+//! An emergency brake for the runtime: [`Config::PauseOrigin`] can pause all calls of a pallet,
+//! or a single call, by name. A pause lifts by itself after [`Config::PauseDuration`] blocks, or
+//! earlier through [`Config::UnpauseOrigin`].
+//!
+//! The pallet is a [`Filter`] on the runtime `Call` and takes effect once it is part of
+//! `frame_system::Config::BaseCallFilter`. The base filter is applied to every dispatch that
+//! is not made by root, so paused calls are also rejected when they are nested inside calls
+//! like `Utility::batch`, `Proxy::proxy` or `Multisig::as_multi`.
+//!
+//! The pallets named in [`Config::UnpausablePallets`] can never be paused. These should at least
+//! include the pallets providing inherents, governance and this pallet itself.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use frame_support::traits::{Filter, Get, GetCallMetadata};
+use sp_std::prelude::*;
+
+pub use pallet::*;
+
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+#[cfg(test)]
+mod mock;
+#[cfg(test)]
+mod tests;
+pub mod weights;
+
+pub use weights::WeightInfo;
+
+#[frame_support::pallet]
+pub mod pallet {
+	use frame_support::pallet_prelude::*;
+	use frame_system::pallet_prelude::*;
+	use super::*;
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		/// The overarching event type.
+		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+
+		/// Origin allowed to pause pallets and calls.
+		type PauseOrigin: EnsureOrigin<Self::Origin>;
+
+		/// Origin allowed to lift a pause before it expires.
+		type UnpauseOrigin: EnsureOrigin<Self::Origin>;
+
+		/// The number of blocks after which a pause lifts by itself.
+		#[pallet::constant]
+		type PauseDuration: Get<Self::BlockNumber>;
+
+		/// The names of the pallets that can never be paused.
+		type UnpausablePallets: Get<Vec<Vec<u8>>>;
+
+		/// The maximum length of a pallet or call name.
+		#[pallet::constant]
+		type MaxNameLen: Get<u32>;
+
+		/// Weight information for extrinsics in this pallet.
+		type WeightInfo: WeightInfo;
+	}
+
+	#[pallet::pallet]
+	#[pallet::generate_store(pub(super) trait Store)]
+	pub struct Pallet<T>(_);
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {}
+
+	/// The block at which the pause of all calls of a pallet lifts, by pallet name.
+	#[pallet::storage]
+	#[pallet::getter(fn paused_pallet)]
+	pub type PausedPallets<T: Config> = StorageMap<_, Blake2_128Concat, Vec<u8>, T::BlockNumber>;
+
+	/// The block at which the pause of a single call lifts, by pallet name and call name.
+	#[pallet::storage]
+	#[pallet::getter(fn paused_call)]
+	pub type PausedCalls<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat, Vec<u8>,
+		Blake2_128Concat, Vec<u8>,
+		T::BlockNumber,
+	>;
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	#[pallet::metadata(T::BlockNumber = "BlockNumber")]
+	pub enum Event<T: Config> {
+		/// All calls of a pallet were paused. \[pallet, until\]
+		PalletPaused(Vec<u8>, T::BlockNumber),
+		/// A pallet is no longer paused. \[pallet\]
+		PalletUnpaused(Vec<u8>),
+		/// A call was paused. \[pallet, call, until\]
+		CallPaused(Vec<u8>, Vec<u8>, T::BlockNumber),
+		/// A call is no longer paused. \[pallet, call\]
+		CallUnpaused(Vec<u8>, Vec<u8>),
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// A pallet or call name is longer than `MaxNameLen`.
+		NameTooLong,
+		/// The pallet is in `UnpausablePallets`.
+		Unpausable,
+		/// The pallet or call is not paused.
+		NotPaused,
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Pause all calls of `pallet` for `PauseDuration` blocks. Pausing a paused pallet
+		/// restarts its pause.
+		///
+		/// The dispatch origin for this call must be `PauseOrigin`.
+		#[pallet::weight(T::WeightInfo::pause_pallet())]
+		pub fn pause_pallet(origin: OriginFor<T>, pallet: Vec<u8>) -> DispatchResultWithPostInfo {
+			T::PauseOrigin::ensure_origin(origin)?;
+			Self::ensure_pausable(&pallet)?;
+
+			let until = Self::pause_end();
+			PausedPallets::<T>::insert(&pallet, until);
+			Self::deposit_event(Event::PalletPaused(pallet, until));
+			Ok(().into())
+		}
+
+		/// Pause the call named `call` of `pallet` for `PauseDuration` blocks. Pausing a paused
+		/// call restarts its pause.
+		///
+		/// The dispatch origin for this call must be `PauseOrigin`.
+		#[pallet::weight(T::WeightInfo::pause_call())]
+		pub fn pause_call(
+			origin: OriginFor<T>,
+			pallet: Vec<u8>,
+			call: Vec<u8>,
+		) -> DispatchResultWithPostInfo {
+			T::PauseOrigin::ensure_origin(origin)?;
+			Self::ensure_pausable(&pallet)?;
+			ensure!(call.len() <= T::MaxNameLen::get() as usize, Error::<T>::NameTooLong);
+
+			let until = Self::pause_end();
+			PausedCalls::<T>::insert(&pallet, &call, until);
+			Self::deposit_event(Event::CallPaused(pallet, call, until));
+			Ok(().into())
+		}
+
+		/// Lift the pause of `pallet`. Also clears the record of a pause that already expired.
+		///
+		/// The dispatch origin for this call must be `UnpauseOrigin`.
+		#[pallet::weight(T::WeightInfo::unpause_pallet())]
+		pub fn unpause_pallet(origin: OriginFor<T>, pallet: Vec<u8>) -> DispatchResultWithPostInfo {
+			T::UnpauseOrigin::ensure_origin(origin)?;
+			PausedPallets::<T>::take(&pallet).ok_or(Error::<T>::NotPaused)?;
+			Self::deposit_event(Event::PalletUnpaused(pallet));
+			Ok(().into())
+		}
+
+		/// Lift the pause of the call named `call` of `pallet`. Also clears the record of a pause
+		/// that already expired.
+		///
+		/// The dispatch origin for this call must be `UnpauseOrigin`.
+		#[pallet::weight(T::WeightInfo::unpause_call())]
+		pub fn unpause_call(
+			origin: OriginFor<T>,
+			pallet: Vec<u8>,
+			call: Vec<u8>,
+		) -> DispatchResultWithPostInfo {
+			T::UnpauseOrigin::ensure_origin(origin)?;
+			PausedCalls::<T>::take(&pallet, &call).ok_or(Error::<T>::NotPaused)?;
+			Self::deposit_event(Event::CallUnpaused(pallet, call));
+			Ok(().into())
+		}
+	}
+}
+
+impl<T: Config> Pallet<T> {
+	/// Whether the call named `call` of `pallet` is currently paused, either on its own or with
+	/// its whole pallet.
+	pub fn is_paused(pallet: &[u8], call: &[u8]) -> bool {
+		if T::UnpausablePallets::get().iter().any(|name| &name[..] == pallet) {
+			return false
+		}
+		let now = frame_system::Pallet::<T>::block_number();
+		let active = |until: T::BlockNumber| now < until;
+		PausedPallets::<T>::get(pallet).map_or(false, active) ||
+			PausedCalls::<T>::get(pallet, call).map_or(false, active)
+	}
+
+	fn pause_end() -> T::BlockNumber {
+		frame_system::Pallet::<T>::block_number() + T::PauseDuration::get()
+	}
+
+	fn ensure_pausable(pallet: &[u8]) -> frame_support::dispatch::DispatchResult {
+		frame_support::ensure!(pallet.len() <= T::MaxNameLen::get() as usize, Error::<T>::NameTooLong);
+		frame_support::ensure!(
+			!T::UnpausablePallets::get().iter().any(|name| &name[..] == pallet),
+			Error::<T>::Unpausable,
+		);
+		Ok(())
+	}
+}
+
+/// Lets through every call that is not paused.
+impl<T: Config, Call: GetCallMetadata> Filter<Call> for Pallet<T> {
+	fn filter(call: &Call) -> bool {
+		let metadata = call.get_call_metadata();
+		!Self::is_paused(metadata.pallet_name.as_bytes(), metadata.function_name.as_bytes())
+	}
+}
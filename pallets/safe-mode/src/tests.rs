@@ -0,0 +1,120 @@
+// Copyright 2019 TrustBase Network
+// This file is part of TrustBase library.
+//
+// The TrustBase library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Lesser General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// The TrustBase library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU Lesser General Public License for more details.
+//
+// You should have received a copy of the GNU Lesser General Public License
+// along with the TrustBase library. If not, see <http://www.gnu.org/licenses/>.
+
+//! Tests for the safe mode pallet.
+
+use super::*;
+use crate::mock::*;
+use frame_support::{assert_noop, assert_ok, dispatch::Dispatchable};
+use sp_runtime::traits::BadOrigin;
+
+fn transfer() -> Call {
+	Call::Balances(pallet_balances::Call::transfer(2, 10))
+}
+
+fn transfer_keep_alive() -> Call {
+	Call::Balances(pallet_balances::Call::transfer_keep_alive(2, 10))
+}
+
+#[test]
+fn only_pause_origin_can_pause() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(SafeMode::pause_pallet(Origin::signed(2), b"Balances".to_vec()), BadOrigin);
+		assert_noop!(
+			SafeMode::pause_call(Origin::signed(2), b"Balances".to_vec(), b"transfer".to_vec()),
+			BadOrigin,
+		);
+		assert_ok!(SafeMode::pause_pallet(Origin::signed(1), b"Balances".to_vec()));
+		assert_eq!(SafeMode::paused_pallet(b"Balances".to_vec()), Some(11));
+	});
+}
+
+#[test]
+fn pausing_a_pallet_filters_all_its_calls() {
+	new_test_ext().execute_with(|| {
+		assert!(<SafeMode as Filter<Call>>::filter(&transfer()));
+		assert_ok!(SafeMode::pause_pallet(Origin::signed(1), b"Balances".to_vec()));
+
+		assert!(!<SafeMode as Filter<Call>>::filter(&transfer()));
+		assert!(!<SafeMode as Filter<Call>>::filter(&transfer_keep_alive()));
+		assert!(transfer().dispatch(Origin::signed(1)).is_err());
+		assert_eq!(Balances::free_balance(2), 100);
+
+		// root is never filtered.
+		assert_ok!(Call::Balances(pallet_balances::Call::force_transfer(1, 2, 10)).dispatch(Origin::root()));
+	});
+}
+
+#[test]
+fn pausing_a_call_filters_only_that_call() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(SafeMode::pause_call(Origin::signed(1), b"Balances".to_vec(), b"transfer".to_vec()));
+		assert!(!<SafeMode as Filter<Call>>::filter(&transfer()));
+		assert!(<SafeMode as Filter<Call>>::filter(&transfer_keep_alive()));
+	});
+}
+
+#[test]
+fn pauses_expire() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(SafeMode::pause_pallet(Origin::signed(1), b"Balances".to_vec()));
+		System::set_block_number(10);
+		assert!(!<SafeMode as Filter<Call>>::filter(&transfer()));
+		System::set_block_number(11);
+		assert!(<SafeMode as Filter<Call>>::filter(&transfer()));
+		assert_ok!(transfer().dispatch(Origin::signed(1)));
+
+		// the expired record can still be cleared.
+		assert_ok!(SafeMode::unpause_pallet(Origin::signed(2), b"Balances".to_vec()));
+		assert_eq!(SafeMode::paused_pallet(b"Balances".to_vec()), None);
+	});
+}
+
+#[test]
+fn unpause_lifts_pause_early() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(SafeMode::pause_pallet(Origin::signed(1), b"Balances".to_vec()));
+		assert_ok!(SafeMode::pause_call(Origin::signed(1), b"Balances".to_vec(), b"transfer".to_vec()));
+
+		assert_noop!(SafeMode::unpause_pallet(Origin::signed(1), b"Balances".to_vec()), BadOrigin);
+		assert_ok!(SafeMode::unpause_pallet(Origin::signed(2), b"Balances".to_vec()));
+		assert!(<SafeMode as Filter<Call>>::filter(&transfer_keep_alive()));
+		assert!(!<SafeMode as Filter<Call>>::filter(&transfer()));
+
+		assert_ok!(SafeMode::unpause_call(Origin::signed(2), b"Balances".to_vec(), b"transfer".to_vec()));
+		assert!(<SafeMode as Filter<Call>>::filter(&transfer()));
+		assert_noop!(
+			SafeMode::unpause_call(Origin::signed(2), b"Balances".to_vec(), b"transfer".to_vec()),
+			Error::<Test>::NotPaused,
+		);
+	});
+}
+
+#[test]
+fn unpausable_pallets_and_long_names_are_rejected() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(SafeMode::pause_pallet(Origin::signed(1), b"SafeMode".to_vec()), Error::<Test>::Unpausable);
+		assert_noop!(
+			SafeMode::pause_call(Origin::signed(1), b"System".to_vec(), b"remark".to_vec()),
+			Error::<Test>::Unpausable,
+		);
+		assert_noop!(SafeMode::pause_pallet(Origin::signed(1), vec![b'a'; 17]), Error::<Test>::NameTooLong);
+		assert_noop!(
+			SafeMode::pause_call(Origin::signed(1), b"Balances".to_vec(), vec![b'a'; 17]),
+			Error::<Test>::NameTooLong,
+		);
+	});
+}
@@ -0,0 +1,33 @@
+// Copyright 2019 TrustBase Network
+// This file is part of TrustBase library.
+//
+// The TrustBase library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Lesser General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// The TrustBase library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU Lesser General Public License for more details.
+//
+// You should have received a copy of the GNU Lesser General Public License
+// along with the TrustBase library. If not, see <http://www.gnu.org/licenses/>.
+
+//! Runtime API definition for reading the allowance of spending proxies.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::Codec;
+use sp_runtime::traits::MaybeDisplay;
+
+sp_api::decl_runtime_apis! {
+	pub trait SpendingLimitApi<AllowanceId, Balance> where
+		AllowanceId: Codec,
+		Balance: Codec + MaybeDisplay,
+	{
+		/// The amount that may still be spent of allowance `id` in the window of the current
+		/// block. `None` if there is no such allowance.
+		fn remaining_allowance(id: AllowanceId) -> Option<Balance>;
+	}
+}
@@ -0,0 +1,102 @@
+// Copyright 2019 TrustBase Network
+// This file is part of TrustBase library.
+//
+// The TrustBase library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Lesser General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// The TrustBase library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU Lesser General Public License for more details.
+//
+// You should have received a copy of the GNU Lesser General Public License
+// along with the TrustBase library. If not, see <http://www.gnu.org/licenses/>.
+
+//! RPC interface for reading the allowance of spending proxies.
+
+use std::sync::Arc;
+use std::convert::TryInto;
+use codec::Codec;
+use sp_blockchain::HeaderBackend;
+use jsonrpc_core::{Error as RpcError, ErrorCode, Result};
+use jsonrpc_derive::rpc;
+use sp_runtime::{generic::BlockId, traits::{Block as BlockT, MaybeDisplay}};
+use sp_api::ProvideRuntimeApi;
+use sp_rpc::number::NumberOrHex;
+pub use pallet_spending_limit_rpc_runtime_api::SpendingLimitApi as SpendingLimitRuntimeApi;
+
+/// Spending limit RPC methods.
+#[rpc]
+pub trait SpendingLimitApi<BlockHash, AllowanceId, ResponseType> {
+	/// The amount that may still be spent of allowance `id` in the window of the current block,
+	/// or `None` if there is no such allowance.
+	#[rpc(name = "spendingLimit_remainingAllowance")]
+	fn remaining_allowance(
+		&self,
+		id: AllowanceId,
+		at: Option<BlockHash>,
+	) -> Result<Option<ResponseType>>;
+}
+
+/// A struct that implements the [`SpendingLimitApi`].
+pub struct SpendingLimit<C, P> {
+	client: Arc<C>,
+	_marker: std::marker::PhantomData<P>,
+}
+
+impl<C, P> SpendingLimit<C, P> {
+	/// Create new `SpendingLimit` with the given reference to the client.
+	pub fn new(client: Arc<C>) -> Self {
+		Self { client, _marker: Default::default() }
+	}
+}
+
+/// Error type of this RPC api.
+pub enum Error {
+	/// The call to runtime failed.
+	RuntimeError,
+}
+
+impl From<Error> for i64 {
+	fn from(e: Error) -> i64 {
+		match e {
+			Error::RuntimeError => 1,
+		}
+	}
+}
+
+impl<C, Block, AllowanceId, Balance> SpendingLimitApi<<Block as BlockT>::Hash, AllowanceId, NumberOrHex>
+	for SpendingLimit<C, Block>
+where
+	Block: BlockT,
+	C: 'static + ProvideRuntimeApi<Block> + HeaderBackend<Block>,
+	C::Api: SpendingLimitRuntimeApi<Block, AllowanceId, Balance>,
+	AllowanceId: Codec + Send + Sync + 'static,
+	Balance: Codec + MaybeDisplay + Copy + TryInto<NumberOrHex>,
+{
+	fn remaining_allowance(
+		&self,
+		id: AllowanceId,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> Result<Option<NumberOrHex>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(||
+			// If the block hash is not supplied assume the best block.
+			self.client.info().best_hash
+		));
+
+		let remaining = api.remaining_allowance(&at, id).map_err(|e| RpcError {
+			code: ErrorCode::ServerError(Error::RuntimeError.into()),
+			message: "Unable to query the remaining allowance.".into(),
+			data: Some(format!("{:?}", e).into()),
+		})?;
+
+		remaining.map(|remaining| remaining.try_into().map_err(|_| RpcError {
+			code: ErrorCode::InvalidParams,
+			message: format!("{} doesn't fit in NumberOrHex representation", remaining),
+			data: None,
+		})).transpose()
+	}
+}
@@ -0,0 +1,109 @@
+// Copyright 2019 TrustBase Network
+// This file is part of TrustBase library.
+//
+// The TrustBase library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Lesser General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// The TrustBase library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU Lesser General Public License for more details.
+//
+// You should have received a copy of the GNU Lesser General Public License
+// along with the TrustBase library. If not, see <http://www.gnu.org/licenses/>.
+
+//! Benchmarks for the spending limit pallet.
+
+use super::*;
+use frame_benchmarking::{account, benchmarks, impl_benchmark_test_suite, whitelisted_caller};
+use frame_system::RawOrigin;
+use sp_runtime::traits::Bounded;
+
+use crate::Pallet as SpendingLimit;
+
+const SEED: u32 = 0;
+
+type CurrencyOf<T> = <T as pallet_proxy::Config>::Currency;
+
+/// A funded owner with `p` proxies that are not spending proxies.
+fn owner<T: Config>(p: u32) -> Result<T::AccountId, &'static str> {
+	let owner: T::AccountId = whitelisted_caller();
+	CurrencyOf::<T>::make_free_balance_be(&owner, BalanceOf::<T>::max_value() / 2u32.into());
+	for i in 0 .. p {
+		pallet_proxy::Pallet::<T>::add_proxy_delegate(
+			&owner,
+			account("target", i, SEED),
+			T::ProxyType::default(),
+			Zero::zero(),
+		)?;
+	}
+	Ok(owner)
+}
+
+/// Adds a spending proxy without limit for `owner`, returning the id of its allowance.
+fn spending_proxy<T: Config>(owner: &T::AccountId) -> Result<AllowanceId, &'static str> {
+	let id = NextAllowanceId::<T>::get();
+	SpendingLimit::<T>::add_spending_proxy(
+		RawOrigin::Signed(owner.clone()).into(),
+		account("delegate", 0, SEED),
+		BalanceOf::<T>::max_value(),
+		T::BlockNumber::max_value(),
+	)?;
+	Ok(id)
+}
+
+benchmarks! {
+	add_spending_proxy {
+		let p in 0 .. (T::MaxProxies::get() - 1).into();
+		let owner = owner::<T>(p)?;
+		let delegate: T::AccountId = account("delegate", 0, SEED);
+		let id = NextAllowanceId::<T>::get();
+	}: _(
+		RawOrigin::Signed(owner),
+		delegate,
+		BalanceOf::<T>::max_value(),
+		T::BlockNumber::max_value()
+	)
+	verify {
+		assert!(Allowances::<T>::contains_key(id));
+	}
+
+	remove_spending_proxy {
+		let p in 0 .. (T::MaxProxies::get() - 1).into();
+		let owner = owner::<T>(p)?;
+		let id = spending_proxy::<T>(&owner)?;
+	}: _(RawOrigin::Signed(owner), id)
+	verify {
+		assert!(!Allowances::<T>::contains_key(id));
+	}
+
+	// The spend is made in a new block, so it prunes the `s - 1` earlier spends and adds its own.
+	spend {
+		let s in 1 .. T::MaxSpends::get();
+		let owner = owner::<T>(0)?;
+		let id = spending_proxy::<T>(&owner)?;
+		let dest: T::AccountId = account("dest", 0, SEED);
+		let amount = CurrencyOf::<T>::minimum_balance().max(1u32.into());
+		for _ in 1 .. s {
+			SpendingLimit::<T>::spend(
+				RawOrigin::Signed(owner.clone()).into(),
+				id,
+				T::Lookup::unlookup(dest.clone()),
+				amount,
+			)?;
+			let now = frame_system::Pallet::<T>::block_number();
+			frame_system::Pallet::<T>::set_block_number(now + 1u32.into());
+		}
+	}: _(RawOrigin::Signed(owner), id, T::Lookup::unlookup(dest), amount)
+	verify {
+		assert_eq!(Allowances::<T>::get(id).unwrap().spends.len(), s as usize);
+	}
+}
+
+impl_benchmark_test_suite!(
+	SpendingLimit,
+	crate::mock::new_test_ext(),
+	crate::mock::Test,
+);
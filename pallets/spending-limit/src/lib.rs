@@ -0,0 +1,321 @@
+// Copyright 2019 TrustBase Network
+// This file is part of TrustBase library.
+//
+// The TrustBase library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Lesser General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// The TrustBase library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU Lesser General Public License for more details.
+//
+// You should have received a copy of the GNU Lesser General Public License
+// along with the TrustBase library. If not, see <http://www.gnu.org/licenses/>.
+
+//! # Spending Limit Pallet
+//!
+//! Proxies that may transfer at most `limit` of the real account's funds in any `period`
+//! consecutive blocks.
+//!
+//! The real account sets one up with [`Pallet::add_spending_proxy`], which creates an
+//! [`Allowance`] and registers the delegate with `pallet_proxy` under the proxy type that
+//! [`Config::SpendingProxy`] maps the allowance to. The `InstanceFilter` of that proxy type must
+//! only let through [`Pallet::spend`] for the allowance of the proxy type, which transfers the
+//! funds and debits the allowance once the transfer succeeded, or emits [`Event::LimitReached`]
+//! if it exceeds what remains.
+//!
+//! The window is rolling: a spend counts against the limit for `period` blocks from the block it
+//! was made in, so no `period` blocks see more than `limit` spent. An allowance keeps the spends
+//! of its window, at most [`Config::MaxSpends`] blocks with spends in it.
+//!
+//! [`Pallet::spend`] only spends allowances of its sender, so a proxy can not spend the
+//! allowance of another account. The runtime should still keep accounts from adding or removing
+//! these proxy types through `pallet_proxy` directly, e.g. in its base call filter, so that every
+//! spending proxy is the delegate of its allowance.
+//!
+//! The owner reserves [`Config::AllowanceDeposit`] on top of the proxy deposit for as long as the
+//! allowance exists.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::{Encode, Decode};
+use frame_support::traits::{Currency, ExistenceRequirement, ReservableCurrency, Get};
+use sp_runtime::{
+	RuntimeDebug,
+	traits::{Convert, Saturating, StaticLookup, Zero},
+};
+use sp_std::prelude::*;
+
+pub use pallet::*;
+
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+#[cfg(test)]
+mod mock;
+#[cfg(test)]
+mod tests;
+pub mod weights;
+
+pub use weights::WeightInfo;
+
+/// Identifier of an allowance.
+pub type AllowanceId = u32;
+
+type BalanceOf<T> = <<T as pallet_proxy::Config>::Currency as Currency<
+	<T as frame_system::Config>::AccountId,
+>>::Balance;
+
+/// An allowance as stored by this pallet.
+pub type AllowanceOf<T> = Allowance<
+	<T as frame_system::Config>::AccountId,
+	BalanceOf<T>,
+	<T as frame_system::Config>::BlockNumber,
+>;
+
+/// The amount a proxy may spend of the funds of its real account.
+#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug)]
+pub struct Allowance<AccountId, Balance, BlockNumber> {
+	/// The account whose funds are spent.
+	pub owner: AccountId,
+	/// The proxy that spends them.
+	pub delegate: AccountId,
+	/// The amount that may be spent in any `period` consecutive blocks.
+	pub limit: Balance,
+	/// The length of the window, in blocks.
+	pub period: BlockNumber,
+	/// The amounts spent by block, oldest first. Spends that left the window may linger until
+	/// the next spend.
+	pub spends: Vec<(BlockNumber, Balance)>,
+	/// The amount reserved from the owner.
+	pub deposit: Balance,
+}
+
+impl<AccountId, Balance, BlockNumber> Allowance<AccountId, Balance, BlockNumber> where
+	Balance: Saturating + Zero + Copy,
+	BlockNumber: Saturating + PartialOrd + Copy,
+{
+	/// Whether a spend made at block `at` is in the window of block `now`.
+	fn in_window(&self, at: BlockNumber, now: BlockNumber) -> bool {
+		now < at.saturating_add(self.period)
+	}
+
+	/// Forget the spends that are not in the window of block `now`.
+	pub fn prune(&mut self, now: BlockNumber) {
+		let period = self.period;
+		self.spends.retain(|(at, _)| now < at.saturating_add(period));
+	}
+
+	/// The amount spent in the window of block `now`, i.e. in the `period` blocks ending with it.
+	pub fn spent(&self, now: BlockNumber) -> Balance {
+		self.spends.iter()
+			.filter(|(at, _)| self.in_window(*at, now))
+			.fold(Zero::zero(), |spent: Balance, (_, amount)| spent.saturating_add(*amount))
+	}
+
+	/// The amount that may still be spent at block `now`.
+	pub fn remaining(&self, now: BlockNumber) -> Balance {
+		self.limit.saturating_sub(self.spent(now))
+	}
+}
+
+#[frame_support::pallet]
+pub mod pallet {
+	use frame_support::{pallet_prelude::*, transactional};
+	use frame_system::pallet_prelude::*;
+	use super::*;
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config + pallet_proxy::Config {
+		/// The overarching event type.
+		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+
+		/// The proxy type under which a delegate spends the allowance with a given id.
+		type SpendingProxy: Convert<AllowanceId, <Self as pallet_proxy::Config>::ProxyType>;
+
+		/// The amount reserved for every allowance.
+		#[pallet::constant]
+		type AllowanceDeposit: Get<BalanceOf<Self>>;
+
+		/// The maximum number of blocks with spends in the window of an allowance.
+		#[pallet::constant]
+		type MaxSpends: Get<u32>;
+
+		/// Weight information for extrinsics in this pallet.
+		type WeightInfo: WeightInfo;
+	}
+
+	#[pallet::pallet]
+	#[pallet::generate_store(pub(super) trait Store)]
+	pub struct Pallet<T>(_);
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {}
+
+	/// The id of the next allowance.
+	#[pallet::storage]
+	pub type NextAllowanceId<T> = StorageValue<_, AllowanceId, ValueQuery>;
+
+	/// The allowances of spending proxies, by id.
+	#[pallet::storage]
+	#[pallet::getter(fn allowance)]
+	pub type Allowances<T: Config> = StorageMap<_, Twox64Concat, AllowanceId, AllowanceOf<T>>;
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	#[pallet::metadata(
+		T::AccountId = "AccountId",
+		BalanceOf<T> = "Balance",
+		T::BlockNumber = "BlockNumber",
+	)]
+	pub enum Event<T: Config> {
+		/// A spending proxy was added. \[id, owner, delegate, limit, period\]
+		AllowanceCreated(AllowanceId, T::AccountId, T::AccountId, BalanceOf<T>, T::BlockNumber),
+		/// A spending proxy was removed. \[id\]
+		AllowanceRemoved(AllowanceId),
+		/// A transfer was refused as it exceeds the allowance. \[id, amount, remaining\]
+		LimitReached(AllowanceId, BalanceOf<T>, BalanceOf<T>),
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// The period of an allowance must be at least one block.
+		ZeroPeriod,
+		/// There is no allowance with this id.
+		NotFound,
+		/// The sender does not own the allowance.
+		NotOwner,
+		/// All allowance ids are taken.
+		NoAvailableId,
+		/// The amount exceeds what remains of the allowance in the current window.
+		LimitExceeded,
+		/// The window of the allowance already has spends in `MaxSpends` blocks.
+		TooManySpends,
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Let `delegate` transfer up to `limit` of the sender's funds every `period` blocks.
+		///
+		/// Registers `delegate` as a proxy of the sender, without delay, and reserves
+		/// `AllowanceDeposit` on top of the proxy deposit.
+		///
+		/// Emits `AllowanceCreated` with the id of the allowance.
+		#[pallet::weight(<T as Config>::WeightInfo::add_spending_proxy(T::MaxProxies::get().into()))]
+		#[transactional]
+		pub fn add_spending_proxy(
+			origin: OriginFor<T>,
+			delegate: T::AccountId,
+			limit: BalanceOf<T>,
+			period: T::BlockNumber,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			ensure!(!period.is_zero(), Error::<T>::ZeroPeriod);
+
+			let id = NextAllowanceId::<T>::get();
+			let next = id.checked_add(1).ok_or(Error::<T>::NoAvailableId)?;
+			pallet_proxy::Pallet::<T>::add_proxy_delegate(
+				&who,
+				delegate.clone(),
+				T::SpendingProxy::convert(id),
+				Zero::zero(),
+			)?;
+			let deposit = T::AllowanceDeposit::get();
+			<T as pallet_proxy::Config>::Currency::reserve(&who, deposit)?;
+
+			Allowances::<T>::insert(id, Allowance {
+				owner: who.clone(),
+				delegate: delegate.clone(),
+				limit,
+				period,
+				spends: Vec::new(),
+				deposit,
+			});
+			NextAllowanceId::<T>::put(next);
+			Self::deposit_event(Event::AllowanceCreated(id, who, delegate, limit, period));
+			Ok(().into())
+		}
+
+		/// Remove the spending proxy with allowance `id` and return its deposit.
+		///
+		/// The dispatch origin for this call must be _Signed_ by the owner of the allowance.
+		#[pallet::weight(<T as Config>::WeightInfo::remove_spending_proxy(T::MaxProxies::get().into()))]
+		pub fn remove_spending_proxy(
+			origin: OriginFor<T>,
+			id: AllowanceId,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			let allowance = Allowances::<T>::get(id).ok_or(Error::<T>::NotFound)?;
+			ensure!(allowance.owner == who, Error::<T>::NotOwner);
+
+			// the proxy may already be gone through `remove_proxies`.
+			let _ = pallet_proxy::Pallet::<T>::remove_proxy_delegate(
+				&who,
+				allowance.delegate,
+				T::SpendingProxy::convert(id),
+				Zero::zero(),
+			);
+			<T as pallet_proxy::Config>::Currency::unreserve(&who, allowance.deposit);
+			Allowances::<T>::remove(id);
+			Self::deposit_event(Event::AllowanceRemoved(id));
+			Ok(().into())
+		}
+
+		/// Transfer `amount` of the sender's funds to `dest`, out of allowance `id`, keeping the
+		/// sender alive. The allowance is only debited once the transfer succeeded.
+		///
+		/// The dispatch origin for this call must be _Signed_ by the owner of the allowance, i.e.
+		/// be dispatched through its spending proxy.
+		///
+		/// Emits `LimitReached` if `amount` exceeds what remains of the allowance. Fails with
+		/// `TooManySpends` if the window already has spends in `MaxSpends` other blocks.
+		#[pallet::weight(<T as Config>::WeightInfo::spend(T::MaxSpends::get()))]
+		pub fn spend(
+			origin: OriginFor<T>,
+			id: AllowanceId,
+			dest: <T::Lookup as StaticLookup>::Source,
+			#[pallet::compact] amount: BalanceOf<T>,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			let dest = T::Lookup::lookup(dest)?;
+			let mut allowance = Allowances::<T>::get(id).ok_or(Error::<T>::NotFound)?;
+			ensure!(allowance.owner == who, Error::<T>::NotOwner);
+
+			let now = frame_system::Pallet::<T>::block_number();
+			allowance.prune(now);
+			let remaining = allowance.remaining(now);
+			if amount > remaining {
+				// the event stays, as neither this call nor `Proxy::proxy` is transactional.
+				Self::deposit_event(Event::LimitReached(id, amount, remaining));
+				return Err(Error::<T>::LimitExceeded.into())
+			}
+			let spent_now = allowance.spends.last().map_or(false, |(at, _)| *at == now);
+			ensure!(
+				spent_now || (allowance.spends.len() as u32) < T::MaxSpends::get(),
+				Error::<T>::TooManySpends,
+			);
+
+			<T as pallet_proxy::Config>::Currency::transfer(
+				&who,
+				&dest,
+				amount,
+				ExistenceRequirement::KeepAlive,
+			)?;
+			match allowance.spends.last_mut() {
+				Some((at, spent)) if *at == now => *spent = spent.saturating_add(amount),
+				_ => allowance.spends.push((now, amount)),
+			}
+			Allowances::<T>::insert(id, allowance);
+			Ok(().into())
+		}
+	}
+}
+
+impl<T: Config> Pallet<T> {
+	/// The amount that may still be spent of allowance `id` in the window of the current block.
+	pub fn remaining(id: AllowanceId) -> Option<BalanceOf<T>> {
+		let now = frame_system::Pallet::<T>::block_number();
+		Allowances::<T>::get(id).map(|allowance| allowance.remaining(now))
+	}
+}
@@ -0,0 +1,217 @@
+// Copyright 2019 TrustBase Network
+// This file is part of TrustBase library.
+//
+// The TrustBase library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Lesser General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// The TrustBase library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU Lesser General Public License for more details.
+//
+// You should have received a copy of the GNU Lesser General Public License
+// along with the TrustBase library. If not, see <http://www.gnu.org/licenses/>.
+
+//! Tests for the spending limit pallet.
+
+use crate::{Allowance, Error, mock::*};
+use frame_support::{assert_noop, assert_ok, traits::InstanceFilter};
+
+fn spend(id: u32, dest: u64, value: u64) -> Box<Call> {
+	Box::new(Call::SpendingLimit(crate::Call::spend(id, dest, value)))
+}
+
+fn transfer(dest: u64, value: u64) -> Box<Call> {
+	Box::new(Call::Balances(pallet_balances::Call::transfer(dest, value)))
+}
+
+fn proxy_executed_ok() -> bool {
+	matches!(events().last(), Some(Event::Proxy(pallet_proxy::Event::ProxyExecuted(Ok(())))))
+}
+
+#[test]
+fn adding_a_spending_proxy_registers_it_with_a_deposit() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(SpendingLimit::add_spending_proxy(Origin::signed(1), 2, 10, 0), Error::<Test>::ZeroPeriod);
+		assert_ok!(SpendingLimit::add_spending_proxy(Origin::signed(1), 2, 10, 5));
+
+		assert_eq!(SpendingLimit::allowance(0), Some(Allowance {
+			owner: 1,
+			delegate: 2,
+			limit: 10,
+			period: 5,
+			spends: vec![],
+			deposit: 5,
+		}));
+		assert_eq!(Proxy::proxies(1).0[0].proxy_type, ProxyType::Spending(0));
+		// the proxy deposit and the allowance deposit.
+		assert_eq!(Balances::reserved_balance(1), 2 + 5);
+		assert_eq!(
+			events().last(),
+			Some(&Event::SpendingLimit(crate::Event::AllowanceCreated(0, 1, 2, 10, 5))),
+		);
+
+		assert_ok!(SpendingLimit::add_spending_proxy(Origin::signed(1), 3, 10, 5));
+		assert!(SpendingLimit::allowance(1).is_some());
+	});
+}
+
+#[test]
+fn transfers_beyond_the_limit_are_refused_until_the_window_ends() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(SpendingLimit::add_spending_proxy(Origin::signed(1), 2, 10, 5));
+
+		assert_ok!(Proxy::proxy(Origin::signed(2), 1, None, spend(0, 3, 6)));
+		assert!(proxy_executed_ok());
+		assert_eq!(SpendingLimit::remaining(0), Some(4));
+
+		assert_ok!(Proxy::proxy(Origin::signed(2), 1, None, spend(0, 3, 6)));
+		assert!(!proxy_executed_ok());
+		assert!(events().contains(&Event::SpendingLimit(crate::Event::LimitReached(0, 6, 4))));
+		assert_eq!(Balances::free_balance(3), 106);
+
+		assert_ok!(Proxy::proxy(Origin::signed(2), 1, None, spend(0, 3, 4)));
+		assert!(proxy_executed_ok());
+		assert_eq!(SpendingLimit::remaining(0), Some(0));
+
+		// the spends of block 1 leave the window after five blocks.
+		System::set_block_number(6);
+		assert_eq!(SpendingLimit::remaining(0), Some(10));
+		assert_ok!(Proxy::proxy(Origin::signed(2), 1, None, spend(0, 3, 6)));
+		assert!(proxy_executed_ok());
+		assert_eq!(Balances::free_balance(3), 116);
+		assert_eq!(SpendingLimit::allowance(0).unwrap().spends, vec![(6, 6)]);
+	});
+}
+
+#[test]
+fn the_window_rolls_over_the_spends() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(SpendingLimit::add_spending_proxy(Origin::signed(1), 2, 10, 5));
+
+		System::set_block_number(4);
+		assert_ok!(Proxy::proxy(Origin::signed(2), 1, None, spend(0, 3, 10)));
+		assert!(proxy_executed_ok());
+
+		// a spend late in a window still counts for the next `period` blocks.
+		System::set_block_number(6);
+		assert_eq!(SpendingLimit::remaining(0), Some(0));
+		assert_ok!(Proxy::proxy(Origin::signed(2), 1, None, spend(0, 3, 1)));
+		assert!(!proxy_executed_ok());
+
+		System::set_block_number(8);
+		assert_eq!(SpendingLimit::remaining(0), Some(0));
+		System::set_block_number(9);
+		assert_eq!(SpendingLimit::remaining(0), Some(10));
+		assert_ok!(Proxy::proxy(Origin::signed(2), 1, None, spend(0, 3, 4)));
+		assert!(proxy_executed_ok());
+
+		// only the spends of block 9 are in the window of block 11.
+		System::set_block_number(11);
+		assert_eq!(SpendingLimit::remaining(0), Some(6));
+		assert_eq!(Balances::free_balance(3), 114);
+	});
+}
+
+#[test]
+fn the_window_holds_spends_of_at_most_max_spends_blocks() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(SpendingLimit::add_spending_proxy(Origin::signed(1), 2, 10, 5));
+
+		for block in 1..=3 {
+			System::set_block_number(block);
+			assert_ok!(SpendingLimit::spend(Origin::signed(1), 0, 3, 1));
+		}
+		// spends of a block already in the window are merged.
+		assert_ok!(SpendingLimit::spend(Origin::signed(1), 0, 3, 1));
+		assert_eq!(SpendingLimit::allowance(0).unwrap().spends, vec![(1, 1), (2, 1), (3, 2)]);
+
+		System::set_block_number(4);
+		assert_noop!(SpendingLimit::spend(Origin::signed(1), 0, 3, 1), Error::<Test>::TooManySpends);
+
+		// once block 1 leaves the window, there is room again.
+		System::set_block_number(6);
+		assert_ok!(SpendingLimit::spend(Origin::signed(1), 0, 3, 1));
+		assert_eq!(SpendingLimit::allowance(0).unwrap().spends, vec![(2, 1), (3, 2), (6, 1)]);
+	});
+}
+
+#[test]
+fn transfers_in_a_batch_are_charged_once() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(SpendingLimit::add_spending_proxy(Origin::signed(1), 2, 10, 5));
+
+		let batch = Call::Utility(pallet_utility::Call::batch(vec![*spend(0, 3, 5), *spend(0, 3, 5)]));
+		assert_ok!(Proxy::proxy(Origin::signed(2), 1, None, Box::new(batch.clone())));
+		assert_eq!(Balances::free_balance(3), 110);
+		assert_eq!(SpendingLimit::remaining(0), Some(0));
+
+		// the batch itself passes, but its spends do not.
+		assert_ok!(Proxy::proxy(Origin::signed(2), 1, None, Box::new(batch)));
+		assert_eq!(Balances::free_balance(3), 110);
+	});
+}
+
+#[test]
+fn only_the_owner_can_remove_a_spending_proxy() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(SpendingLimit::add_spending_proxy(Origin::signed(1), 2, 10, 5));
+		assert_noop!(SpendingLimit::remove_spending_proxy(Origin::signed(2), 0), Error::<Test>::NotOwner);
+		assert_noop!(SpendingLimit::remove_spending_proxy(Origin::signed(1), 1), Error::<Test>::NotFound);
+
+		assert_ok!(SpendingLimit::remove_spending_proxy(Origin::signed(1), 0));
+		assert!(SpendingLimit::allowance(0).is_none());
+		assert!(Proxy::proxies(1).0.is_empty());
+		assert_eq!(Balances::reserved_balance(1), 0);
+		assert_noop!(SpendingLimit::spend(Origin::signed(1), 0, 3, 1), Error::<Test>::NotFound);
+	});
+}
+
+#[test]
+fn the_proxy_can_only_spend_its_allowance() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(SpendingLimit::add_spending_proxy(Origin::signed(1), 2, 10, 5));
+		let filter = |call: &Call| <ProxyType as InstanceFilter<Call>>::filter(&ProxyType::Spending(0), call);
+
+		// the filter only looks at the call, and leaves the allowance alone.
+		assert!(filter(&spend(0, 3, 6)));
+		assert!(!filter(&spend(1, 3, 6)));
+		assert!(!filter(&transfer(3, 6)));
+		assert_eq!(SpendingLimit::remaining(0), Some(10));
+
+		assert_ok!(Proxy::proxy(Origin::signed(2), 1, None, transfer(3, 6)));
+		assert!(!proxy_executed_ok());
+		assert_eq!(Balances::free_balance(3), 100);
+
+		// an allowance of another account can not be spent.
+		assert_ok!(SpendingLimit::add_spending_proxy(Origin::signed(2), 3, 10, 5));
+		assert_noop!(SpendingLimit::spend(Origin::signed(1), 1, 3, 6), Error::<Test>::NotOwner);
+	});
+}
+
+#[test]
+fn failed_transfers_do_not_use_the_allowance() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(SpendingLimit::add_spending_proxy(Origin::signed(1), 2, 1_000, 5));
+
+		// more than the free balance of the owner.
+		assert_ok!(Proxy::proxy(Origin::signed(2), 1, None, spend(0, 3, 500)));
+		assert!(!proxy_executed_ok());
+		assert_eq!(SpendingLimit::remaining(0), Some(1_000));
+		assert_eq!(Balances::free_balance(3), 100);
+	});
+}
+
+#[test]
+fn allowance_can_be_removed_after_its_proxy() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(SpendingLimit::add_spending_proxy(Origin::signed(1), 2, 10, 5));
+		assert_ok!(Proxy::remove_proxies(Origin::signed(1)));
+		assert_eq!(Balances::reserved_balance(1), 5);
+
+		assert_ok!(SpendingLimit::remove_spending_proxy(Origin::signed(1), 0));
+		assert_eq!(Balances::reserved_balance(1), 0);
+	});
+}
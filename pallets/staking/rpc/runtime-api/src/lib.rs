@@ -0,0 +1,93 @@
+// Copyright 2019 TrustBase Network
+// This file is part of TrustBase library.
+//
+// The TrustBase library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Lesser General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// The TrustBase library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU Lesser General Public License for more details.
+//
+// You should have received a copy of the GNU Lesser General Public License
+// along with the TrustBase library. If not, see <http://www.gnu.org/licenses/>.
+
+//! Runtime API definition for projecting staking rewards and unbonding.
+//!
+//! Everything is computed from the staking state at the block the API is called at, so wallets
+//! don't have to re-implement the reward curve of the runtime.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::{Codec, Decode, Encode};
+use sp_runtime::{RuntimeDebug, traits::MaybeDisplay};
+use sp_std::vec::Vec;
+
+/// Counter for the number of eras that have passed.
+pub type EraIndex = u32;
+
+/// The payout projected for the active era.
+#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug)]
+pub struct EraPayout<Balance> {
+	/// The active era.
+	pub era: EraIndex,
+	/// The stake backing the validators of the era.
+	pub total_staked: Balance,
+	/// The total issuance the payout is computed against.
+	pub total_issuance: Balance,
+	/// The expected duration of an era, in milliseconds.
+	pub era_duration: u64,
+	/// The amount paid out to the validators and their nominators at the end of the era.
+	pub validator_payout: Balance,
+	/// The amount minted for the remainder destination, the treasury.
+	pub remainder: Balance,
+}
+
+/// The reward a validator is expected to get at the end of the active era.
+#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug)]
+pub struct ValidatorReward<AccountId, Balance> {
+	/// The active era.
+	pub era: EraIndex,
+	/// The whole reward of the validator, assuming it earns as many points as its peers.
+	pub total: Balance,
+	/// The part of `total` kept by the validator, its commission included.
+	pub validator: Balance,
+	/// The part of `total` paid to each rewarded nominator, by its exposure.
+	pub nominators: Vec<(AccountId, Balance)>,
+}
+
+/// The unbonding schedule of a staking ledger.
+#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug)]
+pub struct Unbonding<Balance> {
+	/// The stake that is still bonded.
+	pub active: Balance,
+	/// The stake that can be withdrawn now.
+	pub withdrawable: Balance,
+	/// The stake that is still unbonding, with the era it can be withdrawn at.
+	pub chunks: Vec<(EraIndex, Balance)>,
+}
+
+sp_api::decl_runtime_apis! {
+	pub trait StakingApi<AccountId, Balance> where
+		AccountId: Codec,
+		Balance: Codec + MaybeDisplay,
+	{
+		/// The payout of the active era, as if it lasted as long as expected. `None` if there is
+		/// no active era.
+		fn projected_era_payout() -> Option<EraPayout<Balance>>;
+
+		/// The reward `validator` is expected to get for the active era, split by its current
+		/// exposure. `None` if it isn't elected in the active era.
+		fn expected_validator_reward(validator: AccountId) -> Option<ValidatorReward<AccountId, Balance>>;
+
+		/// The eras in history that `stash` validated in and that are not paid out yet, oldest
+		/// first.
+		fn unclaimed_eras(stash: AccountId) -> Vec<EraIndex>;
+
+		/// The unbonding schedule of the ledger of `who`, a stash or a controller. `None` if
+		/// `who` isn't bonded.
+		fn unbonding(who: AccountId) -> Option<Unbonding<Balance>>;
+	}
+}
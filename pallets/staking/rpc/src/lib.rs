@@ -0,0 +1,214 @@
+// Copyright 2019 TrustBase Network
+// This file is part of TrustBase library.
+//
+// The TrustBase library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Lesser General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// The TrustBase library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU Lesser General Public License for more details.
+//
+// You should have received a copy of the GNU Lesser General Public License
+// along with the TrustBase library. If not, see <http://www.gnu.org/licenses/>.
+
+//! RPC interface for projecting staking rewards and unbonding.
+
+use std::sync::Arc;
+use std::convert::TryInto;
+use codec::Codec;
+use serde::{Deserialize, Serialize};
+use sp_blockchain::HeaderBackend;
+use jsonrpc_core::{Error as RpcError, ErrorCode, Result};
+use jsonrpc_derive::rpc;
+use sp_runtime::{generic::BlockId, traits::{Block as BlockT, MaybeDisplay}};
+use sp_api::ProvideRuntimeApi;
+use sp_rpc::number::NumberOrHex;
+pub use pallet_staking_rpc_runtime_api::{EraIndex, StakingApi as StakingRuntimeApi};
+
+/// The payout projected for the active era.
+#[derive(Clone, Eq, PartialEq, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EraPayout {
+	/// The active era.
+	pub era: EraIndex,
+	/// The stake backing the validators of the era.
+	pub total_staked: NumberOrHex,
+	/// The total issuance the payout is computed against.
+	pub total_issuance: NumberOrHex,
+	/// The expected duration of an era, in milliseconds.
+	pub era_duration: u64,
+	/// The amount paid out to the validators and their nominators at the end of the era.
+	pub validator_payout: NumberOrHex,
+	/// The amount minted for the treasury.
+	pub remainder: NumberOrHex,
+}
+
+/// The reward a validator is expected to get at the end of the active era.
+#[derive(Clone, Eq, PartialEq, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ValidatorReward<AccountId> {
+	/// The active era.
+	pub era: EraIndex,
+	/// The whole reward of the validator, assuming it earns as many points as its peers.
+	pub total: NumberOrHex,
+	/// The part of `total` kept by the validator, its commission included.
+	pub validator: NumberOrHex,
+	/// The part of `total` paid to each rewarded nominator.
+	pub nominators: Vec<(AccountId, NumberOrHex)>,
+}
+
+/// The unbonding schedule of a staking ledger.
+#[derive(Clone, Eq, PartialEq, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Unbonding {
+	/// The stake that is still bonded.
+	pub active: NumberOrHex,
+	/// The stake that can be withdrawn now.
+	pub withdrawable: NumberOrHex,
+	/// The stake that is still unbonding, with the era it can be withdrawn at.
+	pub chunks: Vec<(EraIndex, NumberOrHex)>,
+}
+
+/// Staking RPC methods.
+#[rpc]
+pub trait StakingApi<BlockHash, AccountId> {
+	/// The payout of the active era, as if it lasted as long as expected.
+	#[rpc(name = "staking_projectedEraPayout")]
+	fn projected_era_payout(&self, at: Option<BlockHash>) -> Result<Option<EraPayout>>;
+
+	/// The reward `validator` is expected to get for the active era, split by its current
+	/// exposure.
+	#[rpc(name = "staking_expectedValidatorReward")]
+	fn expected_validator_reward(
+		&self,
+		validator: AccountId,
+		at: Option<BlockHash>,
+	) -> Result<Option<ValidatorReward<AccountId>>>;
+
+	/// The eras in history that `stash` validated in and that are not paid out yet.
+	#[rpc(name = "staking_unclaimedEras")]
+	fn unclaimed_eras(&self, stash: AccountId, at: Option<BlockHash>) -> Result<Vec<EraIndex>>;
+
+	/// The unbonding schedule of the ledger of `who`, a stash or a controller.
+	#[rpc(name = "staking_unbonding")]
+	fn unbonding(&self, who: AccountId, at: Option<BlockHash>) -> Result<Option<Unbonding>>;
+}
+
+/// A struct that implements the [`StakingApi`].
+pub struct Staking<C, P> {
+	client: Arc<C>,
+	_marker: std::marker::PhantomData<P>,
+}
+
+impl<C, P> Staking<C, P> {
+	/// Create new `Staking` with the given reference to the client.
+	pub fn new(client: Arc<C>) -> Self {
+		Self { client, _marker: Default::default() }
+	}
+}
+
+/// Error type of this RPC api.
+pub enum Error {
+	/// The call to runtime failed.
+	RuntimeError,
+}
+
+impl From<Error> for i64 {
+	fn from(e: Error) -> i64 {
+		match e {
+			Error::RuntimeError => 1,
+		}
+	}
+}
+
+fn runtime_error(message: &str, e: impl std::fmt::Debug) -> RpcError {
+	RpcError {
+		code: ErrorCode::ServerError(Error::RuntimeError.into()),
+		message: message.into(),
+		data: Some(format!("{:?}", e).into()),
+	}
+}
+
+fn number<Balance>(value: Balance) -> Result<NumberOrHex> where
+	Balance: MaybeDisplay + Copy + TryInto<NumberOrHex>,
+{
+	value.try_into().map_err(|_| RpcError {
+		code: ErrorCode::InvalidParams,
+		message: format!("{} doesn't fit in NumberOrHex representation", value),
+		data: None,
+	})
+}
+
+impl<C, Block> Staking<C, Block> where
+	Block: BlockT,
+	C: HeaderBackend<Block>,
+{
+	fn at(&self, at: Option<<Block as BlockT>::Hash>) -> BlockId<Block> {
+		BlockId::hash(at.unwrap_or_else(||
+			// If the block hash is not supplied assume the best block.
+			self.client.info().best_hash
+		))
+	}
+}
+
+impl<C, Block, AccountId, Balance> StakingApi<<Block as BlockT>::Hash, AccountId> for Staking<C, Block>
+where
+	Block: BlockT,
+	C: 'static + ProvideRuntimeApi<Block> + HeaderBackend<Block>,
+	C::Api: StakingRuntimeApi<Block, AccountId, Balance>,
+	AccountId: Codec + Send + Sync + 'static,
+	Balance: Codec + MaybeDisplay + Copy + TryInto<NumberOrHex>,
+{
+	fn projected_era_payout(&self, at: Option<<Block as BlockT>::Hash>) -> Result<Option<EraPayout>> {
+		let payout = self.client.runtime_api().projected_era_payout(&self.at(at))
+			.map_err(|e| runtime_error("Unable to project the era payout.", e))?;
+
+		payout.map(|payout| Ok(EraPayout {
+			era: payout.era,
+			total_staked: number(payout.total_staked)?,
+			total_issuance: number(payout.total_issuance)?,
+			era_duration: payout.era_duration,
+			validator_payout: number(payout.validator_payout)?,
+			remainder: number(payout.remainder)?,
+		})).transpose()
+	}
+
+	fn expected_validator_reward(
+		&self,
+		validator: AccountId,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> Result<Option<ValidatorReward<AccountId>>> {
+		let reward = self.client.runtime_api().expected_validator_reward(&self.at(at), validator)
+			.map_err(|e| runtime_error("Unable to project the validator reward.", e))?;
+
+		reward.map(|reward| Ok(ValidatorReward {
+			era: reward.era,
+			total: number(reward.total)?,
+			validator: number(reward.validator)?,
+			nominators: reward.nominators.into_iter()
+				.map(|(nominator, value)| Ok((nominator, number(value)?)))
+				.collect::<Result<_>>()?,
+		})).transpose()
+	}
+
+	fn unclaimed_eras(&self, stash: AccountId, at: Option<<Block as BlockT>::Hash>) -> Result<Vec<EraIndex>> {
+		self.client.runtime_api().unclaimed_eras(&self.at(at), stash)
+			.map_err(|e| runtime_error("Unable to query the unclaimed eras.", e))
+	}
+
+	fn unbonding(&self, who: AccountId, at: Option<<Block as BlockT>::Hash>) -> Result<Option<Unbonding>> {
+		let unbonding = self.client.runtime_api().unbonding(&self.at(at), who)
+			.map_err(|e| runtime_error("Unable to query the unbonding schedule.", e))?;
+
+		unbonding.map(|unbonding| Ok(Unbonding {
+			active: number(unbonding.active)?,
+			withdrawable: number(unbonding.withdrawable)?,
+			chunks: unbonding.chunks.into_iter()
+				.map(|(era, value)| Ok((era, number(value)?)))
+				.collect::<Result<_>>()?,
+		})).transpose()
+	}
+}
@@ -0,0 +1,132 @@
+// Copyright 2019 TrustBase Network
+// This file is part of TrustBase library.
+//
+// The TrustBase library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Lesser General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// The TrustBase library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU Lesser General Public License for more details.
+//
+// You should have received a copy of the GNU Lesser General Public License
+// along with the TrustBase library. If not, see <http://www.gnu.org/licenses/>.
+
+//! # Sudo Retirement Pallet
+//!
+//! A governance-triggered way to give up the sudo key for good: [`Config::RetireOrigin`],
+//! usually a referendum, calls [`Pallet::retire_sudo`], which removes the key of `pallet_sudo`
+//! and records the retirement.
+//!
+//! The pallet is a [`Filter`] on the runtime `Call` that rejects every call of `pallet_sudo`
+//! once the key is retired, so that no account can become sudo again, including the default
+//! account that `pallet_sudo` falls back to without a key. It takes effect once it is part of
+//! `frame_system::Config::BaseCallFilter`.
+//!
+//! Retiring the key leaves root only reachable through [`Config::RetireOrigin`] and the other
+//! governance origins, so the origins that should not require a referendum must be open to the
+//! collectives before.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use frame_support::{
+	traits::{Filter, GetCallMetadata, PalletInfoAccess},
+	weights::Weight,
+};
+
+pub use pallet::*;
+
+#[cfg(test)]
+mod mock;
+#[cfg(test)]
+mod tests;
+
+/// Weight functions needed for this pallet.
+pub trait WeightInfo {
+	fn retire_sudo() -> Weight;
+}
+
+impl WeightInfo for () {
+	fn retire_sudo() -> Weight {
+		(20_000_000 as Weight)
+			.saturating_add(frame_support::weights::constants::RocksDbWeight::get().reads(2 as Weight))
+			.saturating_add(frame_support::weights::constants::RocksDbWeight::get().writes(2 as Weight))
+	}
+}
+
+#[frame_support::pallet]
+pub mod pallet {
+	use frame_support::pallet_prelude::*;
+	use frame_system::pallet_prelude::*;
+	use super::*;
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config + pallet_sudo::Config {
+		/// The overarching event type.
+		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+
+		/// Origin allowed to retire the sudo key.
+		type RetireOrigin: EnsureOrigin<Self::Origin>;
+
+		/// Weight information for extrinsics in this pallet.
+		type WeightInfo: WeightInfo;
+	}
+
+	#[pallet::pallet]
+	#[pallet::generate_store(pub(super) trait Store)]
+	pub struct Pallet<T>(_);
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {}
+
+	/// Whether the sudo key is retired.
+	#[pallet::storage]
+	#[pallet::getter(fn is_retired)]
+	pub type Retired<T: Config> = StorageValue<_, bool, ValueQuery>;
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	#[pallet::metadata(T::AccountId = "AccountId")]
+	pub enum Event<T: Config> {
+		/// The sudo key was retired. \[key\]
+		SudoRetired(T::AccountId),
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// The sudo key is already retired.
+		AlreadyRetired,
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Remove the sudo key and reject every call of the sudo pallet from now on.
+		///
+		/// The dispatch origin for this call must be `RetireOrigin`.
+		#[pallet::weight((T::WeightInfo::retire_sudo(), DispatchClass::Operational))]
+		pub fn retire_sudo(origin: OriginFor<T>) -> DispatchResultWithPostInfo {
+			T::RetireOrigin::ensure_origin(origin)?;
+			ensure!(!Retired::<T>::get(), Error::<T>::AlreadyRetired);
+
+			let key = pallet_sudo::Pallet::<T>::key();
+			frame_support::storage::migration::remove_storage_prefix(
+				<pallet_sudo::Pallet<T> as PalletInfoAccess>::name().as_bytes(),
+				b"Key",
+				b"",
+			);
+			Retired::<T>::put(true);
+			Self::deposit_event(Event::SudoRetired(key));
+			Ok(().into())
+		}
+	}
+}
+
+/// Lets through every call but those of the sudo pallet once the sudo key is retired.
+impl<T: Config, Call: GetCallMetadata> Filter<Call> for Pallet<T> {
+	fn filter(call: &Call) -> bool {
+		!Self::is_retired() ||
+			call.get_call_metadata().pallet_name != <pallet_sudo::Pallet<T> as PalletInfoAccess>::name()
+	}
+}
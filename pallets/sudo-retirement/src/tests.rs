@@ -0,0 +1,66 @@
+// Copyright 2019 TrustBase Network
+// This file is part of TrustBase library.
+//
+// The TrustBase library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Lesser General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// The TrustBase library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU Lesser General Public License for more details.
+//
+// You should have received a copy of the GNU Lesser General Public License
+// along with the TrustBase library. If not, see <http://www.gnu.org/licenses/>.
+
+//! Tests for the sudo retirement pallet.
+
+use crate::{Error, mock::*};
+use frame_support::{assert_noop, assert_ok, dispatch::Dispatchable, traits::Filter};
+use sp_runtime::traits::BadOrigin;
+
+fn sudo_remark() -> Call {
+	Call::Sudo(pallet_sudo::Call::sudo(Box::new(Call::System(frame_system::Call::remark(vec![])))))
+}
+
+#[test]
+fn only_retire_origin_can_retire() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(SudoRetirement::retire_sudo(Origin::signed(1)), BadOrigin);
+		assert_ok!(SudoRetirement::retire_sudo(Origin::root()));
+		assert!(SudoRetirement::is_retired());
+		assert_eq!(
+			System::events().last().unwrap().event,
+			Event::SudoRetirement(crate::Event::SudoRetired(1)),
+		);
+	});
+}
+
+#[test]
+fn retiring_removes_the_key_and_filters_sudo() {
+	new_test_ext().execute_with(|| {
+		assert!(<SudoRetirement as Filter<Call>>::filter(&sudo_remark()));
+		assert_ok!(sudo_remark().dispatch(Origin::signed(1)));
+
+		assert_ok!(SudoRetirement::retire_sudo(Origin::root()));
+		assert!(!frame_support::storage::migration::have_storage_value(b"Sudo", b"Key", b""));
+		assert!(!<SudoRetirement as Filter<Call>>::filter(&sudo_remark()));
+		assert!(sudo_remark().dispatch(Origin::signed(1)).is_err());
+		// the default account that sudo falls back to is rejected as well.
+		assert!(sudo_remark().dispatch(Origin::signed(0)).is_err());
+
+		// other calls are not affected.
+		let remark = Call::System(frame_system::Call::remark(vec![]));
+		assert!(<SudoRetirement as Filter<Call>>::filter(&remark));
+		assert_ok!(remark.dispatch(Origin::signed(1)));
+	});
+}
+
+#[test]
+fn cannot_retire_twice() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(SudoRetirement::retire_sudo(Origin::root()));
+		assert_noop!(SudoRetirement::retire_sudo(Origin::root()), Error::<Test>::AlreadyRetired);
+	});
+}
@@ -41,6 +41,9 @@ pub type AccountIndex = u32;
 /// Balance of an account.
 pub type Balance = u128;
 
+/// Identifier of a fungible asset.
+pub type AssetId = u32;
+
 /// Type used for expressing timestamp.
 pub type Moment = u64;
 
@@ -29,7 +29,7 @@
 use std::sync::Arc;
 
 use sp_keystore::SyncCryptoStorePtr;
-use node_primitives::{Block, BlockNumber, AccountId, Index, Balance, Hash};
+use node_primitives::{Block, BlockNumber, AccountId, AssetId, Index, Balance, Hash};
 use sc_consensus_babe::{Config, Epoch};
 use sc_consensus_babe_rpc::BabeRpcHandler;
 use sc_consensus_epochs::SharedEpochChanges;
@@ -112,8 +112,13 @@ pub fn create_full<C, P, SC, B>(
 		HeaderMetadata<Block, Error=BlockChainError> + Sync + Send + 'static,
 	C::Api: substrate_frame_rpc_system::AccountNonceApi<Block, AccountId, Index>,
 	C::Api: pallet_contracts_rpc::ContractsRuntimeApi<Block, AccountId, Balance, BlockNumber, Hash>,
-	// C::Api: pallet_mmr_rpc::MmrRuntimeApi<Block, <Block as sp_runtime::traits::Block>::Hash>,
+	C::Api: pallet_mmr_rpc::MmrRuntimeApi<Block, <Block as sp_runtime::traits::Block>::Hash>,
 	C::Api: pallet_transaction_payment_rpc::TransactionPaymentRuntimeApi<Block, Balance>,
+	C::Api: pallet_asset_tx_payment_rpc::AssetTxPaymentRuntimeApi<Block, Balance, AssetId>,
+	C::Api: pallet_spending_limit_rpc::SpendingLimitRuntimeApi<Block, u32, Balance>,
+	C::Api: pallet_staking_rpc::StakingRuntimeApi<Block, AccountId, Balance>,
+	C::Api: pallet_parameters_rpc::ParametersRuntimeApi<Block>,
+	C::Api: pallet_governance_rpc::GovernanceRuntimeApi<Block, AccountId, Balance, BlockNumber, Hash>,
 	C::Api: BabeApi<Block>,
 	C::Api: BlockBuilder<Block>,
 	P: TransactionPool + 'static,
@@ -123,8 +128,13 @@ pub fn create_full<C, P, SC, B>(
 {
 	use substrate_frame_rpc_system::{FullSystem, SystemApi};
 	use pallet_contracts_rpc::{Contracts, ContractsApi};
-	// use pallet_mmr_rpc::{MmrApi, Mmr};
+	use pallet_mmr_rpc::{MmrApi, Mmr};
 	use pallet_transaction_payment_rpc::{TransactionPayment, TransactionPaymentApi};
+	use pallet_asset_tx_payment_rpc::{AssetTxPayment, AssetTxPaymentApi};
+	use pallet_spending_limit_rpc::{SpendingLimit, SpendingLimitApi};
+	use pallet_staking_rpc::{Staking, StakingApi};
+	use pallet_parameters_rpc::{Parameters, ParametersApi};
+	use pallet_governance_rpc::{Governance, GovernanceApi};
 
 	let mut io = jsonrpc_core::IoHandler::default();
 	let FullDeps {
@@ -159,9 +169,27 @@ pub fn create_full<C, P, SC, B>(
 	io.extend_with(
 		ContractsApi::to_delegate(Contracts::new(client.clone()))
 	);
+	io.extend_with(
+		MmrApi::to_delegate(Mmr::new(client.clone()))
+	);
 	io.extend_with(
 		TransactionPaymentApi::to_delegate(TransactionPayment::new(client.clone()))
 	);
+	io.extend_with(
+		AssetTxPaymentApi::to_delegate(AssetTxPayment::new(client.clone()))
+	);
+	io.extend_with(
+		SpendingLimitApi::to_delegate(SpendingLimit::new(client.clone()))
+	);
+	io.extend_with(
+		StakingApi::to_delegate(Staking::new(client.clone()))
+	);
+	io.extend_with(
+		ParametersApi::to_delegate(Parameters::new(client.clone()))
+	);
+	io.extend_with(
+		GovernanceApi::to_delegate(Governance::new(client.clone()))
+	);
 	io.extend_with(
 		sc_consensus_babe_rpc::BabeApi::to_delegate(
 			BabeRpcHandler::new(
@@ -0,0 +1,81 @@
+// Copyright 2019 TrustBase Network
+// This file is part of TrustBase library.
+//
+// The TrustBase library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Lesser General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// The TrustBase library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU Lesser General Public License for more details.
+
+//! Benchmarks for the functions of the TrustBase chain extension.
+//!
+//! The chain extension is not a pallet, so the benchmarks measure the runtime-side work of every
+//! function directly, independent of any contract.
+
+use frame_benchmarking::{account, benchmarks};
+use frame_support::traits::{Currency, LockableCurrency, WithdrawReasons};
+use sp_core::crypto::KeyTypeId;
+use sp_std::{marker::PhantomData, prelude::*};
+use node_primitives::AccountId;
+use crate::{Balances, MaxLocks, MaxValueSize, Runtime, constants::currency::DOLLARS};
+use super::TrustBaseExtension;
+
+const SEED: u32 = 0;
+const MAX_MEMBERS: u32 = 1_000;
+const KEY_TYPE: KeyTypeId = KeyTypeId(*b"tbce");
+
+pub struct Pallet<T: Config>(PhantomData<T>);
+pub trait Config: frame_system::Config {}
+impl Config for Runtime {}
+
+benchmarks! {
+	balance {
+		let who: AccountId = account("who", 0, SEED);
+		Balances::make_free_balance_be(&who, DOLLARS);
+	}: {
+		TrustBaseExtension::balance(&who);
+	}
+
+	locks {
+		let l in 0 .. MaxLocks::get();
+		let who: AccountId = account("who", 0, SEED);
+		Balances::make_free_balance_be(&who, DOLLARS);
+		for i in 0 .. l {
+			let mut id = *b"bench000";
+			id[7] = i as u8;
+			Balances::set_lock(id, &who, DOLLARS, WithdrawReasons::all());
+		}
+	}: {
+		assert_eq!(TrustBaseExtension::locks(&who).len(), l as usize);
+	}
+
+	is_society_member {
+		let m in 1 .. MAX_MEMBERS;
+		let mut members: Vec<AccountId> = (0 .. m).map(|i| account("member", i, SEED)).collect();
+		members.sort();
+		let who = members[members.len() - 1].clone();
+		pallet_society::Members::<Runtime>::put(members);
+	}: {
+		assert!(TrustBaseExtension::is_society_member(&who));
+	}
+
+	sr25519_verify {
+		let n in 0 .. MaxValueSize::get();
+		let public = sp_io::crypto::sr25519_generate(KEY_TYPE, None);
+		let message = vec![0u8; n as usize];
+		let signature = sp_io::crypto::sr25519_sign(KEY_TYPE, &public, &message)
+			.expect("the key was just generated; qed");
+	}: {
+		assert!(TrustBaseExtension::sr25519_verify(&signature, &public, &message));
+	}
+
+	epoch_randomness {
+		let subject = [0u8; 32];
+	}: {
+		TrustBaseExtension::epoch_randomness(&subject);
+	}
+}
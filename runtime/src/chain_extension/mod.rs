@@ -0,0 +1,156 @@
+// Copyright 2019 TrustBase Network
+// This file is part of TrustBase library.
+//
+// The TrustBase library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Lesser General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// The TrustBase library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU Lesser General Public License for more details.
+
+//! The TrustBase chain extension, giving contracts read access to native runtime state.
+//!
+//! A contract picks the function to run with the `func_id` it passes to
+//! `seal_call_chain_extension`. The upper 16 bits of an id are the version of the function and
+//! the lower 16 bits its number. The input and output encoding of an id never changes: an
+//! incompatible change is released under a new version, and the old id keeps working.
+//!
+//! All inputs and outputs are SCALE encoded. Every function returns `0` on success; calling an
+//! unknown id or passing malformed input traps the contract.
+//!
+//! | id            | function                    | input                          | output                                       |
+//! |---------------|-----------------------------|--------------------------------|----------------------------------------------|
+//! | `0x0001_0001` | [`FuncId::Balance`]         | `AccountId`                    | `pallet_balances::AccountData<Balance>`      |
+//! | `0x0001_0002` | [`FuncId::Locks`]           | `AccountId`                    | `Vec<pallet_balances::BalanceLock<Balance>>` |
+//! | `0x0001_0003` | [`FuncId::IsSocietyMember`] | `AccountId`                    | `bool`                                       |
+//! | `0x0001_0004` | [`FuncId::Sr25519Verify`]   | `(Signature, Public, Vec<u8>)` | `bool`                                       |
+//! | `0x0001_0005` | [`FuncId::EpochRandomness`] | `[u8; 32]`                     | `(Hash, BlockNumber)`                        |
+
+pub mod weights;
+#[cfg(feature = "runtime-benchmarks")]
+pub mod benchmarking;
+
+use codec::{Decode, Encode};
+use frame_support::{RuntimeDebug, traits::{Get, Randomness}};
+use pallet_contracts::chain_extension::{
+	ChainExtension, Environment, Ext, InitState, RetVal, SysConfig, UncheckedFrom,
+};
+use sp_core::sr25519;
+use sp_runtime::DispatchError;
+use sp_std::{convert::TryFrom, prelude::*};
+use node_primitives::{AccountId, Balance, BlockNumber, Hash};
+use crate::{Balances, MaxLocks, Runtime, Society, System};
+use weights::WeightInfo;
+
+/// The functions of the chain extension, by their stable id.
+#[derive(Clone, Copy, PartialEq, Eq, RuntimeDebug)]
+#[repr(u32)]
+pub enum FuncId {
+	/// The free, reserved and frozen balance of an account.
+	Balance = 0x0001_0001,
+	/// The balance locks of an account.
+	Locks = 0x0001_0002,
+	/// Whether an account is a member of the society.
+	IsSocietyMember = 0x0001_0003,
+	/// Whether a signature over a message is valid for an sr25519 public key.
+	Sr25519Verify = 0x0001_0004,
+	/// BABE randomness of one epoch ago, mixed with a subject, and the block from which on it
+	/// is known.
+	EpochRandomness = 0x0001_0005,
+}
+
+impl TryFrom<u32> for FuncId {
+	type Error = DispatchError;
+
+	fn try_from(id: u32) -> Result<Self, Self::Error> {
+		match id {
+			0x0001_0001 => Ok(FuncId::Balance),
+			0x0001_0002 => Ok(FuncId::Locks),
+			0x0001_0003 => Ok(FuncId::IsSocietyMember),
+			0x0001_0004 => Ok(FuncId::Sr25519Verify),
+			0x0001_0005 => Ok(FuncId::EpochRandomness),
+			_ => Err(DispatchError::Other("Unknown chain extension function")),
+		}
+	}
+}
+
+/// The chain extension of the TrustBase runtime.
+pub struct TrustBaseExtension;
+
+impl TrustBaseExtension {
+	/// See [`FuncId::Balance`].
+	pub fn balance(who: &AccountId) -> pallet_balances::AccountData<Balance> {
+		System::account(who).data
+	}
+
+	/// See [`FuncId::Locks`].
+	pub fn locks(who: &AccountId) -> Vec<pallet_balances::BalanceLock<Balance>> {
+		Balances::locks(who).to_vec()
+	}
+
+	/// See [`FuncId::IsSocietyMember`].
+	pub fn is_society_member(who: &AccountId) -> bool {
+		Society::members().binary_search(who).is_ok()
+	}
+
+	/// See [`FuncId::Sr25519Verify`].
+	pub fn sr25519_verify(
+		signature: &sr25519::Signature,
+		public: &sr25519::Public,
+		message: &[u8],
+	) -> bool {
+		sp_io::crypto::sr25519_verify(signature, message, public)
+	}
+
+	/// See [`FuncId::EpochRandomness`].
+	pub fn epoch_randomness(subject: &[u8; 32]) -> (Hash, BlockNumber) {
+		pallet_babe::RandomnessFromOneEpochAgo::<Runtime>::random(subject)
+	}
+}
+
+impl ChainExtension<Runtime> for TrustBaseExtension {
+	fn call<E>(func_id: u32, env: Environment<E, InitState>) -> Result<RetVal, DispatchError>
+	where
+		E: Ext<T = Runtime>,
+		<E::T as SysConfig>::AccountId: UncheckedFrom<<E::T as SysConfig>::Hash> + AsRef<[u8]>,
+	{
+		type W = weights::SubstrateWeight<Runtime>;
+		let mut env = env.buf_in_buf_out();
+		let output = match FuncId::try_from(func_id)? {
+			FuncId::Balance => {
+				env.charge_weight(W::balance())?;
+				let who: AccountId = env.read_as()?;
+				Self::balance(&who).encode()
+			},
+			FuncId::Locks => {
+				env.charge_weight(W::locks(MaxLocks::get()))?;
+				let who: AccountId = env.read_as()?;
+				Self::locks(&who).encode()
+			},
+			FuncId::IsSocietyMember => {
+				env.charge_weight(W::is_society_member(Society::max_members()))?;
+				let who: AccountId = env.read_as()?;
+				Self::is_society_member(&who).encode()
+			},
+			FuncId::Sr25519Verify => {
+				let len = env.in_len();
+				env.charge_weight(W::sr25519_verify(len))?;
+				let input = env.read(len)?;
+				let (signature, public, message) =
+					<(sr25519::Signature, sr25519::Public, Vec<u8>)>::decode(&mut &input[..])
+						.map_err(|_| DispatchError::Other("Malformed chain extension input"))?;
+				Self::sr25519_verify(&signature, &public, &message).encode()
+			},
+			FuncId::EpochRandomness => {
+				env.charge_weight(W::epoch_randomness())?;
+				let subject: [u8; 32] = env.read_as()?;
+				Self::epoch_randomness(&subject).encode()
+			},
+		};
+		env.write(&output, false, None)?;
+		Ok(RetVal::Converging(0))
+	}
+}
@@ -0,0 +1,88 @@
+// Copyright 2019 TrustBase Network
+// This file is part of TrustBase library.
+//
+// The TrustBase library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Lesser General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// The TrustBase library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU Lesser General Public License for more details.
+//
+// You should have received a copy of the GNU Lesser General Public License
+// along with the TrustBase library. If not, see <http://www.gnu.org/licenses/>.
+
+//! Weights for trustbase_chain_extension
+//!
+//! Regenerate with `./scripts/benchmark.sh trustbase_chain_extension` on reference hardware.
+//! Until then the times are estimates; the storage accesses are counted from the code.
+
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+
+use frame_support::{traits::Get, weights::{Weight, constants::RocksDbWeight}};
+use sp_std::marker::PhantomData;
+
+/// Weight functions needed for trustbase_chain_extension.
+pub trait WeightInfo {
+	fn balance() -> Weight;
+	fn locks(l: u32, ) -> Weight;
+	fn is_society_member(m: u32, ) -> Weight;
+	fn sr25519_verify(n: u32, ) -> Weight;
+	fn epoch_randomness() -> Weight;
+}
+
+/// Weights for trustbase_chain_extension using the TrustBase node and recommended hardware.
+pub struct SubstrateWeight<T>(PhantomData<T>);
+impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
+	fn balance() -> Weight {
+		(12_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+	}
+	fn locks(l: u32, ) -> Weight {
+		(12_000_000 as Weight)
+			.saturating_add((170_000 as Weight).saturating_mul(l as Weight))
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+	}
+	fn is_society_member(m: u32, ) -> Weight {
+		(13_000_000 as Weight)
+			.saturating_add((40_000 as Weight).saturating_mul(m as Weight))
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+	}
+	fn sr25519_verify(n: u32, ) -> Weight {
+		(49_000_000 as Weight)
+			.saturating_add((2_000 as Weight).saturating_mul(n as Weight))
+	}
+	fn epoch_randomness() -> Weight {
+		(7_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+	}
+}
+
+// For backwards compatibility and tests
+impl WeightInfo for () {
+	fn balance() -> Weight {
+		(12_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
+	}
+	fn locks(l: u32, ) -> Weight {
+		(12_000_000 as Weight)
+			.saturating_add((170_000 as Weight).saturating_mul(l as Weight))
+			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
+	}
+	fn is_society_member(m: u32, ) -> Weight {
+		(13_000_000 as Weight)
+			.saturating_add((40_000 as Weight).saturating_mul(m as Weight))
+			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
+	}
+	fn sr25519_verify(n: u32, ) -> Weight {
+		(49_000_000 as Weight)
+			.saturating_add((2_000 as Weight).saturating_mul(n as Weight))
+	}
+	fn epoch_randomness() -> Weight {
+		(7_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
+	}
+}
@@ -24,6 +24,15 @@ pub mod currency {
 	pub const fn deposit(items: u32, bytes: u32) -> Balance {
 		items as Balance * 15 * CENTS + (bytes as Balance) * 6 * CENTS
 	}
+
+	/// The deposit for `items` storage items of `bytes` bytes in total, priced by the
+	/// `DepositPerItem` and `DepositPerByte` runtime parameters.
+	///
+	/// Only for pallets that keep the amount they reserved, so that a new price does not change
+	/// what they unreserve. Pallets that recompute their deposits use the constant [`deposit`].
+	pub fn governed_deposit(items: u32, bytes: u32) -> Balance {
+		items as Balance * crate::DepositPerItem::get() + (bytes as Balance) * crate::DepositPerByte::get()
+	}
 }
 
 /// Time.
@@ -0,0 +1,612 @@
+// Copyright 2019 TrustBase Network
+// This file is part of TrustBase library.
+//
+// The TrustBase library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Lesser General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// The TrustBase library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU Lesser General Public License for more details.
+
+//! A summary of the open governance business, served through the governance runtime API.
+//!
+//! Deadlines are given with the time they are expected at, counting `MILLISECS_PER_BLOCK` for
+//! every block from the current one. Stored items that do not decode are left out, logged, and
+//! counted in the overview.
+
+use sp_std::prelude::*;
+use codec::{Decode, DecodeAll, Encode};
+use frame_support::{
+	ReversibleStorageHasher, Twox64Concat,
+	storage::{migration, unhashed},
+	traits::{Get, GetCallMetadata},
+};
+use pallet_governance_rpc_runtime_api::{
+	BlockTime, Bounty, BountyStatus, CallName, GovernanceOverview, Motion, PublicProposal,
+	Referendum, Scheduled, TreasuryProposal, VoteThreshold,
+};
+use node_primitives::{AccountId, Balance, BlockNumber, Hash, Moment};
+use crate::{
+	Runtime, Call, System, Timestamp, Democracy, Council, TechnicalCommittee, Treasury, Bounties,
+	constants::time::MILLISECS_PER_BLOCK,
+};
+
+/// Copies of the storage types of the governance pallets, whose fields are private to them.
+///
+/// The stored values are encoded and decoded again as these, so their fields must be kept in
+/// line with the pallets.
+mod layout {
+	use super::*;
+	use sp_std::cell::Cell;
+
+	/// `pallet_democracy::ReferendumStatus`.
+	#[derive(Decode)]
+	pub struct ReferendumStatus {
+		pub end: BlockNumber,
+		pub proposal_hash: Hash,
+		pub threshold: pallet_democracy::VoteThreshold,
+		pub delay: BlockNumber,
+		pub tally: Tally,
+	}
+
+	/// `pallet_democracy::Tally`.
+	#[derive(Decode)]
+	pub struct Tally {
+		pub ayes: Balance,
+		pub nays: Balance,
+		pub turnout: Balance,
+	}
+
+	/// `pallet_collective::Votes`.
+	#[derive(Decode)]
+	pub struct Votes {
+		pub index: u32,
+		pub threshold: u32,
+		pub ayes: Vec<AccountId>,
+		pub nays: Vec<AccountId>,
+		pub end: BlockNumber,
+	}
+
+	/// `pallet_treasury::Proposal`.
+	#[derive(Decode)]
+	pub struct Proposal {
+		pub proposer: AccountId,
+		pub value: Balance,
+		pub beneficiary: AccountId,
+		pub bond: Balance,
+	}
+
+	/// `pallet_bounties::Bounty`.
+	#[derive(Decode)]
+	pub struct Bounty {
+		pub proposer: AccountId,
+		pub value: Balance,
+		pub fee: Balance,
+		pub _curator_deposit: Balance,
+		pub bond: Balance,
+		pub status: pallet_bounties::BountyStatus<AccountId, BlockNumber>,
+	}
+
+	/// `pallet_scheduler::ScheduledV2`.
+	#[derive(Decode)]
+	pub struct Scheduled {
+		pub maybe_id: Option<Vec<u8>>,
+		pub priority: u8,
+		pub call: Call,
+		pub maybe_periodic: Option<(BlockNumber, u32)>,
+		pub _origin: crate::OriginCaller,
+	}
+
+	/// Reads stored values as their layouts, and counts those that do not decode.
+	#[derive(Default)]
+	pub struct Reader {
+		undecodable: Cell<u32>,
+	}
+
+	impl Reader {
+		/// Reads `value` as its layout `L`, which must decode all of it.
+		pub fn read<L: Decode>(&self, value: impl Encode) -> Option<L> {
+			self.read_raw(&value.encode())
+		}
+
+		/// Reads the encoded value `raw` as its layout `L`, which must decode all of it.
+		pub fn read_raw<L: Decode>(&self, raw: &[u8]) -> Option<L> {
+			let read = L::decode_all(raw).ok();
+			if read.is_none() {
+				log::error!(
+					target: "runtime::governance",
+					"a stored value does not decode as {}",
+					sp_std::any::type_name::<L>(),
+				);
+				self.undecodable.set(self.undecodable.get().saturating_add(1));
+			}
+			read
+		}
+
+		/// The number of values that did not decode.
+		pub fn undecodable(&self) -> u32 {
+			self.undecodable.get()
+		}
+	}
+}
+
+/// `block` with the time it is expected at.
+pub fn block_time(block: BlockNumber) -> BlockTime<BlockNumber> {
+	let now = System::block_number();
+	let timestamp = if block >= now {
+		Timestamp::now().saturating_add(Moment::from(block - now).saturating_mul(MILLISECS_PER_BLOCK))
+	} else {
+		Timestamp::now().saturating_sub(Moment::from(now - block).saturating_mul(MILLISECS_PER_BLOCK))
+	};
+
+	BlockTime { block, timestamp }
+}
+
+/// The first block after the current one that a period of `period` blocks ends at.
+fn next_period_end(period: BlockNumber) -> BlockNumber {
+	let period = period.max(1);
+	(System::block_number() / period).saturating_add(1).saturating_mul(period)
+}
+
+fn call_name(call: &Call) -> CallName {
+	let metadata = call.get_call_metadata();
+	CallName {
+		pallet: metadata.pallet_name.as_bytes().to_vec(),
+		function: metadata.function_name.as_bytes().to_vec(),
+	}
+}
+
+/// The call of a democracy proposal, if its preimage is noted.
+fn preimage_call(proposal_hash: &Hash) -> Option<CallName> {
+	let preimage = migration::get_storage_value::<
+		pallet_democracy::PreimageStatus<AccountId, Balance, BlockNumber>,
+	>(b"Democracy", b"Preimages", &proposal_hash.encode())?;
+
+	match preimage {
+		pallet_democracy::PreimageStatus::Available { data, .. } =>
+			Call::decode(&mut &data[..]).ok().as_ref().map(call_name),
+		pallet_democracy::PreimageStatus::Missing(_) => None,
+	}
+}
+
+fn referenda(reader: &layout::Reader) -> Vec<Referendum<Hash, Balance, BlockNumber>> {
+	(Democracy::lowest_unbaked()..Democracy::referendum_count())
+		.filter_map(|index| match Democracy::referendum_info(index)? {
+			pallet_democracy::ReferendumInfo::Ongoing(status) => Some((index, status)),
+			pallet_democracy::ReferendumInfo::Finished { .. } => None,
+		})
+		.filter_map(|(index, status)| {
+			let status = reader.read::<layout::ReferendumStatus>(status)?;
+			Some(Referendum {
+				index,
+				call: preimage_call(&status.proposal_hash),
+				proposal_hash: status.proposal_hash,
+				threshold: match status.threshold {
+					pallet_democracy::VoteThreshold::SuperMajorityApprove => VoteThreshold::SuperMajorityApprove,
+					pallet_democracy::VoteThreshold::SuperMajorityAgainst => VoteThreshold::SuperMajorityAgainst,
+					pallet_democracy::VoteThreshold::SimpleMajority => VoteThreshold::SimpleMajority,
+				},
+				ayes: status.tally.ayes,
+				nays: status.tally.nays,
+				turnout: status.tally.turnout,
+				end: block_time(status.end),
+				delay: status.delay,
+			})
+		})
+		.collect()
+}
+
+/// The block the most backed public proposal is launched at.
+///
+/// Launch periods alternate between public and external proposals, so a waiting external
+/// proposal comes first unless the last referendum launched was external.
+fn public_launch() -> BlockNumber {
+	let period = <Runtime as pallet_democracy::Config>::LaunchPeriod::get();
+	let last_was_external =
+		migration::get_storage_value::<bool>(b"Democracy", b"LastTabledWasExternal", &[]).unwrap_or(false);
+	let external_waiting = migration::get_storage_value::<(Hash, pallet_democracy::VoteThreshold)>(
+		b"Democracy",
+		b"NextExternal",
+		&[],
+	).is_some();
+
+	let next = next_period_end(period);
+	if external_waiting && !last_was_external { next.saturating_add(period.max(1)) } else { next }
+}
+
+fn public_proposals() -> Vec<PublicProposal<AccountId, Hash, Balance, BlockNumber>> {
+	let backing = |index| Democracy::deposit_of(index)
+		.map_or(0, |(seconds, deposit)| deposit.saturating_mul(seconds.len() as Balance));
+	let props = Democracy::public_props();
+	// as `launch_public`, the last of the most backed proposals in the order they are stored.
+	let top = props.iter().max_by_key(|(index, ..)| backing(*index)).map(|(index, ..)| *index);
+
+	let mut proposals: Vec<_> = props.into_iter()
+		.map(|(index, proposal_hash, proposer)| PublicProposal {
+			index,
+			call: preimage_call(&proposal_hash),
+			proposal_hash,
+			proposer,
+			deposit: Democracy::deposit_of(index).map_or(0, |(_, deposit)| deposit),
+			launch: if top == Some(index) { Some(block_time(public_launch())) } else { None },
+		})
+		.collect();
+	proposals.sort_by_key(|proposal| proposal.index);
+	proposals
+}
+
+/// The motions of a collective, given its `proposals` and how to read their votes and calls.
+fn motions(
+	proposals: impl Iterator<Item = Hash>,
+	voting: impl Fn(&Hash) -> Option<layout::Votes>,
+	proposal_of: impl Fn(&Hash) -> Option<Call>,
+) -> Vec<Motion<Hash, BlockNumber>> {
+	let mut motions: Vec<_> = proposals
+		.filter_map(|proposal_hash| {
+			let votes = voting(&proposal_hash)?;
+			let call = proposal_of(&proposal_hash)?;
+			Some(Motion {
+				index: votes.index,
+				proposal_hash,
+				call: call_name(&call),
+				threshold: votes.threshold,
+				ayes: votes.ayes.len() as u32,
+				nays: votes.nays.len() as u32,
+				end: block_time(votes.end),
+			})
+		})
+		.collect();
+	motions.sort_by_key(|motion| motion.index);
+	motions
+}
+
+fn treasury_proposals(
+	reader: &layout::Reader,
+	spend: &BlockTime<BlockNumber>,
+) -> Vec<TreasuryProposal<AccountId, Balance, BlockNumber>> {
+	let approvals = Treasury::approvals();
+	(0..Treasury::proposal_count())
+		.filter_map(|index| Some((index, reader.read::<layout::Proposal>(Treasury::proposals(index)?)?)))
+		.map(|(index, proposal)| TreasuryProposal {
+			index,
+			proposer: proposal.proposer,
+			beneficiary: proposal.beneficiary,
+			value: proposal.value,
+			bond: proposal.bond,
+			spend: if approvals.contains(&index) { Some(spend.clone()) } else { None },
+		})
+		.collect()
+}
+
+fn bounties(
+	reader: &layout::Reader,
+	spend: &BlockTime<BlockNumber>,
+) -> Vec<Bounty<AccountId, Balance, BlockNumber>> {
+	use pallet_bounties::BountyStatus as Status;
+
+	(0..Bounties::bounty_count())
+		.filter_map(|index| Some((index, reader.read::<layout::Bounty>(Bounties::bounties(index)?)?)))
+		.map(|(index, bounty)| {
+			let (status, deadline) = match bounty.status {
+				Status::Proposed => (BountyStatus::Proposed, None),
+				Status::Approved => (BountyStatus::Approved, Some(spend.clone())),
+				Status::Funded => (BountyStatus::Funded, None),
+				Status::CuratorProposed { curator } => (BountyStatus::CuratorProposed { curator }, None),
+				Status::Active { curator, update_due } =>
+					(BountyStatus::Active { curator }, Some(block_time(update_due))),
+				Status::PendingPayout { curator, beneficiary, unlock_at } =>
+					(BountyStatus::PendingPayout { curator, beneficiary }, Some(block_time(unlock_at))),
+			};
+			Bounty {
+				index,
+				description: Bounties::bounty_descriptions(index).map(Into::into).unwrap_or_default(),
+				proposer: bounty.proposer,
+				value: bounty.value,
+				fee: bounty.fee,
+				bond: bounty.bond,
+				status,
+				deadline,
+			}
+		})
+		.collect()
+}
+
+/// The agendas of the scheduler, by block.
+///
+/// The agendas are read from their raw values, so that one which does not decode is counted
+/// rather than silently skipped.
+fn agendas(reader: &layout::Reader) -> Vec<(BlockNumber, Vec<Option<layout::Scheduled>>)> {
+	let prefix = [sp_io::hashing::twox_128(b"Scheduler"), sp_io::hashing::twox_128(b"Agenda")].concat();
+	let mut agendas = Vec::new();
+	let mut key = prefix.clone();
+	while let Some(next) = sp_io::storage::next_key(&key).filter(|next| next.starts_with(&prefix)) {
+		key = next;
+		let agenda = reader.read_raw::<BlockNumber>(Twox64Concat::reverse(&key[prefix.len()..]))
+			.and_then(|when| Some((when, reader.read_raw(&unhashed::get_raw(&key)?)?)));
+		agendas.extend(agenda);
+	}
+	agendas
+}
+
+fn scheduled(reader: &layout::Reader) -> Vec<Scheduled<BlockNumber>> {
+	let mut scheduled: Vec<_> = agendas(reader).into_iter()
+		.flat_map(|(when, agenda)| agenda.into_iter().flatten().map(move |task| (when, task)))
+		.map(|(when, task)| Scheduled {
+			when: block_time(when),
+			id: task.maybe_id,
+			priority: task.priority,
+			call: call_name(&task.call),
+			periodic: task.maybe_periodic,
+		})
+		.collect();
+	scheduled.sort_by_key(|task| (task.when.block, task.priority));
+	scheduled
+}
+
+/// The open governance business at the current block.
+pub fn overview() -> GovernanceOverview<AccountId, Balance, BlockNumber, Hash> {
+	let spend = block_time(next_period_end(<Runtime as pallet_treasury::Config>::SpendPeriod::get()));
+	let reader = layout::Reader::default();
+
+	GovernanceOverview {
+		now: block_time(System::block_number()),
+		referenda: referenda(&reader),
+		public_proposals: public_proposals(),
+		council_motions: motions(
+			Council::proposals().into_iter(),
+			|hash| reader.read(Council::voting(hash)?),
+			|hash| Council::proposal_of(hash),
+		),
+		technical_committee_motions: motions(
+			TechnicalCommittee::proposals().into_iter(),
+			|hash| reader.read(TechnicalCommittee::voting(hash)?),
+			|hash| TechnicalCommittee::proposal_of(hash),
+		),
+		treasury_proposals: treasury_proposals(&reader, &spend),
+		bounties: bounties(&reader, &spend),
+		scheduled: scheduled(&reader),
+		undecodable: reader.undecodable(),
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use frame_support::assert_ok;
+	use sp_runtime::traits::{BlakeTwo256, Hash as _};
+	use crate::{Origin, OriginCaller, Balances, Scheduler, constants::currency::DOLLARS};
+
+	fn at(block: BlockNumber) -> BlockTime<BlockNumber> {
+		BlockTime { block, timestamp: 1_000_000 + Moment::from(block - 10) * MILLISECS_PER_BLOCK }
+	}
+
+	fn name(pallet: &str, function: &str) -> CallName {
+		CallName { pallet: pallet.as_bytes().to_vec(), function: function.as_bytes().to_vec() }
+	}
+
+	/// Run `f` at block 10 with alice, who is funded, and bob.
+	fn with_accounts(f: impl FnOnce(AccountId, AccountId)) {
+		sp_io::TestExternalities::default().execute_with(|| {
+			System::set_block_number(10);
+			let alice = AccountId::from([1u8; 32]);
+			Balances::make_free_balance_be(&alice, 1_000 * DOLLARS);
+			f(alice, AccountId::from([2u8; 32]))
+		});
+	}
+
+	#[test]
+	fn overview_summarizes_every_governance_pallet() {
+		sp_io::TestExternalities::default().execute_with(|| {
+			System::set_block_number(10);
+			Timestamp::set_timestamp(1_000_000);
+			let alice = AccountId::from([1u8; 32]);
+			let bob = AccountId::from([2u8; 32]);
+			Balances::make_free_balance_be(&alice, 1_000 * DOLLARS);
+			let remark = Call::System(frame_system::Call::remark(vec![]));
+
+			assert_ok!(Democracy::note_preimage(Origin::signed(alice.clone()), remark.encode()));
+			let remark_hash = <sp_runtime::traits::BlakeTwo256 as sp_runtime::traits::Hash>::hash_of(&remark);
+			Democracy::internal_start_referendum(remark_hash, pallet_democracy::VoteThreshold::SimpleMajority, 5);
+			assert_ok!(Council::set_members(Origin::root(), vec![alice.clone(), bob.clone()], None, 0));
+			assert_ok!(Council::propose(Origin::signed(alice.clone()), 2, Box::new(remark.clone()), 100));
+			assert_ok!(Treasury::propose_spend(Origin::signed(alice.clone()), 10 * DOLLARS, bob.clone().into()));
+			assert_ok!(Bounties::propose_bounty(Origin::signed(alice.clone()), 10 * DOLLARS, b"docs".to_vec()));
+			assert_ok!(Scheduler::schedule(Origin::root(), 20, None, 1, Box::new(remark.clone())));
+
+			let overview = overview();
+			assert_eq!(overview.now, at(10));
+			assert_eq!(overview.undecodable, 0);
+
+			let voting_period = <Runtime as pallet_democracy::Config>::VotingPeriod::get();
+			assert_eq!(overview.referenda.len(), 1);
+			assert_eq!(overview.referenda[0].call, Some(name("System", "remark")));
+			assert_eq!(overview.referenda[0].threshold, VoteThreshold::SimpleMajority);
+			assert_eq!(overview.referenda[0].end, at(10 + voting_period));
+			assert_eq!(overview.referenda[0].delay, 5);
+
+			let motion_duration = <Runtime as pallet_collective::Config<crate::CouncilCollective>>::MotionDuration::get();
+			assert_eq!(overview.council_motions.len(), 1);
+			assert_eq!(overview.council_motions[0].call, name("System", "remark"));
+			assert_eq!((overview.council_motions[0].threshold, overview.council_motions[0].ayes), (2, 1));
+			assert_eq!(overview.council_motions[0].end, at(10 + motion_duration));
+			assert!(overview.technical_committee_motions.is_empty());
+
+			assert_eq!(overview.treasury_proposals.len(), 1);
+			assert_eq!(overview.treasury_proposals[0].beneficiary, bob);
+			assert_eq!(overview.treasury_proposals[0].value, 10 * DOLLARS);
+			assert_eq!(overview.treasury_proposals[0].spend, None);
+
+			assert_eq!(overview.bounties.len(), 1);
+			assert_eq!(overview.bounties[0].description, b"docs".to_vec());
+			assert_eq!(overview.bounties[0].status, BountyStatus::Proposed);
+
+			assert_eq!(overview.scheduled.len(), 1);
+			assert_eq!(overview.scheduled[0].when, at(20));
+			assert_eq!(overview.scheduled[0].call, name("System", "remark"));
+
+			// approved spends wait for the next spend period.
+			assert_ok!(Treasury::approve_proposal(Origin::root(), 0));
+			let spend_period = <Runtime as pallet_treasury::Config>::SpendPeriod::get();
+			assert_eq!(overview().treasury_proposals[0].spend, Some(at(spend_period)));
+		});
+	}
+
+	#[test]
+	fn the_reader_counts_what_does_not_decode() {
+		let reader = layout::Reader::default();
+		assert!(reader.read::<layout::Tally>((1u128, 2u128, 3u128)).is_some());
+		assert!(reader.read::<layout::Tally>(1u8).is_none());
+		// a layout missing a field of the pallet type does not decode all of it.
+		assert!(reader.read::<layout::Tally>((1u128, 2u128, 3u128, 4u8)).is_none());
+		assert_eq!(reader.undecodable(), 2);
+	}
+
+	#[test]
+	fn referendum_status_layout_decodes_the_pallet_type() {
+		use pallet_democracy::{AccountVote, Conviction, ReferendumInfo, Vote};
+
+		with_accounts(|alice, _| {
+			let proposal_hash = Hash::repeat_byte(1);
+			let index = Democracy::internal_start_referendum(
+				proposal_hash,
+				pallet_democracy::VoteThreshold::SuperMajorityAgainst,
+				5,
+			);
+			assert_ok!(Democracy::vote(Origin::signed(alice), index, AccountVote::Standard {
+				vote: Vote { aye: true, conviction: Conviction::Locked2x },
+				balance: 10 * DOLLARS,
+			}));
+			let status = match Democracy::referendum_info(index) {
+				Some(ReferendumInfo::Ongoing(status)) => status,
+				_ => panic!("the referendum is ongoing"),
+			};
+
+			let reader = layout::Reader::default();
+			let status = reader.read::<layout::ReferendumStatus>(status).unwrap();
+			let voting_period = <Runtime as pallet_democracy::Config>::VotingPeriod::get();
+			assert_eq!((status.end, status.proposal_hash, status.delay), (10 + voting_period, proposal_hash, 5));
+			assert_eq!(status.threshold, pallet_democracy::VoteThreshold::SuperMajorityAgainst);
+			assert_eq!(
+				(status.tally.ayes, status.tally.nays, status.tally.turnout),
+				(20 * DOLLARS, 0, 10 * DOLLARS),
+			);
+			assert_eq!(reader.undecodable(), 0);
+		});
+	}
+
+	#[test]
+	fn votes_layout_decodes_the_pallet_type() {
+		with_accounts(|alice, bob| {
+			let remark = Call::System(frame_system::Call::remark(vec![]));
+			let proposal_hash = BlakeTwo256::hash_of(&remark);
+			assert_ok!(Council::set_members(Origin::root(), vec![alice.clone(), bob.clone()], None, 0));
+			assert_ok!(Council::propose(Origin::signed(alice.clone()), 2, Box::new(remark), 100));
+			assert_ok!(Council::vote(Origin::signed(bob.clone()), proposal_hash, 0, false));
+
+			let reader = layout::Reader::default();
+			let votes = reader.read::<layout::Votes>(Council::voting(proposal_hash).unwrap()).unwrap();
+			let motion_duration = <Runtime as pallet_collective::Config<crate::CouncilCollective>>::MotionDuration::get();
+			assert_eq!((votes.index, votes.threshold, votes.end), (0, 2, 10 + motion_duration));
+			assert_eq!((votes.ayes, votes.nays), (vec![alice], vec![bob]));
+			assert_eq!(reader.undecodable(), 0);
+		});
+	}
+
+	#[test]
+	fn proposal_layout_decodes_the_pallet_type() {
+		with_accounts(|alice, bob| {
+			assert_ok!(Treasury::propose_spend(Origin::signed(alice.clone()), 10 * DOLLARS, bob.clone().into()));
+
+			let reader = layout::Reader::default();
+			let proposal = reader.read::<layout::Proposal>(Treasury::proposals(0).unwrap()).unwrap();
+			assert_eq!((proposal.proposer, proposal.beneficiary), (alice.clone(), bob));
+			assert_eq!((proposal.value, proposal.bond), (10 * DOLLARS, Balances::reserved_balance(&alice)));
+			assert_eq!(reader.undecodable(), 0);
+		});
+	}
+
+	#[test]
+	fn bounty_layout_decodes_the_pallet_type() {
+		with_accounts(|alice, _| {
+			assert_ok!(Bounties::propose_bounty(Origin::signed(alice.clone()), 10 * DOLLARS, b"docs".to_vec()));
+
+			let reader = layout::Reader::default();
+			let bounty = reader.read::<layout::Bounty>(Bounties::bounties(0).unwrap()).unwrap();
+			assert_eq!(bounty.proposer, alice.clone());
+			assert_eq!((bounty.value, bounty.fee, bounty.bond), (10 * DOLLARS, 0, Balances::reserved_balance(&alice)));
+			assert_eq!(bounty.status, pallet_bounties::BountyStatus::Proposed);
+			assert_eq!(reader.undecodable(), 0);
+		});
+	}
+
+	#[test]
+	fn scheduled_layout_decodes_the_pallet_type() {
+		with_accounts(|_, _| {
+			let remark = Call::System(frame_system::Call::remark(vec![]));
+			assert_ok!(Scheduler::schedule_named(
+				Origin::root(),
+				b"task".to_vec(),
+				20,
+				Some((3, 2)),
+				7,
+				Box::new(remark.clone()),
+			));
+
+			let reader = layout::Reader::default();
+			let (when, agenda) = agendas(&reader).pop().unwrap();
+			let task = agenda.into_iter().flatten().next().unwrap();
+			assert_eq!((when, task.maybe_id, task.priority), (20, Some(b"task".to_vec()), 7));
+			assert_eq!((task.call, task.maybe_periodic), (remark, Some((3, 2))));
+			assert_eq!(task._origin, OriginCaller::system(frame_system::RawOrigin::Root));
+			assert_eq!(reader.undecodable(), 0);
+		});
+	}
+
+	#[test]
+	fn agendas_that_do_not_decode_are_counted() {
+		with_accounts(|_, _| {
+			Timestamp::set_timestamp(1_000_000);
+			let remark = Call::System(frame_system::Call::remark(vec![]));
+			assert_ok!(Scheduler::schedule(Origin::root(), 20, None, 1, Box::new(remark)));
+			migration::put_storage_value(b"Scheduler", b"Agenda", &Twox64Concat::hash(&30u32.encode()), 1u8);
+
+			let overview = overview();
+			assert_eq!(overview.scheduled.len(), 1);
+			assert_eq!(overview.scheduled[0].when, at(20));
+			assert_eq!(overview.undecodable, 1);
+		});
+	}
+
+	#[test]
+	fn only_the_most_backed_public_proposal_launches() {
+		with_accounts(|alice, _| {
+			Timestamp::set_timestamp(1_000_000);
+			let deposit = <Runtime as pallet_democracy::Config>::MinimumDeposit::get();
+			assert_ok!(Democracy::propose(Origin::signed(alice.clone()), Hash::repeat_byte(1), deposit));
+			assert_ok!(Democracy::propose(Origin::signed(alice.clone()), Hash::repeat_byte(2), 2 * deposit));
+			let launches = || overview().public_proposals.into_iter()
+				.map(|proposal| proposal.launch)
+				.collect::<Vec<_>>();
+
+			let launch_period = <Runtime as pallet_democracy::Config>::LaunchPeriod::get();
+			assert_eq!(launches(), vec![None, Some(at(launch_period))]);
+
+			// seconds count towards the backing.
+			assert_ok!(Democracy::second(Origin::signed(alice.clone()), 0, u32::max_value()));
+			assert_ok!(Democracy::second(Origin::signed(alice), 0, u32::max_value()));
+			assert_eq!(launches(), vec![Some(at(launch_period)), None]);
+
+			// a waiting external proposal takes the next launch.
+			migration::put_storage_value(
+				b"Democracy",
+				b"NextExternal",
+				&[],
+				(Hash::repeat_byte(3), pallet_democracy::VoteThreshold::SimpleMajority),
+			);
+			assert_eq!(launches(), vec![Some(at(2 * launch_period)), None]);
+		});
+	}
+}
@@ -30,8 +30,8 @@ use frame_support::{
 		DispatchClass,
 	},
 	traits::{
-		Currency, Imbalance, KeyOwnerProofSystem, OnUnbalanced, LockIdentifier,
-		U128CurrencyToVote, MaxEncodedLen,
+		Currency, KeyOwnerProofSystem, OnUnbalanced, LockIdentifier,
+		U128CurrencyToVote, MaxEncodedLen, Filter,
 	},
 };
 use frame_system::{
@@ -46,7 +46,7 @@ use sp_core::{
 	OpaqueMetadata,
 };
 pub use node_primitives::{AccountId, Signature};
-use node_primitives::{AccountIndex, Balance, BlockNumber, Hash, Index, Moment};
+use node_primitives::{AccountIndex, AssetId, Balance, BlockNumber, Hash, Index, Moment};
 use sp_api::impl_runtime_apis;
 use sp_runtime::{
 	Permill, Perbill, Perquintill, Percent, ApplyExtrinsicResult, impl_opaque_keys, generic,
@@ -56,7 +56,7 @@ use sp_runtime::curve::PiecewiseLinear;
 use sp_runtime::transaction_validity::{TransactionValidity, TransactionSource, TransactionPriority};
 use sp_runtime::traits::{
 	self, BlakeTwo256, Block as BlockT, StaticLookup, SaturatedConversion, OpaqueKeys,
-	NumberFor,
+	NumberFor, ConvertInto,
 };
 use sp_version::RuntimeVersion;
 #[cfg(any(feature = "std", test))]
@@ -68,6 +68,7 @@ use sp_authority_discovery::AuthorityId as AuthorityDiscoveryId;
 use pallet_transaction_payment::{FeeDetails, RuntimeDispatchInfo};
 pub use pallet_transaction_payment::{Multiplier, TargetedFeeAdjustment, CurrencyAdapter};
 use pallet_session::{historical as pallet_session_historical};
+use pallet_fee_split::{ImbalanceKind, Split, Splits};
 use sp_inherents::{InherentData, CheckInherentsResult};
 use static_assertions::const_assert;
 use pallet_contracts::weights::WeightInfo;
@@ -85,6 +86,18 @@ pub use pallet_staking::StakerStatus;
 pub mod impls;
 use impls::Author;
 
+/// The chain extension available to contracts.
+pub mod chain_extension;
+
+/// Storage migrations run on runtime upgrades.
+pub mod migrations;
+
+/// Staking reward and unbonding projections for the staking runtime API.
+pub mod staking_projection;
+
+/// The governance overview for the governance runtime API.
+pub mod governance;
+
 /// Constant values used within the runtime.
 pub mod constants;
 use constants::{time::*, currency::*};
@@ -112,10 +125,10 @@ pub const VERSION: RuntimeVersion = RuntimeVersion {
 	// and set impl_version to 0. If only runtime
 	// implementation changes and behavior does not, then leave spec_version as
 	// is and increment impl_version.
-	spec_version: 1,
-	impl_version: 1,
+	spec_version: 2,
+	impl_version: 0,
 	apis: RUNTIME_API_VERSIONS,
-	transaction_version: 1,
+	transaction_version: 2,
 };
 
 /// The BABE epoch configuration at genesis.
@@ -140,18 +153,56 @@ pub struct DealWithFees;
 impl OnUnbalanced<NegativeImbalance> for DealWithFees {
 	fn on_unbalanceds<B>(mut fees_then_tips: impl Iterator<Item=NegativeImbalance>) {
 		if let Some(fees) = fees_then_tips.next() {
-			// for fees, 80% to treasury, 20% to author
-			let mut split = fees.ration(80, 20);
+			FeeSplit::distribute(ImbalanceKind::Fee, fees);
 			if let Some(tips) = fees_then_tips.next() {
-				// for tips, if any, 80% to treasury, 20% to author (though this can be anything)
-				tips.ration_merge_into(80, 20, &mut split);
+				FeeSplit::distribute(ImbalanceKind::Tip, tips);
 			}
-			Treasury::on_unbalanced(split.0);
-			Author::on_unbalanced(split.1);
 		}
 	}
 }
 
+/// Distributes the funds the treasury burns at the end of every spend period.
+pub struct DealWithBurn;
+impl OnUnbalanced<NegativeImbalance> for DealWithBurn {
+	fn on_nonzero_unbalanced(amount: NegativeImbalance) {
+		FeeSplit::distribute(ImbalanceKind::Burn, amount);
+	}
+}
+
+/// Distributes the dust of reaped accounts.
+pub struct DealWithDust;
+impl OnUnbalanced<NegativeImbalance> for DealWithDust {
+	fn on_nonzero_unbalanced(amount: NegativeImbalance) {
+		FeeSplit::distribute(ImbalanceKind::Dust, amount);
+	}
+}
+
+impl pallet_asset_tx_payment::HandleAssetFees<AccountId, AssetId, Balance> for DealWithFees {
+	fn handle_asset_fees(asset: AssetId, fee: Balance, tip: Balance) {
+		use frame_support::traits::tokens::fungibles::Mutate;
+
+		// fees and tips paid in an asset are split like native ones, the burnt share by not
+		// minting it again. A share that cannot be held by the author or the ecosystem account
+		// goes to the treasury, and one that cannot be held by the treasury is burnt.
+		let splits = FeeSplit::splits();
+		let share = |of: fn(&Split) -> Permill| (of(&splits.fee) * fee).saturating_add(of(&splits.tip) * tip);
+		let to_author = share(|split| split.author);
+		let to_ecosystem = share(|split| split.ecosystem);
+		let mut to_treasury = fee.saturating_add(tip)
+			.saturating_sub(to_author)
+			.saturating_sub(to_ecosystem)
+			.saturating_sub(share(|split| split.burn));
+		if Assets::mint_into(asset, &Authorship::author(), to_author).is_err() {
+			to_treasury = to_treasury.saturating_add(to_author);
+		}
+		match pallet_fee_split::EcosystemAccount::<Runtime>::get() {
+			Some(account) if Assets::mint_into(asset, &account, to_ecosystem).is_ok() => (),
+			_ => to_treasury = to_treasury.saturating_add(to_ecosystem),
+		}
+		let _ = Assets::mint_into(asset, &Treasury::account_id(), to_treasury);
+	}
+}
+
 /// We assume that ~10% of the block weight is consumed by `on_initialize` handlers.
 /// This is used to limit the maximal weight of a single extrinsic.
 const AVERAGE_ON_INITIALIZE_RATIO: Perbill = Perbill::from_percent(10);
@@ -189,8 +240,27 @@ parameter_types! {
 
 const_assert!(NORMAL_DISPATCH_RATIO.deconstruct() >= AVERAGE_ON_INITIALIZE_RATIO.deconstruct());
 
+/// Calls that no signed origin may dispatch.
+pub struct BaseFilter;
+impl Filter<Call> for BaseFilter {
+	fn filter(call: &Call) -> bool {
+		// Assets are permissioned: they can only be created by governance via `force_create`.
+		!matches!(call, Call::Assets(pallet_assets::Call::create(..))) &&
+			// Spending proxies are tied to the allowance of their real account, so only
+			// `SpendingLimits` may add or remove them.
+			!matches!(
+				call,
+				Call::Proxy(pallet_proxy::Call::add_proxy(_, ProxyType::SpendingLimit(..), _)) |
+				Call::Proxy(pallet_proxy::Call::remove_proxy(_, ProxyType::SpendingLimit(..), _)) |
+				Call::Proxy(pallet_proxy::Call::anonymous(ProxyType::SpendingLimit(..), ..))
+			) &&
+			<SafeMode as Filter<Call>>::filter(call) &&
+			<SudoRetirement as Filter<Call>>::filter(call)
+	}
+}
+
 impl frame_system::Config for Runtime {
-	type BaseCallFilter = ();
+	type BaseCallFilter = BaseFilter;
 	type BlockWeights = RuntimeBlockWeights;
 	type BlockLength = RuntimeBlockLength;
 	type DbWeight = RocksDbWeight;
@@ -212,11 +282,9 @@ impl frame_system::Config for Runtime {
 	type OnKilledAccount = ();
 	type SystemWeightInfo = frame_system::weights::SubstrateWeight<Runtime>;
 	type SS58Prefix = SS58Prefix;
-	type OnSetCode = ();
+	type OnSetCode = AuthorizedUpgrade;
 }
 
-impl pallet_randomness_collective_flip::Config for Runtime {}
-
 impl pallet_utility::Config for Runtime {
 	type Event = Event;
 	type Call = Call;
@@ -225,9 +293,9 @@ impl pallet_utility::Config for Runtime {
 
 parameter_types! {
 	// One storage item; key size is 32; value is size 4+4+16+32 bytes = 56 bytes.
-	pub const DepositBase: Balance = deposit(1, 88);
+	pub DepositBase: Balance = governed_deposit(1, 88);
 	// Additional storage item size of 32 bytes.
-	pub const DepositFactor: Balance = deposit(0, 32);
+	pub DepositFactor: Balance = governed_deposit(0, 32);
 	pub const MaxSignatories: u16 = 100;
 }
 
@@ -243,12 +311,12 @@ impl pallet_multisig::Config for Runtime {
 
 parameter_types! {
 	// One storage item; key size 32, value size 8; .
-	pub const ProxyDepositBase: Balance = deposit(1, 8);
+	pub ProxyDepositBase: Balance = governed_deposit(1, 8);
 	// Additional storage item size of 33 bytes.
-	pub const ProxyDepositFactor: Balance = deposit(0, 33);
+	pub ProxyDepositFactor: Balance = governed_deposit(0, 33);
 	pub const MaxProxies: u16 = 32;
-	pub const AnnouncementDepositBase: Balance = deposit(1, 8);
-	pub const AnnouncementDepositFactor: Balance = deposit(0, 66);
+	pub AnnouncementDepositBase: Balance = governed_deposit(1, 8);
+	pub AnnouncementDepositFactor: Balance = governed_deposit(0, 66);
 	pub const MaxPending: u16 = 32;
 }
 
@@ -259,38 +327,116 @@ pub enum ProxyType {
 	NonTransfer,
 	Governance,
 	Staking,
+	IdentityJudgement,
+	Recovery,
+	CancelProxy,
+	Contracts,
+	SessionKeys,
+	Treasury,
+	Society,
+	/// Transfers through `SpendingLimits::spend`, up to the allowance with the given id. Only
+	/// added through `SpendingLimits::add_spending_proxy`.
+	SpendingLimit(pallet_spending_limit::AllowanceId),
 }
 impl Default for ProxyType { fn default() -> Self { Self::Any } }
-impl InstanceFilter<Call> for ProxyType {
-	fn filter(&self, c: &Call) -> bool {
+impl ProxyType {
+	/// Whether the proxy may dispatch `c` itself. Calls nested in `c` are not looked at.
+	fn allows(&self, c: &Call) -> bool {
 		match self {
 			ProxyType::Any => true,
 			ProxyType::NonTransfer => !matches!(
 				c,
 				Call::Balances(..) |
-				// Call::Assets(..) |
-				// Call::Uniques(..) |
-				// Call::Vesting(pallet_vesting::Call::vested_transfer(..)) |
-				Call::Indices(pallet_indices::Call::transfer(..))
-			),
+				Call::Assets(..) |
+				Call::Uniques(..) |
+				Call::Recovery(pallet_recovery::Call::as_recovered(..)) |
+				Call::Society(pallet_society::Call::bid(..)) |
+				Call::Vesting(pallet_vesting::Call::vested_transfer(..)) |
+				Call::Indices(pallet_indices::Call::transfer(..)) |
+				// `add_spending_proxy` grants proxies without the `is_superset` check of
+				// `Proxy::add_proxy`.
+				Call::SpendingLimits(..)
+			) && !Self::moves_contract_value(c),
 			ProxyType::Governance => matches!(
 				c,
 				Call::Democracy(..) |
 				Call::Council(..) |
-				Call::Society(..) |
 				Call::TechnicalCommittee(..) |
 				Call::Elections(..) |
-				Call::Treasury(..)
+				Call::Treasury(..) |
+				Call::Bounties(..) |
+				Call::Tips(..)
+			),
+			ProxyType::Staking => matches!(
+				c,
+				Call::Staking(..) |
+				Call::Session(..) |
+				Call::NominationPools(..)
+			),
+			ProxyType::IdentityJudgement => matches!(
+				c,
+				Call::Identity(pallet_identity::Call::provide_judgement(..))
+			),
+			ProxyType::Recovery => matches!(c, Call::Recovery(..)),
+			ProxyType::CancelProxy => matches!(
+				c,
+				Call::Proxy(pallet_proxy::Call::reject_announcement(..))
+			),
+			ProxyType::Contracts => matches!(c, Call::Contracts(..)),
+			ProxyType::SessionKeys => matches!(c, Call::Session(..)),
+			ProxyType::Treasury => matches!(
+				c,
+				Call::Treasury(..) |
+				Call::Bounties(..) |
+				Call::Tips(..)
+			),
+			ProxyType::Society => matches!(c, Call::Society(..)),
+			ProxyType::SpendingLimit(id) => matches!(
+				c,
+				Call::SpendingLimits(pallet_spending_limit::Call::spend(allowance, ..)) if allowance == id
 			),
-			ProxyType::Staking => matches!(c, Call::Staking(..)),
 		}
 	}
+
+	/// Whether the proxy may dispatch `c`, and every call batched in it.
+	fn allows_nested(&self, c: &Call) -> bool {
+		match c {
+			Call::Utility(pallet_utility::Call::batch(calls)) |
+			Call::Utility(pallet_utility::Call::batch_all(calls)) =>
+				calls.iter().all(|call| self.allows_nested(call)),
+			Call::Utility(pallet_utility::Call::as_derivative(_, call)) => self.allows_nested(call),
+			_ => self.allows(c),
+		}
+	}
+
+	/// Whether `c` endows a contract with some value.
+	fn moves_contract_value(c: &Call) -> bool {
+		match c {
+			Call::Contracts(pallet_contracts::Call::call(_, value, ..)) |
+			Call::Contracts(pallet_contracts::Call::instantiate_with_code(value, ..)) |
+			Call::Contracts(pallet_contracts::Call::instantiate(value, ..)) => *value != 0,
+			_ => false,
+		}
+	}
+}
+impl InstanceFilter<Call> for ProxyType {
+	fn filter(&self, c: &Call) -> bool {
+		self.allows_nested(c)
+	}
 	fn is_superset(&self, o: &Self) -> bool {
 		match (self, o) {
 			(x, y) if x == y => true,
 			(ProxyType::Any, _) => true,
 			(_, ProxyType::Any) => false,
-			(ProxyType::NonTransfer, _) => true,
+			// `NonTransfer` leaves out `as_recovered`, `Society::bid` and endowing contracts.
+			(ProxyType::NonTransfer, ProxyType::Governance) |
+			(ProxyType::NonTransfer, ProxyType::Staking) |
+			(ProxyType::NonTransfer, ProxyType::IdentityJudgement) |
+			(ProxyType::NonTransfer, ProxyType::CancelProxy) |
+			(ProxyType::NonTransfer, ProxyType::SessionKeys) |
+			(ProxyType::NonTransfer, ProxyType::Treasury) => true,
+			(ProxyType::Governance, ProxyType::Treasury) => true,
+			(ProxyType::Staking, ProxyType::SessionKeys) => true,
 			_ => false,
 		}
 	}
@@ -323,7 +469,7 @@ impl pallet_scheduler::Config for Runtime {
 	type PalletsOrigin = OriginCaller;
 	type Call = Call;
 	type MaximumWeight = MaximumSchedulerWeight;
-	type ScheduleOrigin = EnsureRoot<AccountId>;
+	type ScheduleOrigin = EnsureRootOrHalfCouncil;
 	type MaxScheduledPerBlock = MaxScheduledPerBlock;
 	type WeightInfo = pallet_scheduler::weights::SubstrateWeight<Runtime>;
 }
@@ -373,6 +519,8 @@ impl pallet_indices::Config for Runtime {
 }
 
 parameter_types! {
+	// The existential deposit is not a runtime parameter: accounts, reserves and the deposits of
+	// other pallets are sized by it, and none of them would follow a change.
 	pub const ExistentialDeposit: Balance = 1 * DOLLARS;
 	// For weight estimation, we assume that the most locks on an individual account will be 50.
 	// This number may need to be adjusted in the future if this assumption no longer holds true.
@@ -385,7 +533,7 @@ impl pallet_balances::Config for Runtime {
 	type MaxReserves = MaxReserves;
 	type ReserveIdentifier = [u8; 8];
 	type Balance = Balance;
-	type DustRemoval = ();
+	type DustRemoval = DealWithDust;
 	type Event = Event;
 	type ExistentialDeposit = ExistentialDeposit;
 	type AccountStore = frame_system::Pallet<Runtime>;
@@ -393,7 +541,6 @@ impl pallet_balances::Config for Runtime {
 }
 
 parameter_types! {
-	pub const TransactionByteFee: Balance = 10 * MILLICENTS;
 	pub const TargetBlockFullness: Perquintill = Perquintill::from_percent(25);
 	pub AdjustmentVariable: Multiplier = Multiplier::saturating_from_rational(1, 100_000);
 	pub MinimumMultiplier: Multiplier = Multiplier::saturating_from_rational(1, 1_000_000_000u128);
@@ -407,6 +554,14 @@ impl pallet_transaction_payment::Config for Runtime {
 		TargetedFeeAdjustment<Self, TargetBlockFullness, AdjustmentVariable, MinimumMultiplier>;
 }
 
+impl pallet_asset_tx_payment::Config for Runtime {
+	type Event = Event;
+	type Fungibles = Assets;
+	type RateOrigin = EnsureRootOrHalfCouncil;
+	type OnAssetFees = DealWithFees;
+	type WeightInfo = ();
+}
+
 parameter_types! {
 	pub const MinimumPeriod: Moment = SLOT_DURATION / 2;
 }
@@ -514,9 +669,11 @@ parameter_types! {
 	pub const SignedPhase: u32 = EPOCH_DURATION_IN_BLOCKS / 4;
 	pub const UnsignedPhase: u32 = EPOCH_DURATION_IN_BLOCKS / 4;
 
-	// fallback: no need to do on-chain phragmen initially.
-	pub const Fallback: pallet_election_provider_multi_phase::FallbackStrategy =
-		pallet_election_provider_multi_phase::FallbackStrategy::Nothing;
+	// signed config
+	pub const SignedMaxSubmissions: u32 = 10;
+	pub const SignedRewardBase: Balance = 1 * DOLLARS;
+	pub const SignedDepositBase: Balance = 1 * DOLLARS;
+	pub const SignedDepositByte: Balance = 1 * CENTS;
 
 	pub SolutionImprovementThreshold: Perbill = Perbill::from_rational(1u32, 10_000);
 
@@ -546,6 +703,48 @@ sp_npos_elections::generate_solution_type!(
 pub const MAX_NOMINATIONS: u32 =
 	<NposCompactSolution16 as sp_npos_elections::CompactSolution>::LIMIT as u32;
 
+/// The weight of an on-chain sequential phragmen election of `desired` out of `targets` by
+/// `voters`, including reading the voters and targets from staking.
+///
+/// The coefficients of the election itself are those of the upstream `phragmen(v, t, d)` benchmark
+/// of `frame-election-provider-support`.
+pub fn onchain_election_weight(voters: u32, targets: u32, desired: u32) -> Weight {
+	(32_973_000 as Weight).saturating_mul(voters as Weight)
+		.saturating_add((1_334_000 as Weight).saturating_mul(targets as Weight))
+		.saturating_add((2_636_364_000 as Weight).saturating_mul(desired as Weight))
+		// ledger, bonded and nominations of each voter, validator prefs and ledger of each target.
+		.saturating_add(RocksDbWeight::get().reads(3u64.saturating_mul(voters as u64)))
+		.saturating_add(RocksDbWeight::get().reads(2u64.saturating_mul(targets as u64)))
+}
+
+/// Falls back to an on-chain sequential phragmen election, as long as it fits in what is left of
+/// the block. The strategy is read by `elect`, so the weight used so far is that of the block in
+/// which the election happens.
+///
+/// A larger election, or one without a snapshot, is not attempted and the current validators stay.
+pub struct OnChainFallback;
+impl frame_support::traits::Get<pallet_election_provider_multi_phase::FallbackStrategy>
+	for OnChainFallback
+{
+	fn get() -> pallet_election_provider_multi_phase::FallbackStrategy {
+		use pallet_election_provider_multi_phase::FallbackStrategy;
+
+		let size = match ElectionProviderMultiPhase::snapshot_metadata() {
+			Some(size) => size,
+			None => return FallbackStrategy::Nothing,
+		};
+		let desired_targets = ElectionProviderMultiPhase::desired_targets().unwrap_or_default();
+		let weight = onchain_election_weight(size.voters, size.targets, desired_targets);
+		let remaining = RuntimeBlockWeights::get().max_block
+			.saturating_sub(System::block_weight().total());
+		if weight <= remaining {
+			FallbackStrategy::OnChain
+		} else {
+			FallbackStrategy::Nothing
+		}
+	}
+}
+
 impl pallet_election_provider_multi_phase::Config for Runtime {
 	type Event = Event;
 	type Currency = Balances;
@@ -557,23 +756,27 @@ impl pallet_election_provider_multi_phase::Config for Runtime {
 	type MinerMaxWeight = MinerMaxWeight;
 	type MinerMaxLength = MinerMaxLength;
 	type MinerTxPriority = MultiPhaseUnsignedPriority;
+	type SignedMaxSubmissions = SignedMaxSubmissions;
+	type SignedRewardBase = SignedRewardBase;
+	type SignedDepositBase = SignedDepositBase;
+	type SignedDepositByte = SignedDepositByte;
+	type SignedDepositWeight = ();
+	type SignedMaxWeight = MinerMaxWeight;
+	type SlashHandler = Treasury; // invalid solutions forfeit their deposit to the treasury.
+	type RewardHandler = (); // rewards are minted from the void
 	type DataProvider = Staking;
 	type OnChainAccuracy = Perbill;
 	type CompactSolution = NposCompactSolution16;
-	type Fallback = Fallback;
+	type Fallback = OnChainFallback;
 	type WeightInfo = pallet_election_provider_multi_phase::weights::SubstrateWeight<Runtime>;
 	type ForceOrigin = EnsureRootOrHalfCouncil;
 	type BenchmarkingConfig = ();
 }
 
 parameter_types! {
-	pub const LaunchPeriod: BlockNumber = 28 * 24 * 60 * MINUTES;
-	pub const VotingPeriod: BlockNumber = 28 * 24 * 60 * MINUTES;
 	pub const FastTrackVotingPeriod: BlockNumber = 3 * 24 * 60 * MINUTES;
 	pub const InstantAllowed: bool = true;
 	pub const MinimumDeposit: Balance = 100 * DOLLARS;
-	pub const EnactmentPeriod: BlockNumber = 30 * 24 * 60 * MINUTES;
-	pub const CooloffPeriod: BlockNumber = 28 * 24 * 60 * MINUTES;
 	// One cent: $10,000 / MB
 	pub const PreimageByteDeposit: Balance = 1 * CENTS;
 	pub const MaxVotes: u32 = 100;
@@ -610,7 +813,12 @@ impl pallet_democracy::Config for Runtime {
 		EnsureRoot<AccountId>,
 		pallet_collective::EnsureProportionAtLeast<_1, _1, AccountId, TechnicalCollective>,
 	>;
-	type BlacklistOrigin = EnsureRoot<AccountId>;
+	// To blacklist a proposal for good, 2/3 of the council must agree to it.
+	type BlacklistOrigin = EnsureOneOf<
+		AccountId,
+		EnsureRoot<AccountId>,
+		pallet_collective::EnsureProportionAtLeast<_2, _3, AccountId, CouncilCollective>,
+	>;
 	// Any single technical committee member may veto a coming council proposal, however they can
 	// only do it once and it lasts only for the cool-off period.
 	type VetoOrigin = pallet_collective::EnsureMember<AccountId, TechnicalCollective>;
@@ -644,11 +852,10 @@ impl pallet_collective::Config<CouncilCollective> for Runtime {
 }
 
 parameter_types! {
-	pub const CandidacyBond: Balance = 10 * DOLLARS;
 	// 1 storage item created, key size is 32 bytes, value size is 16+16.
-	pub const VotingBondBase: Balance = deposit(1, 64);
+	pub VotingBondBase: Balance = governed_deposit(1, 64);
 	// additional data per vote is 32 bytes (account id).
-	pub const VotingBondFactor: Balance = deposit(0, 32);
+	pub VotingBondFactor: Balance = governed_deposit(0, 32);
 	pub const TermDuration: BlockNumber = 7 * DAYS;
 	pub const DesiredMembers: u32 = 13;
 	pub const DesiredRunnersUp: u32 = 7;
@@ -701,6 +908,12 @@ type EnsureRootOrHalfCouncil = EnsureOneOf<
 	EnsureRoot<AccountId>,
 	pallet_collective::EnsureProportionMoreThan<_1, _2, AccountId, CouncilCollective>
 >;
+type EnsureRootOrHalfTechnicalCommittee = EnsureOneOf<
+	AccountId,
+	EnsureRoot<AccountId>,
+	pallet_collective::EnsureProportionMoreThan<_1, _2, AccountId, TechnicalCollective>
+>;
+
 impl pallet_membership::Config<pallet_membership::Instance1> for Runtime {
 	type Event = Event;
 	type AddOrigin = EnsureRootOrHalfCouncil;
@@ -715,7 +928,6 @@ impl pallet_membership::Config<pallet_membership::Instance1> for Runtime {
 }
 
 parameter_types! {
-	pub const ProposalBond: Permill = Permill::from_percent(5);
 	pub const ProposalBondMinimum: Balance = 1 * DOLLARS;
 	pub const SpendPeriod: BlockNumber = 1 * DAYS;
 	pub const Burn: Permill = Permill::from_percent(50);
@@ -752,7 +964,7 @@ impl pallet_treasury::Config for Runtime {
 	type ProposalBondMinimum = ProposalBondMinimum;
 	type SpendPeriod = SpendPeriod;
 	type Burn = Burn;
-	type BurnDestination = ();
+	type BurnDestination = DealWithBurn;
 	type SpendFunds = Bounties;
 	type WeightInfo = pallet_treasury::weights::SubstrateWeight<Runtime>;
 	type MaxApprovals = MaxApprovals;
@@ -770,16 +982,16 @@ impl pallet_bounties::Config for Runtime {
 	type WeightInfo = pallet_bounties::weights::SubstrateWeight<Runtime>;
 }
 
-// impl pallet_tips::Config for Runtime {
-// 	type Event = Event;
-// 	type DataDepositPerByte = DataDepositPerByte;
-// 	type MaximumReasonLength = MaximumReasonLength;
-// 	type Tippers = Elections;
-// 	type TipCountdown = TipCountdown;
-// 	type TipFindersFee = TipFindersFee;
-// 	type TipReportDepositBase = TipReportDepositBase;
-// 	type WeightInfo = pallet_tips::weights::SubstrateWeight<Runtime>;
-// }
+impl pallet_tips::Config for Runtime {
+	type Event = Event;
+	type DataDepositPerByte = DataDepositPerByte;
+	type MaximumReasonLength = MaximumReasonLength;
+	type Tippers = Elections;
+	type TipCountdown = TipCountdown;
+	type TipFindersFee = TipFindersFee;
+	type TipReportDepositBase = TipReportDepositBase;
+	type WeightInfo = pallet_tips::weights::SubstrateWeight<Runtime>;
+}
 
 parameter_types! {
 	pub TombstoneDeposit: Balance = deposit(
@@ -807,7 +1019,7 @@ parameter_types! {
 
 impl pallet_contracts::Config for Runtime {
 	type Time = Timestamp;
-	type Randomness = RandomnessCollectiveFlip;
+	type Randomness = pallet_babe::RandomnessFromOneEpochAgo<Runtime>;
 	type Currency = Balances;
 	type Event = Event;
 	type RentPayment = ();
@@ -821,7 +1033,7 @@ impl pallet_contracts::Config for Runtime {
 	type CallStack = [pallet_contracts::Frame<Self>; 31];
 	type WeightPrice = pallet_transaction_payment::Module<Self>;
 	type WeightInfo = pallet_contracts::weights::SubstrateWeight<Self>;
-	type ChainExtension = ();
+	type ChainExtension = chain_extension::TrustBaseExtension;
 	type DeletionQueueDepth = DeletionQueueDepth;
 	type DeletionWeightLimit = DeletionWeightLimit;
 	type Schedule = Schedule;
@@ -832,6 +1044,15 @@ impl pallet_sudo::Config for Runtime {
 	type Call = Call;
 }
 
+impl pallet_migrations::Config for Runtime {}
+
+impl pallet_sudo_retirement::Config for Runtime {
+	type Event = Event;
+	// only a referendum can retire the sudo key.
+	type RetireOrigin = EnsureRoot<AccountId>;
+	type WeightInfo = ();
+}
+
 parameter_types! {
 	pub const ImOnlineUnsignedPriority: TransactionPriority = TransactionPriority::max_value();
 	/// We prioritize im-online heartbeats over election solution submission.
@@ -867,7 +1088,7 @@ impl<LocalCall> frame_system::offchain::CreateSignedTransaction<LocalCall> for R
 			frame_system::CheckEra::<Runtime>::from(era),
 			frame_system::CheckNonce::<Runtime>::from(nonce),
 			frame_system::CheckWeight::<Runtime>::new(),
-			pallet_transaction_payment::ChargeTransactionPayment::<Runtime>::from(tip),
+			pallet_asset_tx_payment::ChargeAssetTxPayment::<Runtime>::from(tip, None),
 		);
 		let raw_payload = SignedPayload::new(call, extra)
 			.map_err(|e| {
@@ -950,7 +1171,7 @@ impl pallet_society::Config for Runtime {
 	type Event = Event;
 	type PalletId = SocietyPalletId;
 	type Currency = Balances;
-	type Randomness = RandomnessCollectiveFlip;
+	type Randomness = pallet_babe::RandomnessFromOneEpochAgo<Runtime>;
 	type CandidateDeposit = CandidateDeposit;
 	type WrongSideDeduction = WrongSideDeduction;
 	type MaxStrikes = MaxStrikes;
@@ -964,6 +1185,304 @@ impl pallet_society::Config for Runtime {
 	type ChallengePeriod = ChallengePeriod;
 }
 
+parameter_types! {
+	pub const BasicDeposit: Balance = 10 * DOLLARS;       // 258 bytes on-chain
+	pub const FieldDeposit: Balance = 250 * CENTS;        // 66 bytes on-chain
+	pub const SubAccountDeposit: Balance = 2 * DOLLARS;   // 53 bytes on-chain
+	pub const MaxSubAccounts: u32 = 100;
+	pub const MaxAdditionalFields: u32 = 100;
+	pub const MaxRegistrars: u32 = 20;
+}
+
+impl pallet_identity::Config for Runtime {
+	type Event = Event;
+	type Currency = Balances;
+	type BasicDeposit = BasicDeposit;
+	type FieldDeposit = FieldDeposit;
+	type SubAccountDeposit = SubAccountDeposit;
+	type MaxSubAccounts = MaxSubAccounts;
+	type MaxAdditionalFields = MaxAdditionalFields;
+	type MaxRegistrars = MaxRegistrars;
+	type Slashed = Treasury;
+	/// Registrars are added, and identities forcibly cleared, by the council.
+	type ForceOrigin = EnsureRootOrHalfCouncil;
+	type RegistrarOrigin = EnsureRootOrHalfCouncil;
+	type WeightInfo = pallet_identity::weights::SubstrateWeight<Runtime>;
+}
+
+parameter_types! {
+	// One storage item; key size 40, value size 4+16+2 bytes excluding the friends.
+	pub ConfigDepositBase: Balance = governed_deposit(1, 62);
+	// Additional storage item size of 32 bytes.
+	pub FriendDepositFactor: Balance = governed_deposit(0, 32);
+	pub const MaxFriends: u16 = 9;
+	// One storage item; key size 80, value size 4+16 bytes excluding the vouching friends.
+	pub RecoveryDeposit: Balance = governed_deposit(1, 100);
+}
+
+impl pallet_recovery::Config for Runtime {
+	type Event = Event;
+	type Call = Call;
+	type Currency = Balances;
+	type ConfigDepositBase = ConfigDepositBase;
+	type FriendDepositFactor = FriendDepositFactor;
+	type MaxFriends = MaxFriends;
+	type RecoveryDeposit = RecoveryDeposit;
+}
+
+parameter_types! {
+	pub const AssetDeposit: Balance = 100 * DOLLARS;
+	pub const ApprovalDeposit: Balance = 1 * DOLLARS;
+	pub const StringLimit: u32 = 50;
+	pub const MetadataDepositBase: Balance = 10 * DOLLARS;
+	pub const MetadataDepositPerByte: Balance = 1 * DOLLARS;
+}
+
+impl pallet_assets::Config for Runtime {
+	type Event = Event;
+	type Balance = Balance;
+	type AssetId = AssetId;
+	type Currency = Balances;
+	type ForceOrigin = EnsureRootOrHalfCouncil;
+	type AssetDeposit = AssetDeposit;
+	type MetadataDepositBase = MetadataDepositBase;
+	type MetadataDepositPerByte = MetadataDepositPerByte;
+	type ApprovalDeposit = ApprovalDeposit;
+	type StringLimit = StringLimit;
+	type Freezer = ();
+	type Extra = ();
+	type WeightInfo = pallet_assets::weights::SubstrateWeight<Runtime>;
+}
+
+parameter_types! {
+	// One storage item; key size is 4; value is 4 * 32 + 16 + 1 + 3 * 4 + 1 = 158 bytes.
+	pub ClassDeposit: Balance = governed_deposit(1, 162);
+	// One storage item; key size is 4 + 4; value is 32 + 33 + 1 + 16 = 82 bytes.
+	pub InstanceDeposit: Balance = governed_deposit(1, 90);
+	// One storage item; key size is 4 + 5; value is 16 + 1 bytes, plus the metadata itself.
+	pub UniquesMetadataDepositBase: Balance = governed_deposit(1, 26);
+	// One storage item; key size is 4 + 5 + 4, plus the attribute key and value themselves.
+	pub AttributeDepositBase: Balance = governed_deposit(1, 13);
+	pub UniquesDepositPerByte: Balance = governed_deposit(0, 1);
+	pub const UniquesStringLimit: u32 = 128;
+	pub const KeyLimit: u32 = 32;
+	pub const ValueLimit: u32 = 256;
+}
+
+impl pallet_uniques::Config for Runtime {
+	type Event = Event;
+	type ClassId = u32;
+	type InstanceId = u32;
+	type Currency = Balances;
+	type ForceOrigin = EnsureRootOrHalfCouncil;
+	type ClassDeposit = ClassDeposit;
+	type InstanceDeposit = InstanceDeposit;
+	type MetadataDepositBase = UniquesMetadataDepositBase;
+	type AttributeDepositBase = AttributeDepositBase;
+	type DepositPerByte = UniquesDepositPerByte;
+	type StringLimit = UniquesStringLimit;
+	type KeyLimit = KeyLimit;
+	type ValueLimit = ValueLimit;
+	type WeightInfo = pallet_uniques::weights::SubstrateWeight<Runtime>;
+}
+
+parameter_types! {
+	pub const MinVestedTransfer: Balance = 100 * DOLLARS;
+}
+
+impl pallet_vesting::Config for Runtime {
+	type Event = Event;
+	type Currency = Balances;
+	type BlockNumberToBalance = ConvertInto;
+	type MinVestedTransfer = MinVestedTransfer;
+	type WeightInfo = pallet_vesting::weights::SubstrateWeight<Runtime>;
+}
+
+parameter_types! {
+	// Two storage items; key size 100 each, value size 1+5+16 bytes excluding the data.
+	pub AttestationDepositBase: Balance = governed_deposit(2, 222);
+	pub AttestationDepositPerByte: Balance = governed_deposit(0, 1);
+	pub const MaxAttestationDataLength: u32 = 256;
+}
+
+impl pallet_attestation::Config for Runtime {
+	type Event = Event;
+	type Currency = Balances;
+	type DepositBase = AttestationDepositBase;
+	type DepositPerByte = AttestationDepositPerByte;
+	type MaxDataLength = MaxAttestationDataLength;
+	type WeightInfo = pallet_attestation::weights::SubstrateWeight<Runtime>;
+}
+
+parameter_types! {
+	pub const PauseDuration: BlockNumber = 2 * DAYS;
+	// Inherents, governance, upgrades and the safe mode itself must keep working while calls are
+	// paused.
+	pub UnpausablePallets: Vec<Vec<u8>> = vec![
+		b"System".to_vec(),
+		b"Babe".to_vec(),
+		b"Timestamp".to_vec(),
+		b"Authorship".to_vec(),
+		b"Grandpa".to_vec(),
+		b"ImOnline".to_vec(),
+		b"TransactionStorage".to_vec(),
+		b"Democracy".to_vec(),
+		b"Council".to_vec(),
+		b"TechnicalCommittee".to_vec(),
+		b"Elections".to_vec(),
+		b"TechnicalMembership".to_vec(),
+		b"Sudo".to_vec(),
+		b"SudoRetirement".to_vec(),
+		b"SafeMode".to_vec(),
+		b"AuthorizedUpgrade".to_vec(),
+	];
+	pub const MaxPalletNameLen: u32 = 64;
+}
+
+impl pallet_safe_mode::Config for Runtime {
+	type Event = Event;
+	type PauseOrigin = EnsureRootOrHalfTechnicalCommittee;
+	type UnpauseOrigin = EnsureRootOrHalfTechnicalCommittee;
+	type PauseDuration = PauseDuration;
+	type UnpausablePallets = UnpausablePallets;
+	type MaxNameLen = MaxPalletNameLen;
+	type WeightInfo = pallet_safe_mode::weights::SubstrateWeight<Runtime>;
+}
+
+parameter_types! {
+	pub const MaxAllowanceSpends: u32 = 16;
+	// One storage item; key size 4, value size 2 * 32 + 2 * 16 + 4 + 1 + 16 * (4 + 16).
+	pub AllowanceDeposit: Balance = governed_deposit(1, 425);
+}
+
+pub struct SpendingLimitProxy;
+impl sp_runtime::traits::Convert<pallet_spending_limit::AllowanceId, ProxyType> for SpendingLimitProxy {
+	fn convert(id: pallet_spending_limit::AllowanceId) -> ProxyType {
+		ProxyType::SpendingLimit(id)
+	}
+}
+
+impl pallet_spending_limit::Config for Runtime {
+	type Event = Event;
+	type SpendingProxy = SpendingLimitProxy;
+	type AllowanceDeposit = AllowanceDeposit;
+	type MaxSpends = MaxAllowanceSpends;
+	type WeightInfo = pallet_spending_limit::weights::SubstrateWeight<Runtime>;
+}
+
+parameter_types! {
+	pub DefaultFeeSplits: Splits = {
+		let to_treasury_and_author = Split {
+			treasury: Permill::from_percent(80),
+			author: Permill::from_percent(20),
+			..Default::default()
+		};
+		let burnt = Split { burn: Permill::from_percent(100), ..Default::default() };
+		Splits {
+			fee: to_treasury_and_author,
+			tip: to_treasury_and_author,
+			burn: burnt,
+			dust: burnt,
+		}
+	};
+}
+
+impl pallet_fee_split::Config for Runtime {
+	type Event = Event;
+	type Currency = Balances;
+	type SplitOrigin = EnsureRootOrHalfCouncil;
+	type Treasury = Treasury;
+	type Author = Author;
+	type DefaultSplits = DefaultFeeSplits;
+	type WeightInfo = pallet_fee_split::weights::SubstrateWeight<Runtime>;
+}
+
+pallet_parameters::define_parameters! {
+	pub enum ParameterKey, ParameterValue, RuntimeParameters for Runtime {
+		/// The fee per byte of a transaction.
+		TransactionByteFee: Balance = 10 * MILLICENTS,
+			1 * MILLICENTS..=1 * CENTS, EnsureRootOrHalfCouncil;
+		/// The price of a storage item in `governed_deposit`.
+		DepositPerItem: Balance = 15 * CENTS,
+			1 * CENTS..=10 * DOLLARS, EnsureRootOrHalfCouncil;
+		/// The price of a byte of storage in `governed_deposit`.
+		DepositPerByte: Balance = 6 * CENTS,
+			1 * MILLICENTS..=1 * DOLLARS, EnsureRootOrHalfCouncil;
+		/// The bond of a candidate for the council.
+		CandidacyBond: Balance = 10 * DOLLARS,
+			1 * DOLLARS..=1_000 * DOLLARS, EnsureRootOrHalfCouncil;
+		/// The share of a treasury proposal that its proposer bonds.
+		ProposalBond: Permill = Permill::from_percent(5),
+			Permill::from_percent(1)..=Permill::from_percent(50), EnsureRootOrHalfCouncil;
+		/// How often new public referenda are launched. Only a referendum can change the
+		/// democracy periods.
+		LaunchPeriod: BlockNumber = 28 * 24 * 60 * MINUTES,
+			1 * DAYS..=90 * DAYS, EnsureRoot<AccountId>;
+		/// How long a referendum is open for voting.
+		VotingPeriod: BlockNumber = 28 * 24 * 60 * MINUTES,
+			1 * DAYS..=90 * DAYS, EnsureRoot<AccountId>;
+		/// The delay between the approval of a referendum and its enactment.
+		EnactmentPeriod: BlockNumber = 30 * 24 * 60 * MINUTES,
+			1 * DAYS..=90 * DAYS, EnsureRoot<AccountId>;
+		/// How long a vetoed proposal can not be re-proposed.
+		CooloffPeriod: BlockNumber = 28 * 24 * 60 * MINUTES,
+			1 * DAYS..=90 * DAYS, EnsureRoot<AccountId>;
+	}
+}
+
+impl pallet_parameters::Config for Runtime {
+	type Event = Event;
+	type Key = ParameterKey;
+	type Value = ParameterValue;
+	type Schema = RuntimeParameters;
+	type WeightInfo = pallet_parameters::weights::SubstrateWeight<Runtime>;
+}
+
+impl pallet_authorized_upgrade::Config for Runtime {
+	type Event = Event;
+	// upgrades are authorized by referendum, whose proposal now only carries the code hash.
+	type AuthorizeOrigin = EnsureRoot<AccountId>;
+	type WeightInfo = pallet_authorized_upgrade::weights::SubstrateWeight<Runtime>;
+}
+
+parameter_types! {
+	pub const NominationPoolsPalletId: PalletId = PalletId(*b"py/nopls");
+	// One storage item; key size 4, value size 32 + 4 * 16 + 4 + 1 + 32 * 4. Up to 32 unbonding
+	// pools, as staking keeps at most 32 unlocking chunks; key size 8, value size 2 * 16.
+	pub const PoolDeposit: Balance = deposit(1 + 32, 229 + 32 * (8 + 32));
+	// One storage item; key size 32, value size 4 + 3 * 16 + 1 + MaxPoolUnbonding * (4 + 16).
+	pub const PoolMemberDeposit: Balance = deposit(1, 245);
+	pub const MinCreatePoolBond: Balance = 100 * DOLLARS;
+	pub const MinJoinPoolBond: Balance = 1 * DOLLARS;
+	// Filling the 32 unlocking chunks of a pool takes unbonds of this much in 32 eras, by at
+	// least 32 / MaxPoolUnbonding members.
+	pub const MinPoolUnbond: Balance = 10 * DOLLARS;
+	pub const MaxPoolUnbonding: u32 = 8;
+}
+
+impl pallet_nomination_pools::Config for Runtime {
+	type Event = Event;
+	type Currency = Balances;
+	type Staking = Staking;
+	type PalletId = NominationPoolsPalletId;
+	type PoolDeposit = PoolDeposit;
+	type MemberDeposit = PoolMemberDeposit;
+	type MinCreateBond = MinCreatePoolBond;
+	type MinJoinBond = MinJoinPoolBond;
+	type MinUnbond = MinPoolUnbond;
+	type MaxUnbonding = MaxPoolUnbonding;
+	type WeightInfo = pallet_nomination_pools::weights::SubstrateWeight<Runtime>;
+}
+
+impl pallet_mmr::Config for Runtime {
+	const INDEXING_PREFIX: &'static [u8] = b"mmr";
+	type Hashing = <Runtime as frame_system::Config>::Hashing;
+	type Hash = <Runtime as frame_system::Config>::Hash;
+	type LeafData = frame_system::Pallet<Self>;
+	type OnNewRoot = ();
+	type WeightInfo = ();
+}
+
 impl pallet_transaction_storage::Config for Runtime {
 	type Event = Event;
 	type Currency = Balances;
@@ -978,37 +1497,54 @@ construct_runtime!(
 		NodeBlock = node_primitives::Block,
 		UncheckedExtrinsic = UncheckedExtrinsic
 	{
-		System: frame_system::{Pallet, Call, Config, Storage, Event<T>},
-		Utility: pallet_utility::{Pallet, Call, Event},
-		Babe: pallet_babe::{Pallet, Call, Storage, Config, ValidateUnsigned},
-		Timestamp: pallet_timestamp::{Pallet, Call, Storage, Inherent},
-		Authorship: pallet_authorship::{Pallet, Call, Storage, Inherent},
-		Indices: pallet_indices::{Pallet, Call, Storage, Config<T>, Event<T>},
-		Balances: pallet_balances::{Pallet, Call, Storage, Config<T>, Event<T>},
-		TransactionPayment: pallet_transaction_payment::{Pallet, Storage},
-		ElectionProviderMultiPhase: pallet_election_provider_multi_phase::{Pallet, Call, Storage, Event<T>, ValidateUnsigned},
-		Staking: pallet_staking::{Pallet, Call, Config<T>, Storage, Event<T>},
-		Session: pallet_session::{Pallet, Call, Storage, Event, Config<T>},
-		Democracy: pallet_democracy::{Pallet, Call, Storage, Config<T>, Event<T>},
-		Council: pallet_collective::<Instance1>::{Pallet, Call, Storage, Origin<T>, Event<T>, Config<T>},
-		TechnicalCommittee: pallet_collective::<Instance2>::{Pallet, Call, Storage, Origin<T>, Event<T>, Config<T>},
-		Elections: pallet_elections_phragmen::{Pallet, Call, Storage, Event<T>, Config<T>},
-		TechnicalMembership: pallet_membership::<Instance1>::{Pallet, Call, Storage, Event<T>, Config<T>},
-		Grandpa: pallet_grandpa::{Pallet, Call, Storage, Config, Event, ValidateUnsigned},
-		Treasury: pallet_treasury::{Pallet, Call, Storage, Config, Event<T>},
-		Contracts: pallet_contracts::{Pallet, Call, Storage, Event<T>},
-		Sudo: pallet_sudo::{Pallet, Call, Config<T>, Storage, Event<T>},
-		ImOnline: pallet_im_online::{Pallet, Call, Storage, Event<T>, ValidateUnsigned, Config<T>},
-		AuthorityDiscovery: pallet_authority_discovery::{Pallet, Config},
-		Offences: pallet_offences::{Pallet, Storage, Event},
-		Historical: pallet_session_historical::{Pallet},
-		RandomnessCollectiveFlip: pallet_randomness_collective_flip::{Pallet, Storage},
-		Society: pallet_society::{Pallet, Call, Storage, Event<T>, Config<T>},
-		Scheduler: pallet_scheduler::{Pallet, Call, Storage, Event<T>},
-		Proxy: pallet_proxy::{Pallet, Call, Storage, Event<T>},
-		Multisig: pallet_multisig::{Pallet, Call, Storage, Event<T>},
-		Bounties: pallet_bounties::{Pallet, Call, Storage, Event<T>},
-		TransactionStorage: pallet_transaction_storage::{Pallet, Call, Storage, Inherent, Config<T>, Event<T>},
+		System: frame_system::{Pallet, Call, Config, Storage, Event<T>} = 0,
+		Utility: pallet_utility::{Pallet, Call, Event} = 1,
+		Babe: pallet_babe::{Pallet, Call, Storage, Config, ValidateUnsigned} = 2,
+		Timestamp: pallet_timestamp::{Pallet, Call, Storage, Inherent} = 3,
+		Authorship: pallet_authorship::{Pallet, Call, Storage, Inherent} = 4,
+		Indices: pallet_indices::{Pallet, Call, Storage, Config<T>, Event<T>} = 5,
+		Balances: pallet_balances::{Pallet, Call, Storage, Config<T>, Event<T>} = 6,
+		TransactionPayment: pallet_transaction_payment::{Pallet, Storage} = 7,
+		ElectionProviderMultiPhase: pallet_election_provider_multi_phase::{Pallet, Call, Storage, Event<T>, ValidateUnsigned} = 8,
+		Staking: pallet_staking::{Pallet, Call, Config<T>, Storage, Event<T>} = 9,
+		Session: pallet_session::{Pallet, Call, Storage, Event, Config<T>} = 10,
+		Democracy: pallet_democracy::{Pallet, Call, Storage, Config<T>, Event<T>} = 11,
+		Council: pallet_collective::<Instance1>::{Pallet, Call, Storage, Origin<T>, Event<T>, Config<T>} = 12,
+		TechnicalCommittee: pallet_collective::<Instance2>::{Pallet, Call, Storage, Origin<T>, Event<T>, Config<T>} = 13,
+		Elections: pallet_elections_phragmen::{Pallet, Call, Storage, Event<T>, Config<T>} = 14,
+		TechnicalMembership: pallet_membership::<Instance1>::{Pallet, Call, Storage, Event<T>, Config<T>} = 15,
+		Grandpa: pallet_grandpa::{Pallet, Call, Storage, Config, Event, ValidateUnsigned} = 16,
+		Treasury: pallet_treasury::{Pallet, Call, Storage, Config, Event<T>} = 17,
+		Contracts: pallet_contracts::{Pallet, Call, Storage, Event<T>} = 18,
+		Sudo: pallet_sudo::{Pallet, Call, Config<T>, Storage, Event<T>} = 19,
+		ImOnline: pallet_im_online::{Pallet, Call, Storage, Event<T>, ValidateUnsigned, Config<T>} = 20,
+		AuthorityDiscovery: pallet_authority_discovery::{Pallet, Config} = 21,
+		Offences: pallet_offences::{Pallet, Storage, Event} = 22,
+		Historical: pallet_session_historical::{Pallet} = 23,
+		// Index 24 was `RandomnessCollectiveFlip`; keep it unused so stored calls keep decoding.
+		Society: pallet_society::{Pallet, Call, Storage, Event<T>, Config<T>} = 25,
+		Scheduler: pallet_scheduler::{Pallet, Call, Storage, Event<T>} = 26,
+		Proxy: pallet_proxy::{Pallet, Call, Storage, Event<T>} = 27,
+		Multisig: pallet_multisig::{Pallet, Call, Storage, Event<T>} = 28,
+		Bounties: pallet_bounties::{Pallet, Call, Storage, Event<T>} = 29,
+		TransactionStorage: pallet_transaction_storage::{Pallet, Call, Storage, Inherent, Config<T>, Event<T>} = 30,
+		Vesting: pallet_vesting::{Pallet, Call, Storage, Event<T>, Config<T>} = 31,
+		Identity: pallet_identity::{Pallet, Call, Storage, Event<T>} = 32,
+		Assets: pallet_assets::{Pallet, Call, Storage, Config<T>, Event<T>} = 33,
+		AssetTxPayment: pallet_asset_tx_payment::{Pallet, Call, Storage, Event<T>} = 34,
+		Uniques: pallet_uniques::{Pallet, Call, Storage, Event<T>} = 35,
+		Tips: pallet_tips::{Pallet, Call, Storage, Event<T>} = 36,
+		Mmr: pallet_mmr::{Pallet, Storage} = 37,
+		Recovery: pallet_recovery::{Pallet, Call, Storage, Event<T>} = 38,
+		Attestations: pallet_attestation::{Pallet, Call, Storage, Event<T>} = 39,
+		SafeMode: pallet_safe_mode::{Pallet, Call, Storage, Event<T>} = 40,
+		SudoRetirement: pallet_sudo_retirement::{Pallet, Call, Storage, Event<T>} = 41,
+		Migrations: pallet_migrations::{Pallet, Storage, Config} = 42,
+		SpendingLimits: pallet_spending_limit::{Pallet, Call, Storage, Event<T>} = 43,
+		FeeSplit: pallet_fee_split::{Pallet, Call, Storage, Event<T>} = 44,
+		Parameters: pallet_parameters::{Pallet, Call, Storage, Event<T>} = 45,
+		AuthorizedUpgrade: pallet_authorized_upgrade::{Pallet, Call, Storage, Event<T>} = 46,
+		NominationPools: pallet_nomination_pools::{Pallet, Call, Storage, Event<T>} = 47,
 	}
 );
 
@@ -1024,7 +1560,8 @@ pub type SignedBlock = generic::SignedBlock<Block>;
 pub type BlockId = generic::BlockId<Block>;
 /// The SignedExtension to the basic transaction logic.
 ///
-/// When you change this, you **MUST** modify [`sign`] in `bin/node/testing/src/keyring.rs`!
+/// When you change this, you **MUST** modify [`sign`] in `bin/node/testing/src/keyring.rs`,
+/// `create_transaction` in `staking-miner/src/signer.rs` and `sign` in `cli/src/payout.rs`!
 ///
 /// [`sign`]: <../../testing/src/keyring.rs.html>
 pub type SignedExtra = (
@@ -1034,7 +1571,7 @@ pub type SignedExtra = (
 	frame_system::CheckEra<Runtime>,
 	frame_system::CheckNonce<Runtime>,
 	frame_system::CheckWeight<Runtime>,
-	pallet_transaction_payment::ChargeTransactionPayment<Runtime>,
+	pallet_asset_tx_payment::ChargeAssetTxPayment<Runtime>,
 );
 /// Unchecked extrinsic type as expected by this runtime.
 pub type UncheckedExtrinsic = generic::UncheckedExtrinsic<Address, Call, Signature, SignedExtra>;
@@ -1049,9 +1586,22 @@ pub type Executive = frame_executive::Executive<
 	frame_system::ChainContext<Runtime>,
 	Runtime,
 	AllPallets,
-	(),
+	migrations::Migrations,
 >;
 
+/// MMR helper types.
+mod mmr {
+	use super::Runtime;
+	pub use pallet_mmr::primitives::*;
+
+	pub type Leaf = <
+		<Runtime as pallet_mmr::Config>::LeafData
+		as
+		LeafDataProvider
+	>::LeafData;
+	pub type Hash = <Runtime as pallet_mmr::Config>::Hash;
+}
+
 impl_runtime_apis! {
 	impl sp_api::Core<Block> for Runtime {
 		fn version() -> RuntimeVersion {
@@ -1256,6 +1806,118 @@ impl_runtime_apis! {
 		}
 	}
 
+	impl pallet_asset_tx_payment_rpc_runtime_api::AssetTxPaymentApi<
+		Block,
+		Balance,
+		AssetId,
+	> for Runtime {
+		fn query_fee_in_asset(
+			uxt: <Block as BlockT>::Extrinsic,
+			len: u32,
+			asset_id: AssetId,
+		) -> Option<Balance> {
+			let partial_fee = TransactionPayment::query_info(uxt, len).partial_fee;
+			AssetTxPayment::to_asset_balance(asset_id, partial_fee)
+		}
+	}
+
+	impl pallet_attestation_runtime_api::AttestationApi<
+		Block,
+		AccountId,
+		pallet_attestation::SchemaId,
+		pallet_attestation::AttestationOf<Runtime>,
+	> for Runtime {
+		fn inbound(
+			subject: AccountId,
+		) -> Vec<(AccountId, pallet_attestation::SchemaId, pallet_attestation::AttestationOf<Runtime>)> {
+			Attestations::inbound(subject)
+		}
+
+		fn outbound(
+			issuer: AccountId,
+		) -> Vec<(AccountId, pallet_attestation::SchemaId, pallet_attestation::AttestationOf<Runtime>)> {
+			Attestations::outbound(issuer)
+		}
+	}
+
+	impl pallet_spending_limit_rpc_runtime_api::SpendingLimitApi<
+		Block,
+		pallet_spending_limit::AllowanceId,
+		Balance,
+	> for Runtime {
+		fn remaining_allowance(id: pallet_spending_limit::AllowanceId) -> Option<Balance> {
+			SpendingLimits::remaining(id)
+		}
+	}
+
+	impl pallet_staking_rpc_runtime_api::StakingApi<Block, AccountId, Balance> for Runtime {
+		fn projected_era_payout() -> Option<pallet_staking_rpc_runtime_api::EraPayout<Balance>> {
+			staking_projection::projected_era_payout()
+		}
+
+		fn expected_validator_reward(
+			validator: AccountId,
+		) -> Option<pallet_staking_rpc_runtime_api::ValidatorReward<AccountId, Balance>> {
+			staking_projection::expected_validator_reward(validator)
+		}
+
+		fn unclaimed_eras(stash: AccountId) -> Vec<pallet_staking_rpc_runtime_api::EraIndex> {
+			staking_projection::unclaimed_eras(stash)
+		}
+
+		fn unbonding(who: AccountId) -> Option<pallet_staking_rpc_runtime_api::Unbonding<Balance>> {
+			staking_projection::unbonding(who)
+		}
+	}
+
+	impl pallet_governance_rpc_runtime_api::GovernanceApi<Block, AccountId, Balance, BlockNumber, Hash> for Runtime {
+		fn overview() -> pallet_governance_rpc_runtime_api::GovernanceOverview<AccountId, Balance, BlockNumber, Hash> {
+			governance::overview()
+		}
+	}
+
+	impl pallet_nomination_pools_runtime_api::NominationPoolsApi<Block, AccountId, Balance> for Runtime {
+		fn pending_rewards(member: AccountId) -> Balance {
+			NominationPools::pending_rewards(&member)
+		}
+	}
+
+	impl pallet_parameters_rpc_runtime_api::ParametersApi<Block> for Runtime {
+		fn parameters() -> Vec<pallet_parameters_rpc_runtime_api::ParameterInfo> {
+			use pallet_parameters::ParameterSchema;
+
+			Parameters::parameters().into_iter().map(|(key, value)|
+				pallet_parameters_rpc_runtime_api::ParameterInfo {
+					name: RuntimeParameters::name(key).as_bytes().to_vec(),
+					key: key.encode(),
+					value: value.encode(),
+				}
+			).collect()
+		}
+	}
+
+	impl pallet_mmr::primitives::MmrApi<
+		Block,
+		mmr::Hash,
+	> for Runtime {
+		fn generate_proof(leaf_index: u64)
+			-> Result<(mmr::EncodableOpaqueLeaf, mmr::Proof<mmr::Hash>), mmr::Error>
+		{
+			Mmr::generate_proof(leaf_index)
+				.map(|(leaf, proof)| (mmr::EncodableOpaqueLeaf::from_leaf(&leaf), proof))
+		}
+
+		fn verify_proof(leaf: mmr::EncodableOpaqueLeaf, proof: mmr::Proof<mmr::Hash>)
+			-> Result<(), mmr::Error>
+		{
+			let leaf: mmr::Leaf = leaf
+				.into_opaque_leaf()
+				.try_decode()
+				.ok_or(mmr::Error::Verify)?;
+			Mmr::verify_leaf(leaf, proof)
+		}
+	}
+
 	impl sp_session::SessionKeys<Block> for Runtime {
 		fn generate_session_keys(seed: Option<Vec<u8>>) -> Vec<u8> {
 			SessionKeys::generate(seed)
@@ -1288,6 +1950,7 @@ impl_runtime_apis! {
 			use pallet_session_benchmarking::Pallet as SessionBench;
 			use pallet_offences_benchmarking::Pallet as OffencesBench;
 			use frame_system_benchmarking::Pallet as SystemBench;
+			use chain_extension::benchmarking::Pallet as ChainExtensionBench;
 
 			impl pallet_session_benchmarking::Config for Runtime {}
 			impl pallet_offences_benchmarking::Config for Runtime {}
@@ -1312,6 +1975,8 @@ impl_runtime_apis! {
 			let params = (&config, &whitelist);
 
 			add_benchmark!(params, batches, pallet_assets, Assets);
+			add_benchmark!(params, batches, pallet_attestation, Attestation);
+			add_benchmark!(params, batches, pallet_authorized_upgrade, AuthorizedUpgrade);
 			add_benchmark!(params, batches, pallet_babe, Babe);
 			add_benchmark!(params, batches, pallet_balances, Balances);
 			add_benchmark!(params, batches, pallet_bounties, Bounties);
@@ -1320,24 +1985,28 @@ impl_runtime_apis! {
 			add_benchmark!(params, batches, pallet_democracy, Democracy);
 			add_benchmark!(params, batches, pallet_election_provider_multi_phase, ElectionProviderMultiPhase);
 			add_benchmark!(params, batches, pallet_elections_phragmen, Elections);
-			add_benchmark!(params, batches, pallet_gilt, Gilt);
+			add_benchmark!(params, batches, pallet_fee_split, FeeSplit);
 			add_benchmark!(params, batches, pallet_grandpa, Grandpa);
 			add_benchmark!(params, batches, pallet_identity, Identity);
 			add_benchmark!(params, batches, pallet_im_online, ImOnline);
 			add_benchmark!(params, batches, pallet_indices, Indices);
-			add_benchmark!(params, batches, pallet_lottery, Lottery);
 			add_benchmark!(params, batches, pallet_membership, TechnicalMembership);
 			add_benchmark!(params, batches, pallet_mmr, Mmr);
 			add_benchmark!(params, batches, pallet_multisig, Multisig);
+			add_benchmark!(params, batches, pallet_nomination_pools, NominationPools);
 			add_benchmark!(params, batches, pallet_offences, OffencesBench::<Runtime>);
+			add_benchmark!(params, batches, pallet_parameters, Parameters);
 			add_benchmark!(params, batches, pallet_proxy, Proxy);
+			add_benchmark!(params, batches, pallet_safe_mode, SafeMode);
 			add_benchmark!(params, batches, pallet_scheduler, Scheduler);
 			add_benchmark!(params, batches, pallet_session, SessionBench::<Runtime>);
+			add_benchmark!(params, batches, pallet_spending_limit, SpendingLimits);
 			add_benchmark!(params, batches, pallet_staking, Staking);
 			add_benchmark!(params, batches, frame_system, SystemBench::<Runtime>);
 			add_benchmark!(params, batches, pallet_timestamp, Timestamp);
 			add_benchmark!(params, batches, pallet_tips, Tips);
 			add_benchmark!(params, batches, pallet_transaction_storage, TransactionStorage);
+			add_benchmark!(params, batches, trustbase_chain_extension, ChainExtensionBench::<Runtime>);
 			add_benchmark!(params, batches, pallet_treasury, Treasury);
 			add_benchmark!(params, batches, pallet_uniques, Uniques);
 			add_benchmark!(params, batches, pallet_utility, Utility);
@@ -1362,4 +2031,480 @@ mod tests {
 
 		is_submit_signed_transaction::<Runtime>();
 	}
+
+	#[test]
+	fn pallets_keep_the_indices_of_stored_calls() {
+		use frame_support::traits::PalletInfo as _;
+
+		fn index<P: 'static>() -> Option<usize> {
+			<Runtime as frame_system::Config>::PalletInfo::index::<P>()
+		}
+
+		assert_eq!(index::<Historical>(), Some(23));
+		assert_eq!(index::<Society>(), Some(25));
+		assert_eq!(index::<Scheduler>(), Some(26));
+		assert_eq!(index::<Proxy>(), Some(27));
+		assert_eq!(index::<Multisig>(), Some(28));
+		assert_eq!(index::<Bounties>(), Some(29));
+		assert_eq!(index::<TransactionStorage>(), Some(30));
+		assert_eq!(index::<Vesting>(), Some(31));
+		assert_eq!(Call::Proxy(pallet_proxy::Call::remove_proxies()).encode()[0], 27);
+	}
+
+	#[test]
+	fn paused_calls_are_filtered_when_nested() {
+		use frame_support::assert_ok;
+
+		sp_io::TestExternalities::default().execute_with(|| {
+			System::set_block_number(1);
+			let alice = AccountId::from([1u8; 32]);
+			let bob = AccountId::from([2u8; 32]);
+			let multisig = Multisig::multi_account_id(&[alice.clone(), bob.clone()], 1);
+			Balances::make_free_balance_be(&alice, 1_000 * DOLLARS);
+			Balances::make_free_balance_be(&multisig, 1_000 * DOLLARS);
+			let transfer = Call::Balances(pallet_balances::Call::transfer(bob.clone().into(), DOLLARS));
+
+			assert_ok!(SafeMode::pause_call(Origin::root(), b"Balances".to_vec(), b"transfer".to_vec()));
+			assert!(!BaseFilter::filter(&transfer));
+
+			assert_ok!(Utility::batch(Origin::signed(alice.clone()), vec![transfer.clone()]));
+			assert_ok!(Proxy::add_proxy(Origin::signed(alice.clone()), bob.clone(), ProxyType::Any, 0));
+			assert_ok!(Proxy::proxy(Origin::signed(bob.clone()), alice.clone(), None, Box::new(transfer.clone())));
+			assert!(Multisig::as_multi_threshold_1(
+				Origin::signed(alice.clone()),
+				vec![bob.clone()],
+				Box::new(transfer.clone()),
+			).is_err());
+			assert_eq!(Balances::free_balance(&bob), 0);
+
+			assert_ok!(SafeMode::unpause_call(Origin::root(), b"Balances".to_vec(), b"transfer".to_vec()));
+			assert_ok!(Utility::batch(Origin::signed(alice), vec![transfer]));
+			assert_eq!(Balances::free_balance(&bob), DOLLARS);
+		});
+	}
+
+	#[test]
+	fn recovery_proxy_cannot_be_granted_by_non_transfer() {
+		let as_recovered = Call::Recovery(pallet_recovery::Call::as_recovered(
+			Default::default(),
+			Box::new(Call::System(frame_system::Call::remark(vec![]))),
+		));
+		assert!(ProxyType::Recovery.filter(&as_recovered));
+		assert!(!ProxyType::NonTransfer.filter(&as_recovered));
+		assert!(!ProxyType::NonTransfer.is_superset(&ProxyType::Recovery));
+		assert!(ProxyType::Any.is_superset(&ProxyType::Recovery));
+	}
+
+	const PROXY_TYPES: [ProxyType; 12] = [
+		ProxyType::Any,
+		ProxyType::NonTransfer,
+		ProxyType::Governance,
+		ProxyType::Staking,
+		ProxyType::IdentityJudgement,
+		ProxyType::Recovery,
+		ProxyType::CancelProxy,
+		ProxyType::Contracts,
+		ProxyType::SessionKeys,
+		ProxyType::Treasury,
+		ProxyType::Society,
+		ProxyType::SpendingLimit(0),
+	];
+
+	fn remark() -> Call {
+		Call::System(frame_system::Call::remark(vec![]))
+	}
+
+	fn transfer() -> Call {
+		Call::Balances(pallet_balances::Call::transfer(AccountId::from([1u8; 32]).into(), DOLLARS))
+	}
+
+	fn spend(id: pallet_spending_limit::AllowanceId) -> Call {
+		Call::SpendingLimits(pallet_spending_limit::Call::spend(id, AccountId::from([1u8; 32]).into(), DOLLARS))
+	}
+
+	fn call_contract(value: Balance) -> Call {
+		Call::Contracts(pallet_contracts::Call::call(AccountId::from([1u8; 32]).into(), value, 0, vec![]))
+	}
+
+	fn batch(calls: Vec<Call>) -> Call {
+		Call::Utility(pallet_utility::Call::batch(calls))
+	}
+
+	/// At least one call allowed by each proxy type and not by any type it is not a subset of.
+	fn sample_calls() -> Vec<Call> {
+		vec![
+			remark(),
+			transfer(),
+			spend(0),
+			call_contract(0),
+			call_contract(DOLLARS),
+			Call::Council(pallet_collective::Call::close(Default::default(), 0, 0, 0)),
+			Call::Treasury(pallet_treasury::Call::reject_proposal(0)),
+			Call::Bounties(pallet_bounties::Call::close_bounty(0)),
+			Call::Tips(pallet_tips::Call::retract_tip(Default::default())),
+			Call::Staking(pallet_staking::Call::chill()),
+			Call::Session(pallet_session::Call::purge_keys()),
+			Call::Identity(pallet_identity::Call::provide_judgement(
+				0,
+				AccountId::from([1u8; 32]).into(),
+				pallet_identity::Judgement::Reasonable,
+			)),
+			Call::Recovery(pallet_recovery::Call::as_recovered(Default::default(), Box::new(remark()))),
+			Call::Proxy(pallet_proxy::Call::reject_announcement(Default::default(), Default::default())),
+			Call::Society(pallet_society::Call::bid(DOLLARS)),
+			Call::Society(pallet_society::Call::unbid(0)),
+			batch(vec![remark()]),
+			batch(vec![transfer()]),
+			Call::Utility(pallet_utility::Call::as_derivative(0, Box::new(transfer()))),
+		]
+	}
+
+	#[test]
+	fn proxy_type_superset_is_a_partial_order() {
+		for a in PROXY_TYPES.iter() {
+			assert!(a.is_superset(a));
+			for b in PROXY_TYPES.iter() {
+				if a != b && a.is_superset(b) {
+					assert!(!b.is_superset(a), "{:?} and {:?} are supersets of each other", a, b);
+				}
+				for c in PROXY_TYPES.iter() {
+					if a.is_superset(b) && b.is_superset(c) {
+						assert!(a.is_superset(c), "{:?} > {:?} > {:?} is not transitive", a, b, c);
+					}
+				}
+			}
+		}
+	}
+
+	#[test]
+	fn proxy_type_superset_agrees_with_filters() {
+		let calls = sample_calls();
+		for a in PROXY_TYPES.iter() {
+			for b in PROXY_TYPES.iter() {
+				let covers = calls.iter().all(|c| !b.filter(c) || a.filter(c));
+				assert_eq!(a.is_superset(b), covers, "{:?} superset of {:?}", a, b);
+			}
+		}
+	}
+
+	#[test]
+	fn proxy_filters_look_into_batches() {
+		let batch_all = |calls| Call::Utility(pallet_utility::Call::batch_all(calls));
+		let chill = Call::Staking(pallet_staking::Call::chill());
+		let purge_keys = Call::Session(pallet_session::Call::purge_keys());
+
+		assert!(ProxyType::NonTransfer.filter(&batch(vec![remark(), batch_all(vec![remark()])])));
+		assert!(!ProxyType::NonTransfer.filter(&batch(vec![remark(), transfer()])));
+		assert!(!ProxyType::NonTransfer.filter(&batch(vec![batch_all(vec![transfer()])])));
+		assert!(!ProxyType::NonTransfer.filter(
+			&Call::Utility(pallet_utility::Call::as_derivative(0, Box::new(transfer())))
+		));
+		assert!(ProxyType::Any.filter(&batch(vec![transfer()])));
+
+		assert!(ProxyType::Staking.filter(&batch_all(vec![chill.clone(), purge_keys.clone()])));
+		assert!(!ProxyType::SessionKeys.filter(&batch_all(vec![chill, purge_keys.clone()])));
+		assert!(ProxyType::SessionKeys.filter(&batch(vec![purge_keys])));
+	}
+
+	#[test]
+	fn non_transfer_proxy_cannot_move_value() {
+		let bid = Call::Society(pallet_society::Call::bid(DOLLARS));
+
+		assert!(ProxyType::NonTransfer.filter(&call_contract(0)));
+		assert!(!ProxyType::NonTransfer.filter(&call_contract(DOLLARS)));
+		assert!(ProxyType::Contracts.filter(&call_contract(DOLLARS)));
+		assert!(!ProxyType::NonTransfer.filter(&bid));
+		assert!(ProxyType::Society.filter(&bid));
+		assert!(!ProxyType::Governance.filter(&bid));
+	}
+
+	#[test]
+	fn spending_proxies_are_limited_by_their_allowance() {
+		use frame_support::assert_ok;
+
+		sp_io::TestExternalities::default().execute_with(|| {
+			System::set_block_number(1);
+			let alice = AccountId::from([1u8; 32]);
+			let bob = AccountId::from([2u8; 32]);
+			let charlie = AccountId::from([3u8; 32]);
+			Balances::make_free_balance_be(&alice, 1_000 * DOLLARS);
+			Balances::make_free_balance_be(&bob, 1_000 * DOLLARS);
+			let spend = |value| Box::new(Call::SpendingLimits(
+				pallet_spending_limit::Call::spend(0, charlie.clone().into(), value)
+			));
+
+			// the proxy type can not be granted directly, as it may point to any allowance.
+			let add_proxy = Call::Proxy(pallet_proxy::Call::add_proxy(bob.clone(), ProxyType::SpendingLimit(0), 0));
+			assert!(!BaseFilter::filter(&add_proxy));
+			let remove_proxy = Call::Proxy(pallet_proxy::Call::remove_proxy(bob.clone(), ProxyType::SpendingLimit(0), 0));
+			assert!(!BaseFilter::filter(&remove_proxy));
+
+			assert_ok!(SpendingLimits::add_spending_proxy(Origin::signed(alice.clone()), bob.clone(), 10 * DOLLARS, DAYS));
+			assert_ok!(Proxy::proxy(Origin::signed(bob.clone()), alice.clone(), None, spend(6 * DOLLARS)));
+			assert_ok!(Proxy::proxy(Origin::signed(bob.clone()), alice.clone(), None, spend(6 * DOLLARS)));
+			assert_eq!(Balances::free_balance(&charlie), 6 * DOLLARS);
+			assert_eq!(SpendingLimits::remaining(0), Some(4 * DOLLARS));
+			assert!(System::events().iter().any(|r| r.event ==
+				Event::SpendingLimits(pallet_spending_limit::Event::LimitReached(0, 6 * DOLLARS, 4 * DOLLARS))
+			));
+
+			// only spends of the allowance are allowed, not plain transfers.
+			let transfer = Box::new(Call::Balances(
+				pallet_balances::Call::transfer_keep_alive(charlie.clone().into(), DOLLARS)
+			));
+			assert_ok!(Proxy::proxy(Origin::signed(bob.clone()), alice.clone(), None, transfer));
+			assert!(matches!(
+				System::events().last().map(|r| &r.event),
+				Some(Event::Proxy(pallet_proxy::Event::ProxyExecuted(Err(_)))),
+			));
+			assert_eq!(Balances::free_balance(&charlie), 6 * DOLLARS);
+
+			System::set_block_number(1 + DAYS);
+			assert_ok!(Proxy::proxy(Origin::signed(bob), alice, None, spend(6 * DOLLARS)));
+			assert_eq!(Balances::free_balance(&charlie), 12 * DOLLARS);
+		});
+	}
+
+	#[test]
+	fn non_transfer_proxy_cannot_add_a_spending_proxy() {
+		use frame_support::assert_ok;
+
+		sp_io::TestExternalities::default().execute_with(|| {
+			System::set_block_number(1);
+			let alice = AccountId::from([1u8; 32]);
+			let bob = AccountId::from([2u8; 32]);
+			let charlie = AccountId::from([3u8; 32]);
+			Balances::make_free_balance_be(&alice, 1_000 * DOLLARS);
+			let add_spending_proxy = Call::SpendingLimits(
+				pallet_spending_limit::Call::add_spending_proxy(charlie.clone(), 10 * DOLLARS, DAYS)
+			);
+			for proxy_type in [
+				ProxyType::NonTransfer,
+				ProxyType::Governance,
+				ProxyType::Staking,
+				ProxyType::Treasury,
+			].iter() {
+				assert!(!proxy_type.filter(&add_spending_proxy));
+			}
+
+			assert_ok!(Proxy::add_proxy(Origin::signed(alice.clone()), bob.clone(), ProxyType::NonTransfer, 0));
+			assert_ok!(Proxy::proxy(Origin::signed(bob), alice.clone(), None, Box::new(add_spending_proxy)));
+			assert!(matches!(
+				System::events().last().map(|r| &r.event),
+				Some(Event::Proxy(pallet_proxy::Event::ProxyExecuted(Err(_)))),
+			));
+			assert_eq!(SpendingLimits::allowance(0), None);
+			assert_eq!(Proxy::proxies(&alice).0.len(), 1);
+		});
+	}
+
+	#[test]
+	fn asset_fees_follow_the_fee_and_tip_splits() {
+		use frame_support::{assert_ok, weights::{DispatchInfo, PostDispatchInfo}};
+		use pallet_asset_tx_payment::ChargeAssetTxPayment;
+		use sp_runtime::{FixedU128, traits::SignedExtension};
+
+		sp_io::TestExternalities::default().execute_with(|| {
+			System::set_block_number(1);
+			let alice = AccountId::from([1u8; 32]);
+			let ecosystem = AccountId::from([2u8; 32]);
+			let author = Authorship::author();
+			let treasury = Treasury::account_id();
+			assert_ok!(Assets::force_create(Origin::root(), 1, alice.clone().into(), true, 1));
+			assert_ok!(Assets::mint(Origin::signed(alice.clone()), 1, alice.clone().into(), 1_000 * DOLLARS));
+			assert_ok!(AssetTxPayment::set_conversion_rate(Origin::root(), 1, Some(FixedU128::from(2))));
+			assert_ok!(FeeSplit::set_ecosystem_account(Origin::root(), Some(ecosystem.clone())));
+			let split = Split {
+				treasury: Permill::from_percent(40),
+				author: Permill::from_percent(30),
+				burn: Permill::from_percent(20),
+				ecosystem: Permill::from_percent(10),
+			};
+			assert_ok!(FeeSplit::set_split(Origin::root(), ImbalanceKind::Fee, split));
+			let tip_split = Split { author: Permill::from_percent(100), ..Default::default() };
+			assert_ok!(FeeSplit::set_split(Origin::root(), ImbalanceKind::Tip, tip_split));
+
+			let call = Call::System(frame_system::Call::remark(vec![]));
+			let info = DispatchInfo { weight: 1_000_000, ..Default::default() };
+			let len = 100;
+			let tip = 10 * CENTS;
+			let fee = TransactionPayment::compute_fee(len as u32, &info, tip) - tip;
+			let supply = Assets::total_supply(1);
+			let pre = ChargeAssetTxPayment::<Runtime>::from(tip, Some(1))
+				.pre_dispatch(&alice, &call, &info, len)
+				.unwrap();
+			assert_ok!(ChargeAssetTxPayment::<Runtime>::post_dispatch(
+				pre,
+				&info,
+				&PostDispatchInfo::default(),
+				len,
+				&Ok(()),
+			));
+
+			// the fee and tip are paid at the rate, and the burnt share leaves the supply.
+			let (fee, tip) = (2 * fee, 2 * tip);
+			let burnt = Permill::from_percent(20) * fee;
+			assert_eq!(Assets::balance(1, &author), Permill::from_percent(30) * fee + tip);
+			assert_eq!(Assets::balance(1, &ecosystem), Permill::from_percent(10) * fee);
+			assert_eq!(Assets::total_supply(1), supply - burnt);
+			assert_eq!(
+				Assets::balance(1, &treasury),
+				fee + tip - Assets::balance(1, &author) - Assets::balance(1, &ecosystem) - burnt,
+			);
+		});
+	}
+
+	#[test]
+	fn dust_and_treasury_burn_follow_their_split() {
+		use frame_support::{assert_ok, traits::OnUnbalanced};
+
+		sp_io::TestExternalities::default().execute_with(|| {
+			System::set_block_number(1);
+			let alice = AccountId::from([1u8; 32]);
+			let bob = AccountId::from([2u8; 32]);
+			let treasury = Treasury::account_id();
+			Balances::make_free_balance_be(&alice, 20 * DOLLARS);
+			Balances::make_free_balance_be(&treasury, 100 * DOLLARS);
+
+			// by default dust is burnt.
+			let issuance = Balances::total_issuance();
+			assert_ok!(Balances::transfer(Origin::signed(alice.clone()), bob.clone().into(), 19 * DOLLARS + 50 * CENTS));
+			assert_eq!(Balances::total_issuance(), issuance - 50 * CENTS);
+			assert_eq!(Balances::free_balance(&treasury), 100 * DOLLARS);
+
+			let to_treasury = Split { treasury: Permill::from_percent(100), ..Default::default() };
+			assert_ok!(FeeSplit::set_split(Origin::root(), ImbalanceKind::Dust, to_treasury));
+			let issuance = Balances::total_issuance();
+			assert_ok!(Balances::transfer(Origin::signed(bob), alice, 19 * DOLLARS));
+			assert_eq!(Balances::total_issuance(), issuance);
+			assert_eq!(Balances::free_balance(&treasury), 100 * DOLLARS + 50 * CENTS);
+
+			// the burn of the treasury is split the same way.
+			let half = Split {
+				treasury: Permill::from_percent(50),
+				burn: Permill::from_percent(50),
+				..Default::default()
+			};
+			assert_ok!(FeeSplit::set_split(Origin::root(), ImbalanceKind::Burn, half));
+			let (burn, _) = Balances::slash(&treasury, 10 * DOLLARS);
+			DealWithBurn::on_unbalanced(burn);
+			assert_eq!(Balances::free_balance(&treasury), 95 * DOLLARS + 50 * CENTS);
+		});
+	}
+
+	#[test]
+	fn on_chain_fallback_is_bounded_by_weight() {
+		use frame_support::traits::Get;
+		use pallet_election_provider_multi_phase::{
+			DesiredTargets, FallbackStrategy, SnapshotMetadata, SolutionOrSnapshotSize,
+		};
+
+		sp_io::TestExternalities::default().execute_with(|| {
+			// without a snapshot the size of the election is unknown.
+			assert_eq!(OnChainFallback::get(), FallbackStrategy::Nothing);
+
+			DesiredTargets::<Runtime>::put(100);
+			SnapshotMetadata::<Runtime>::put(SolutionOrSnapshotSize { voters: 1_000, targets: 200 });
+			assert_eq!(OnChainFallback::get(), FallbackStrategy::OnChain);
+
+			// the same election does not fit once the block is nearly full.
+			System::register_extra_weight_unchecked(
+				RuntimeBlockWeights::get().max_block - onchain_election_weight(1_000, 200, 100) + 1,
+				DispatchClass::Mandatory,
+			);
+			assert_eq!(OnChainFallback::get(), FallbackStrategy::Nothing);
+
+			SnapshotMetadata::<Runtime>::put(SolutionOrSnapshotSize { voters: 10_000_000, targets: 10_000 });
+			assert_eq!(OnChainFallback::get(), FallbackStrategy::Nothing);
+		});
+	}
+
+	#[test]
+	fn parameter_defaults_are_within_their_bounds() {
+		use pallet_parameters::ParameterSchema;
+
+		for key in RuntimeParameters::keys() {
+			let default = RuntimeParameters::default_value(key);
+			assert!(RuntimeParameters::is_valid(key, &default), "{:?} is out of bounds", default);
+		}
+	}
+
+	#[test]
+	fn config_constants_follow_their_parameters() {
+		use frame_support::{assert_noop, assert_ok};
+		use sp_runtime::traits::BadOrigin;
+
+		sp_io::TestExternalities::default().execute_with(|| {
+			assert_eq!(<Runtime as pallet_transaction_payment::Config>::TransactionByteFee::get(), 10 * MILLICENTS);
+			assert_eq!(ProxyDepositBase::get(), 15 * CENTS + 8 * 6 * CENTS);
+			assert_eq!(<Runtime as pallet_democracy::Config>::VotingPeriod::get(), 28 * DAYS);
+
+			let council: Origin = pallet_collective::RawOrigin::<AccountId, CouncilCollective>::Members(1, 1).into();
+			assert_ok!(Parameters::set_parameter(
+				council.clone(),
+				ParameterKey::TransactionByteFee,
+				ParameterValue::TransactionByteFee(20 * MILLICENTS),
+			));
+			assert_ok!(Parameters::set_parameter(
+				council.clone(),
+				ParameterKey::DepositPerItem,
+				ParameterValue::DepositPerItem(30 * CENTS),
+			));
+			// only a referendum can change the democracy periods.
+			assert_noop!(
+				Parameters::set_parameter(council, ParameterKey::VotingPeriod, ParameterValue::VotingPeriod(7 * DAYS)),
+				BadOrigin,
+			);
+			assert_ok!(Parameters::set_parameter(
+				Origin::root(),
+				ParameterKey::VotingPeriod,
+				ParameterValue::VotingPeriod(7 * DAYS),
+			));
+
+			assert_eq!(<Runtime as pallet_transaction_payment::Config>::TransactionByteFee::get(), 20 * MILLICENTS);
+			assert_eq!(ProxyDepositBase::get(), 30 * CENTS + 8 * 6 * CENTS);
+			assert_eq!(<Runtime as pallet_democracy::Config>::VotingPeriod::get(), 7 * DAYS);
+			// deposits that are recomputed rather than stored keep their price.
+			assert_eq!(DepositPerStorageItem::get(), 15 * CENTS);
+			assert_eq!(PoolMemberDeposit::get(), deposit(1, 245));
+		});
+	}
+
+	#[test]
+	fn nomination_pools_bond_and_nominate_through_staking() {
+		use frame_support::assert_ok;
+
+		sp_io::TestExternalities::default().execute_with(|| {
+			System::set_block_number(1);
+			let alice = AccountId::from([1u8; 32]);
+			let bob = AccountId::from([2u8; 32]);
+			let validator = AccountId::from([3u8; 32]);
+			Balances::make_free_balance_be(&alice, 1_000 * DOLLARS);
+			Balances::make_free_balance_be(&bob, 1_000 * DOLLARS);
+
+			assert_ok!(NominationPools::create(Origin::signed(alice.clone()), 100 * DOLLARS, vec![validator.clone()]));
+			assert_ok!(NominationPools::join(Origin::signed(bob.clone()), 0, 50 * DOLLARS));
+			let bonded = NominationPools::bonded_account(0);
+			let reward = NominationPools::reward_account(0);
+			assert_eq!(Staking::ledger(&bonded).unwrap().active, 150 * DOLLARS);
+			assert_eq!(Staking::nominators(&bonded).unwrap().targets, vec![validator]);
+			assert_eq!(Staking::payee(&bonded), pallet_staking::RewardDestination::Account(reward.clone()));
+
+			// a payout to the reward account is shared by points.
+			Balances::make_free_balance_be(&reward, ExistentialDeposit::get() + 30 * DOLLARS);
+			assert_eq!(NominationPools::pending_rewards(&alice), 20 * DOLLARS);
+			assert_eq!(NominationPools::pending_rewards(&bob), 10 * DOLLARS);
+
+			assert_ok!(NominationPools::unbond(Origin::signed(bob.clone()), 20 * DOLLARS));
+			assert_ok!(NominationPools::unbond(Origin::signed(bob.clone()), 30 * DOLLARS));
+			assert_eq!(Staking::ledger(&bonded).unwrap().active, 100 * DOLLARS);
+			// unbonds in the same era share one unlocking chunk.
+			assert_eq!(Staking::ledger(&bonded).unwrap().unlocking.len(), 1);
+			assert_eq!(Staking::ledger(&bonded).unwrap().unlocking[0].value, 50 * DOLLARS);
+			assert_eq!(
+				Balances::free_balance(&bob),
+				1_000 * DOLLARS - 50 * DOLLARS - PoolMemberDeposit::get() + 10 * DOLLARS,
+			);
+		});
+	}
 }
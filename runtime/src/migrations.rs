@@ -0,0 +1,385 @@
+// Copyright 2019 TrustBase Network
+// This file is part of TrustBase library.
+//
+// The TrustBase library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Lesser General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// The TrustBase library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU Lesser General Public License for more details.
+
+//! Storage migrations of the trustbase runtime.
+//!
+//! Every migration is tagged with the `spec_version` that introduced it through [`Versioned`],
+//! and [`Migrations`] lists them in the order in which they have to run. On a runtime upgrade
+//! the migrations of every `spec_version` above the last one applied, up to and including the
+//! new [`VERSION`], run exactly once. The last `spec_version` applied is kept by
+//! `pallet_migrations`, so later upgrades skip them. A new chain records the `spec_version` of
+//! its genesis runtime, as its storage already has the layout of that version.
+//!
+//! With the `try-runtime` feature, the `pre_upgrade` and `post_upgrade` hooks of the pending
+//! migrations are checked by `TryRuntime_on_runtime_upgrade`, e.g. through
+//! `trustbase try-runtime on-runtime-upgrade live`.
+
+use frame_support::{
+	storage::migration,
+	traits::{Get, OnRuntimeUpgrade},
+	weights::{Weight, constants::RocksDbWeight},
+};
+use crate::{Runtime, VERSION};
+#[cfg(any(feature = "try-runtime", test))]
+use sp_std::prelude::*;
+#[cfg(any(feature = "try-runtime", test))]
+use crate::{AccountId, CouncilCollective, Origin, RuntimeParameters, TechnicalCollective};
+
+/// All migrations of the runtime, in ascending `spec_version`.
+///
+/// Migrations of the same `spec_version` share one [`Versioned`] entry, as the version is
+/// recorded once they have all run.
+pub type Migrations = (
+	Versioned<2, (RemoveCollectiveFlip, OpenRootOnlyOrigins)>,
+);
+
+/// The `spec_version` of the last migrations applied, if any.
+pub fn last_applied() -> Option<u32> {
+	pallet_migrations::Pallet::<Runtime>::last_applied()
+}
+
+/// Runs the migrations `M` of `spec_version` `SPEC`, if they are due.
+pub struct Versioned<const SPEC: u32, M>(sp_std::marker::PhantomData<M>);
+
+impl<const SPEC: u32, M: OnRuntimeUpgrade> Versioned<SPEC, M> {
+	/// Whether `M` has not run yet and the runtime is at `SPEC` or above.
+	fn is_due() -> bool {
+		last_applied().map_or(true, |last| last < SPEC) && VERSION.spec_version >= SPEC
+	}
+}
+
+impl<const SPEC: u32, M: OnRuntimeUpgrade> OnRuntimeUpgrade for Versioned<SPEC, M> {
+	fn on_runtime_upgrade() -> Weight {
+		if !Self::is_due() {
+			return RocksDbWeight::get().reads(1)
+		}
+		let weight = M::on_runtime_upgrade();
+		pallet_migrations::LastAppliedSpecVersion::<Runtime>::put(SPEC);
+		weight.saturating_add(RocksDbWeight::get().reads_writes(1, 1))
+	}
+
+	#[cfg(feature = "try-runtime")]
+	fn pre_upgrade() -> Result<(), &'static str> {
+		if Self::is_due() {
+			M::pre_upgrade()?;
+		}
+		Ok(())
+	}
+
+	#[cfg(feature = "try-runtime")]
+	fn post_upgrade() -> Result<(), &'static str> {
+		if VERSION.spec_version < SPEC {
+			return Ok(())
+		}
+		if last_applied().map_or(true, |last| last < SPEC) {
+			return Err("migrations of a due spec version were not applied")
+		}
+		M::post_upgrade()
+	}
+}
+
+/// Removes the storage of the `RandomnessCollectiveFlip` pallet, which was replaced by BABE
+/// randomness.
+pub struct RemoveCollectiveFlip;
+impl OnRuntimeUpgrade for RemoveCollectiveFlip {
+	fn on_runtime_upgrade() -> Weight {
+		migration::remove_storage_prefix(b"RandomnessCollectiveFlip", b"RandomMaterial", b"");
+		RocksDbWeight::get().writes(1)
+	}
+
+	#[cfg(feature = "try-runtime")]
+	fn post_upgrade() -> Result<(), &'static str> {
+		if migration::have_storage_value(b"RandomnessCollectiveFlip", b"RandomMaterial", b"") {
+			return Err("the collective flip random material is still in storage")
+		}
+		Ok(())
+	}
+}
+
+/// Opens the origins that used to accept root alone to the collectives, ahead of a referendum
+/// retiring the sudo key through `SudoRetirement::retire_sudo`. The origins are part of the
+/// runtime config, so the upgrade changes no storage, and its checks enumerate every origin of
+/// the config that accepts root, and every call that checks for root itself.
+pub struct OpenRootOnlyOrigins;
+
+/// The calls that check for root in their own code rather than through an origin of the
+/// runtime config, by pallet.
+///
+/// Once the sudo key is retired, they are reached through a referendum: the council proposes
+/// one with `Democracy::external_propose`, or anyone with `Democracy::propose`, and `Democracy`
+/// dispatches the passed proposal as root. The technical committee can fast-track it.
+#[cfg(any(feature = "try-runtime", test))]
+const ROOT_CALLS: &[(&str, &[&str])] = &[
+	("System", &[
+		"set_heap_pages", "set_code", "set_code_without_checks", "set_changes_trie_config",
+		"set_storage", "kill_storage", "kill_prefix",
+	]),
+	("Babe", &["plan_config_change"]),
+	("Indices", &["force_transfer"]),
+	("Balances", &["set_balance", "force_transfer"]),
+	("Staking", &[
+		"set_validator_count", "increase_validator_count", "scale_validator_count",
+		"force_no_eras", "force_new_era", "set_invulnerables", "force_unstake",
+		"force_new_era_always", "set_history_depth",
+	]),
+	("Democracy", &["cancel_referendum", "cancel_queued", "clear_public_proposals", "enact_proposal"]),
+	("Council", &["set_members", "disapprove_proposal"]),
+	("TechnicalCommittee", &["set_members", "disapprove_proposal"]),
+	("Elections", &["remove_member", "clean_defunct_voters"]),
+	("Grandpa", &["note_stalled"]),
+	("Society", &["set_max_members"]),
+	("Vesting", &["force_vested_transfer"]),
+	("Recovery", &["set_recovered"]),
+];
+
+/// The origins of the config through which a referendum reaching [`ROOT_CALLS`] is proposed or
+/// fast-tracked by a collective.
+#[cfg(any(feature = "try-runtime", test))]
+const REFERENDUM_ROUTE: &[(&str, &str)] = &[
+	("Democracy", "ExternalOrigin"),
+	("Democracy", "ExternalMajorityOrigin"),
+	("Democracy", "FastTrackOrigin"),
+];
+
+/// The origins of the runtime config that only root passes on purpose, by pallet and name. Once
+/// the sudo key is retired, only a referendum passes them.
+#[cfg(any(feature = "try-runtime", test))]
+const REFERENDUM_ONLY: &[(&str, &str)] = &[
+	("AuthorizedUpgrade", "AuthorizeOrigin"),
+	("SudoRetirement", "RetireOrigin"),
+	("Parameters", "LaunchPeriod"),
+	("Parameters", "VotingPeriod"),
+	("Parameters", "EnactmentPeriod"),
+	("Parameters", "CooloffPeriod"),
+];
+
+#[cfg(any(feature = "try-runtime", test))]
+impl OpenRootOnlyOrigins {
+	/// Every origin of the runtime config that root passes, by pallet and name, with whether a
+	/// unanimous council or technical committee passes it as well.
+	fn root_origins() -> Vec<((&'static str, &'static str), bool)> {
+		use frame_support::traits::EnsureOrigin;
+		use pallet_parameters::ParameterSchema;
+
+		fn council() -> Origin {
+			pallet_collective::RawOrigin::<AccountId, CouncilCollective>::Members(1, 1).into()
+		}
+		fn technical_committee() -> Origin {
+			pallet_collective::RawOrigin::<AccountId, TechnicalCollective>::Members(1, 1).into()
+		}
+		fn access<O: EnsureOrigin<Origin>>() -> (bool, bool) {
+			(
+				O::try_origin(Origin::root()).is_ok(),
+				O::try_origin(council()).is_ok() || O::try_origin(technical_committee()).is_ok(),
+			)
+		}
+		macro_rules! origins {
+			($( $pallet:ident: $config:path => $( $name:ident ),+; )*) => {
+				vec![$($(
+					((stringify!($pallet), stringify!($name)), access::<<Runtime as $config>::$name>()),
+				)+)*]
+			}
+		}
+
+		let mut origins = origins! {
+			Scheduler: pallet_scheduler::Config => ScheduleOrigin;
+			AssetTxPayment: pallet_asset_tx_payment::Config => RateOrigin;
+			Staking: pallet_staking::Config => SlashCancelOrigin;
+			ElectionProviderMultiPhase: pallet_election_provider_multi_phase::Config => ForceOrigin;
+			Democracy: pallet_democracy::Config => ExternalOrigin, ExternalMajorityOrigin,
+				ExternalDefaultOrigin, FastTrackOrigin, InstantOrigin, CancellationOrigin,
+				CancelProposalOrigin, BlacklistOrigin, VetoOrigin, OperationalPreimageOrigin;
+			TechnicalMembership: pallet_membership::Config<pallet_membership::Instance1> =>
+				AddOrigin, RemoveOrigin, SwapOrigin, ResetOrigin, PrimeOrigin;
+			Treasury: pallet_treasury::Config => ApproveOrigin, RejectOrigin;
+			Society: pallet_society::Config => FounderSetOrigin, SuspensionJudgementOrigin;
+			Identity: pallet_identity::Config => ForceOrigin, RegistrarOrigin;
+			Assets: pallet_assets::Config => ForceOrigin;
+			Uniques: pallet_uniques::Config => ForceOrigin;
+			SafeMode: pallet_safe_mode::Config => PauseOrigin, UnpauseOrigin;
+			FeeSplit: pallet_fee_split::Config => SplitOrigin;
+			AuthorizedUpgrade: pallet_authorized_upgrade::Config => AuthorizeOrigin;
+			SudoRetirement: pallet_sudo_retirement::Config => RetireOrigin;
+		};
+		origins.extend(RuntimeParameters::keys().into_iter().map(|key| {
+			let passes = |origin| RuntimeParameters::ensure_origin(key, origin).is_ok();
+			(
+				("Parameters", RuntimeParameters::name(key)),
+				(passes(Origin::root()), passes(council()) || passes(technical_committee())),
+			)
+		}));
+		origins.into_iter()
+			.filter(|(_, (root, _))| *root)
+			.map(|(name, (_, collective))| (name, collective))
+			.collect()
+	}
+
+	/// Checks that every origin that root passes is open to the collectives, or referendum-only
+	/// on purpose, and that the calls checking for root themselves are reached by a referendum
+	/// the collectives can bring about.
+	fn ensure_no_root_only_origins() -> Result<(), &'static str> {
+		use frame_support::traits::GetCallMetadata;
+
+		let origins = Self::root_origins();
+		for (name, collective) in &origins {
+			if !collective && !REFERENDUM_ONLY.contains(name) {
+				log::error!("{}::{} only accepts root", name.0, name.1);
+				return Err("an origin that only root passes is not referendum-only on purpose")
+			}
+		}
+		for (pallet, calls) in ROOT_CALLS {
+			let names = <crate::Call as GetCallMetadata>::get_call_names(pallet);
+			if let Some(call) = calls.iter().find(|call| !names.contains(call)) {
+				log::error!("{}::{} is not a call of the runtime", pallet, call);
+				return Err("a root-only call is not a call of the runtime")
+			}
+		}
+		for name in REFERENDUM_ROUTE {
+			if !origins.contains(&(*name, true)) {
+				log::error!("{}::{} is not open to the collectives", name.0, name.1);
+				return Err("no referendum reaches the root-only calls")
+			}
+		}
+		Ok(())
+	}
+}
+
+impl OnRuntimeUpgrade for OpenRootOnlyOrigins {
+	fn on_runtime_upgrade() -> Weight {
+		0
+	}
+
+	#[cfg(feature = "try-runtime")]
+	fn pre_upgrade() -> Result<(), &'static str> {
+		Self::ensure_no_root_only_origins()
+	}
+
+	#[cfg(feature = "try-runtime")]
+	fn post_upgrade() -> Result<(), &'static str> {
+		Self::ensure_no_root_only_origins()
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::Hash;
+
+	fn put_collective_flip() {
+		migration::put_storage_value(
+			b"RandomnessCollectiveFlip",
+			b"RandomMaterial",
+			b"",
+			vec![Hash::default(); 3],
+		);
+	}
+
+	fn has_collective_flip() -> bool {
+		migration::have_storage_value(b"RandomnessCollectiveFlip", b"RandomMaterial", b"")
+	}
+
+	#[test]
+	fn collective_flip_storage_is_removed() {
+		sp_io::TestExternalities::default().execute_with(|| {
+			put_collective_flip();
+			RemoveCollectiveFlip::on_runtime_upgrade();
+			assert!(!has_collective_flip());
+		});
+	}
+
+	#[test]
+	fn every_root_origin_is_open_to_the_collectives_or_referendum_only() {
+		sp_io::TestExternalities::default().execute_with(|| {
+			assert_eq!(OpenRootOnlyOrigins::ensure_no_root_only_origins(), Ok(()));
+
+			let origins = OpenRootOnlyOrigins::root_origins();
+			assert!(origins.contains(&(("Scheduler", "ScheduleOrigin"), true)));
+			assert!(origins.contains(&(("Democracy", "BlacklistOrigin"), true)));
+			// every origin that is referendum-only on purpose still exists and only accepts root.
+			for name in REFERENDUM_ONLY {
+				assert!(origins.contains(&(*name, false)), "{:?} is not a root-only origin", name);
+			}
+		});
+	}
+
+	#[test]
+	fn root_calls_only_accept_root() {
+		use frame_support::dispatch::Dispatchable;
+		use sp_runtime::DispatchError;
+		use crate::Call;
+
+		sp_io::TestExternalities::default().execute_with(|| {
+			let council: Origin =
+				pallet_collective::RawOrigin::<AccountId, CouncilCollective>::Members(1, 1).into();
+			let who = AccountId::from([1u8; 32]);
+			let calls = vec![
+				Call::System(frame_system::Call::set_heap_pages(8)),
+				Call::Balances(pallet_balances::Call::set_balance(who.clone().into(), 0, 0)),
+				Call::Balances(pallet_balances::Call::force_transfer(who.clone().into(), who.clone().into(), 0)),
+				Call::Staking(pallet_staking::Call::force_new_era()),
+				Call::Staking(pallet_staking::Call::set_validator_count(1)),
+				Call::Vesting(pallet_vesting::Call::force_vested_transfer(
+					who.clone().into(),
+					who.clone().into(),
+					pallet_vesting::VestingInfo { locked: 0, per_block: 0, starting_block: 0 },
+				)),
+			];
+			for call in calls {
+				let error = call.clone().dispatch(council.clone()).unwrap_err().error;
+				assert_eq!(error, DispatchError::BadOrigin, "{:?}", call);
+			}
+		});
+	}
+
+	#[test]
+	fn migrations_run_once_per_spec_version() {
+		sp_io::TestExternalities::default().execute_with(|| {
+			assert_eq!(last_applied(), None);
+			put_collective_flip();
+			Migrations::on_runtime_upgrade();
+			assert!(!has_collective_flip());
+			assert!(last_applied().is_some());
+
+			// an upgrade that keeps the spec version does not run them again.
+			put_collective_flip();
+			Migrations::on_runtime_upgrade();
+			assert!(has_collective_flip());
+		});
+	}
+
+	#[test]
+	fn a_fresh_genesis_skips_the_migrations_of_its_spec_version() {
+		use frame_support::traits::GenesisBuild;
+
+		let mut t = frame_system::GenesisConfig::default().build_storage::<Runtime>().unwrap();
+		GenesisBuild::<Runtime>::assimilate_storage(&pallet_migrations::GenesisConfig::default(), &mut t)
+			.unwrap();
+		sp_io::TestExternalities::new(t).execute_with(|| {
+			assert_eq!(last_applied(), Some(VERSION.spec_version));
+			put_collective_flip();
+			Migrations::on_runtime_upgrade();
+			assert!(has_collective_flip());
+			assert_eq!(last_applied(), Some(VERSION.spec_version));
+		});
+	}
+
+	#[test]
+	fn migrations_of_a_later_spec_version_wait() {
+		sp_io::TestExternalities::default().execute_with(|| {
+			put_collective_flip();
+			Versioned::<{ u32::MAX }, RemoveCollectiveFlip>::on_runtime_upgrade();
+			assert!(has_collective_flip());
+			assert_eq!(last_applied(), None);
+		});
+	}
+}
@@ -0,0 +1,213 @@
+// Copyright 2019 TrustBase Network
+// This file is part of TrustBase library.
+//
+// The TrustBase library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Lesser General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// The TrustBase library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU Lesser General Public License for more details.
+
+//! Projections of staking rewards and unbonding, served through the staking runtime API.
+
+use sp_std::prelude::*;
+use sp_runtime::Perbill;
+use frame_support::traits::Currency;
+use pallet_staking::EraPayout as _;
+use pallet_staking_rpc_runtime_api::{EraIndex, EraPayout, ValidatorReward, Unbonding};
+use node_primitives::{AccountId, Balance};
+use crate::{Runtime, Staking, Balances, SessionsPerEra, constants::time::{EPOCH_DURATION_IN_SLOTS, SLOT_DURATION}};
+
+/// The duration of an era that runs as planned, in milliseconds.
+pub fn era_duration() -> u64 {
+	SessionsPerEra::get() as u64 * EPOCH_DURATION_IN_SLOTS * SLOT_DURATION
+}
+
+/// The payout of the active era, computed the way staking does when the era ends.
+pub fn projected_era_payout() -> Option<EraPayout<Balance>> {
+	let era = Staking::active_era()?.index;
+	let total_staked = Staking::eras_total_stake(era);
+	let total_issuance = Balances::total_issuance();
+	let era_duration = era_duration();
+	let (validator_payout, remainder) = <Runtime as pallet_staking::Config>::EraPayout::era_payout(
+		total_staked,
+		total_issuance,
+		era_duration,
+	);
+
+	Some(EraPayout { era, total_staked, total_issuance, era_duration, validator_payout, remainder })
+}
+
+/// The reward of `validator` for the active era, split the way `payout_stakers` does.
+///
+/// The validator payout is shared by reward points, which are only known once the era is over,
+/// so every validator of the era is assumed to earn the same.
+pub fn expected_validator_reward(validator: AccountId) -> Option<ValidatorReward<AccountId, Balance>> {
+	let payout = projected_era_payout()?;
+	let era = payout.era;
+	let exposure = Staking::eras_stakers_clipped(era, &validator);
+	if exposure.total == 0 {
+		return None
+	}
+
+	let validators = pallet_staking::ErasValidatorPrefs::<Runtime>::iter_prefix_values(era).count();
+	let total = payout.validator_payout / validators.max(1) as Balance;
+	let commission = Staking::eras_validator_prefs(era, &validator).commission * total;
+	let leftover = total.saturating_sub(commission);
+	let own = Perbill::from_rational(exposure.own, exposure.total) * leftover;
+	let nominators = exposure.others.into_iter()
+		.map(|nominator| (nominator.who, Perbill::from_rational(nominator.value, exposure.total) * leftover))
+		.collect();
+
+	Some(ValidatorReward { era, total, validator: commission.saturating_add(own), nominators })
+}
+
+/// The eras in history that `stash` validated in and that `payout_stakers` can still be called
+/// for.
+pub fn unclaimed_eras(stash: AccountId) -> Vec<EraIndex> {
+	let ledger = match Staking::bonded(&stash).and_then(|controller| Staking::ledger(&controller)) {
+		Some(ledger) => ledger,
+		None => return Vec::new(),
+	};
+	let current_era = Staking::current_era().unwrap_or(0);
+	let oldest_era = current_era.saturating_sub(Staking::history_depth());
+
+	(oldest_era..=current_era)
+		.filter(|era| !ledger.claimed_rewards.contains(era))
+		.filter(|era| Staking::eras_validator_reward(era).is_some())
+		.filter(|era| Staking::eras_stakers(era, &stash).total > 0)
+		.collect()
+}
+
+/// The unbonding schedule of the ledger of `who`, which may be its stash or its controller.
+pub fn unbonding(who: AccountId) -> Option<Unbonding<Balance>> {
+	let controller = Staking::bonded(&who).unwrap_or(who);
+	let ledger = Staking::ledger(&controller)?;
+	let current_era = Staking::current_era().unwrap_or(0);
+
+	// chunks of past eras are released by the next `withdraw_unbonded`.
+	let mut withdrawable: Balance = 0;
+	let mut chunks = Vec::new();
+	for chunk in ledger.unlocking {
+		if chunk.era <= current_era {
+			withdrawable = withdrawable.saturating_add(chunk.value);
+		} else {
+			chunks.push((chunk.era, chunk.value));
+		}
+	}
+
+	Some(Unbonding { active: ledger.active, withdrawable, chunks })
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use sp_runtime::assert_eq_error_rate;
+	use pallet_staking::{ActiveEraInfo, Exposure, IndividualExposure, StakingLedger, UnlockChunk, ValidatorPrefs};
+	use crate::constants::currency::DOLLARS;
+
+	const MILLISECS_PER_YEAR: u64 = 1000 * 3600 * 24 * 36525 / 100;
+
+	fn start_era(era: EraIndex) {
+		pallet_staking::CurrentEra::<Runtime>::put(era);
+		pallet_staking::ActiveEra::<Runtime>::put(ActiveEraInfo { index: era, start: None });
+	}
+
+	fn elect(era: EraIndex, validator: &AccountId, commission: Perbill, exposure: Exposure<AccountId, Balance>) {
+		pallet_staking::ErasValidatorPrefs::<Runtime>::insert(
+			era,
+			validator,
+			ValidatorPrefs { commission, ..Default::default() },
+		);
+		pallet_staking::ErasTotalStake::<Runtime>::mutate(era, |total| *total += exposure.total);
+		pallet_staking::ErasStakersClipped::<Runtime>::insert(era, validator, exposure.clone());
+		pallet_staking::ErasStakers::<Runtime>::insert(era, validator, exposure);
+	}
+
+	fn exposure(own: Balance, others: Vec<(AccountId, Balance)>) -> Exposure<AccountId, Balance> {
+		Exposure {
+			total: own + others.iter().map(|(_, value)| value).sum::<Balance>(),
+			own,
+			others: others.into_iter().map(|(who, value)| IndividualExposure { who, value }).collect(),
+		}
+	}
+
+	#[test]
+	fn era_payout_follows_the_reward_curve() {
+		sp_io::TestExternalities::default().execute_with(|| {
+			let validator = AccountId::from([1u8; 32]);
+			assert_eq!(projected_era_payout(), None);
+
+			Balances::make_free_balance_be(&validator, 1_000_000 * DOLLARS);
+			start_era(1);
+			// half of the issuance is staked, where the curve pays the maximum inflation.
+			elect(1, &validator, Perbill::zero(), exposure(500_000 * DOLLARS, vec![]));
+
+			let payout = projected_era_payout().unwrap();
+			let max_payout = 1_000_000 * DOLLARS / 10 * era_duration() as Balance / MILLISECS_PER_YEAR as Balance;
+			assert_eq!(payout.era, 1);
+			assert_eq!(payout.total_staked, 500_000 * DOLLARS);
+			assert_eq!(payout.total_issuance, 1_000_000 * DOLLARS);
+			assert_eq_error_rate!(payout.validator_payout, max_payout, max_payout / 100);
+			assert_eq_error_rate!(payout.remainder, 0, max_payout / 100);
+		});
+	}
+
+	#[test]
+	fn validator_reward_is_split_by_commission_and_exposure() {
+		sp_io::TestExternalities::default().execute_with(|| {
+			let validator = AccountId::from([1u8; 32]);
+			let other = AccountId::from([2u8; 32]);
+			let nominator = AccountId::from([3u8; 32]);
+			Balances::make_free_balance_be(&validator, 1_000_000 * DOLLARS);
+			start_era(1);
+			elect(1, &validator, Perbill::from_percent(10), exposure(100 * DOLLARS, vec![(nominator.clone(), 300 * DOLLARS)]));
+			elect(1, &other, Perbill::zero(), exposure(400 * DOLLARS, vec![]));
+			assert_eq!(expected_validator_reward(nominator.clone()), None);
+
+			let payout = projected_era_payout().unwrap();
+			let reward = expected_validator_reward(validator).unwrap();
+			assert_eq!(reward.total, payout.validator_payout / 2);
+			let commission = Perbill::from_percent(10) * reward.total;
+			let leftover = reward.total - commission;
+			assert_eq!(reward.validator, commission + Perbill::from_percent(25) * leftover);
+			assert_eq!(reward.nominators, vec![(nominator, Perbill::from_percent(75) * leftover)]);
+		});
+	}
+
+	#[test]
+	fn unclaimed_eras_and_unbonding_come_from_the_ledger() {
+		sp_io::TestExternalities::default().execute_with(|| {
+			let stash = AccountId::from([1u8; 32]);
+			let controller = AccountId::from([2u8; 32]);
+			pallet_staking::Bonded::<Runtime>::insert(&stash, &controller);
+			pallet_staking::Ledger::<Runtime>::insert(&controller, StakingLedger {
+				stash: stash.clone(),
+				total: 130 * DOLLARS,
+				active: 100 * DOLLARS,
+				unlocking: vec![
+					UnlockChunk { value: 10 * DOLLARS, era: 2 },
+					UnlockChunk { value: 20 * DOLLARS, era: 5 },
+				],
+				claimed_rewards: vec![1],
+			});
+			for era in 0..3 {
+				elect(era, &stash, Perbill::zero(), exposure(100 * DOLLARS, vec![]));
+				pallet_staking::ErasValidatorReward::<Runtime>::insert(era, 10 * DOLLARS);
+			}
+			start_era(3);
+
+			// era 1 is claimed and era 3 isn't over yet.
+			assert_eq!(unclaimed_eras(stash.clone()), vec![0, 2]);
+			assert_eq!(unclaimed_eras(controller.clone()), Vec::<EraIndex>::new());
+
+			let schedule = Unbonding { active: 100 * DOLLARS, withdrawable: 10 * DOLLARS, chunks: vec![(5, 20 * DOLLARS)] };
+			assert_eq!(unbonding(stash), Some(schedule.clone()));
+			assert_eq!(unbonding(controller), Some(schedule));
+			assert_eq!(unbonding(AccountId::from([3u8; 32])), None);
+		});
+	}
+}
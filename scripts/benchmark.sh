@@ -0,0 +1,36 @@
+#!/usr/bin/env bash
+
+# Regenerates the weights of the TrustBase pallets and of the chain extension from their
+# benchmarks. Run it from the repository root on reference hardware, optionally naming the
+# pallets to regenerate, e.g. `./scripts/benchmark.sh pallet_attestation`.
+
+set -e
+
+declare -A OUTPUT=(
+	[pallet_attestation]=pallets/attestation/src/weights.rs
+	[pallet_authorized_upgrade]=pallets/authorized-upgrade/src/weights.rs
+	[pallet_fee_split]=pallets/fee-split/src/weights.rs
+	[pallet_nomination_pools]=pallets/nomination-pools/src/weights.rs
+	[pallet_parameters]=pallets/parameters/src/weights.rs
+	[pallet_safe_mode]=pallets/safe-mode/src/weights.rs
+	[pallet_spending_limit]=pallets/spending-limit/src/weights.rs
+	[trustbase_chain_extension]=runtime/src/chain_extension/weights.rs
+)
+
+cargo build --release --features node-cli/runtime-benchmarks
+
+for pallet in ${@:-${!OUTPUT[@]}}; do
+	echo "*** Benchmarking $pallet"
+	./target/release/trustbase benchmark \
+		--chain dev \
+		--execution wasm \
+		--wasm-execution compiled \
+		--pallet "$pallet" \
+		--extrinsic '*' \
+		--steps 50 \
+		--repeat 20 \
+		--heap-pages 4096 \
+		--header ./file_header.txt \
+		--template ./.maintain/frame-weight-template.hbs \
+		--output "${OUTPUT[$pallet]}"
+done
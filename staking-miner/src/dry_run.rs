@@ -0,0 +1,65 @@
+// Copyright 2019 TrustBase Network
+// This file is part of TrustBase library.
+//
+// The TrustBase library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Lesser General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// The TrustBase library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU Lesser General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Mine a solution at a single block and check it against the node without submitting it.
+
+use crate::{
+	create_election_ext, mine_solution, rpc_helpers::{param, rpc, rpc_decode},
+	signer::{create_transaction, Signer}, submit_call, DryRunConfig, Error, LOG_TARGET,
+};
+use codec::Encode;
+use jsonrpsee_ws_client::WsClient;
+use node_primitives::Hash;
+use node_runtime::ElectionProviderMultiPhase;
+use sp_runtime::ApplyExtrinsicResult;
+
+pub(crate) async fn run(
+	client: &WsClient,
+	uri: &str,
+	signer: &Signer,
+	config: DryRunConfig,
+) -> Result<(), Error> {
+	let at = match config.at {
+		Some(at) => at,
+		None => rpc::<Hash>(client, "chain_getBlockHash", vec![]).await?,
+	};
+
+	let mut ext = create_election_ext(uri.to_string(), Some(at), config.force_snapshot).await?;
+	let (call, score, witness) = ext.execute_with(|| {
+		if config.force_snapshot && ElectionProviderMultiPhase::snapshot().is_none() {
+			log::info!(target: LOG_TARGET, "no snapshot at {:?}, creating one", at);
+			ElectionProviderMultiPhase::create_snapshot()?;
+		}
+
+		let (solution, witness) = mine_solution(&config.solver)?;
+		let score = solution.score;
+		Ok::<_, Error>((submit_call(solution), score, witness))
+	})?;
+
+	let xt = create_transaction(client, signer, call).await?;
+	let outcome: ApplyExtrinsicResult = rpc_decode(
+		client,
+		"system_dryRun",
+		vec![param(sp_core::Bytes(xt.encode())), param(at)],
+	).await?;
+
+	println!("mined at {:?} over {:?}", at, witness);
+	println!("score: {:?}", score);
+	println!("encoded length: {}", xt.encoded_size());
+	println!("dry-run outcome: {:?}", outcome);
+
+	Ok(())
+}
@@ -0,0 +1,224 @@
+// Copyright 2019 TrustBase Network
+// This file is part of TrustBase library.
+//
+// The TrustBase library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Lesser General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// The TrustBase library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU Lesser General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! A standalone miner for the signed phase of the multi-phase election.
+//!
+//! It connects to a node over a websocket RPC, downloads the election snapshot into a set of
+//! remote externalities, mines an `NposCompactSolution16` against it with the chosen algorithm
+//! and submits the result as a signed `ElectionProviderMultiPhase::submit` transaction.
+
+mod dry_run;
+mod monitor;
+mod rpc_helpers;
+mod signer;
+
+use jsonrpsee_ws_client::WsClientBuilder;
+use node_primitives::Hash;
+use node_runtime::{Block, ElectionProviderMultiPhase, Runtime};
+use pallet_election_provider_multi_phase as EPM;
+use remote_externalities::{Builder, Mode, OnlineConfig};
+use sp_core::hashing::twox_128;
+use sp_npos_elections::{phragmms, seq_phragmen};
+use structopt::StructOpt;
+
+pub(crate) const LOG_TARGET: &str = "staking-miner";
+
+/// The websocket endpoint of a locally running node.
+const DEFAULT_URI: &str = "ws://127.0.0.1:9944";
+
+/// The solution type of the runtime, and what it is submitted with.
+pub(crate) type Solution = EPM::RawSolution<EPM::CompactOf<Runtime>>;
+
+/// Errors that can stop the miner, or make it skip a block.
+#[derive(Debug, derive_more::From)]
+pub(crate) enum Error {
+	/// Failed to talk to the node.
+	Rpc(jsonrpsee_ws_client::Error),
+	/// Failed to decode an RPC response.
+	Codec(codec::Error),
+	/// The signer's secret URI could not be parsed.
+	Crypto(sp_core::crypto::SecretStringError),
+	/// Failed to build the remote externalities.
+	RemoteExternalities(&'static str),
+	/// Failed to create a snapshot from the staking state.
+	Election(EPM::ElectionError),
+	/// The election algorithm failed.
+	Solver(sp_npos_elections::Error),
+	/// The mined solution could not be trimmed into a submittable one.
+	Miner(EPM::unsigned::MinerError),
+	/// The mined solution would not pass the on-chain feasibility check.
+	Feasibility(EPM::FeasibilityError),
+	/// There is no election snapshot at the given block.
+	#[from(ignore)]
+	SnapshotUnavailable,
+	/// The election is not in its signed phase.
+	#[from(ignore)]
+	IncorrectPhase,
+	/// The signer already has a solution queued in this round.
+	#[from(ignore)]
+	AlreadySubmitted,
+}
+
+/// The election algorithm to mine with.
+#[derive(Debug, Clone, StructOpt)]
+pub(crate) enum Solver {
+	/// Sequential phragmen, the algorithm of the validators' offchain workers.
+	SeqPhragmen {
+		/// The number of balancing iterations run on the result.
+		#[structopt(long, default_value = "10")]
+		iterations: usize,
+	},
+	/// PhragMMS, which is slower but usually gives a better score.
+	#[structopt(name = "phragmms")]
+	PhragMMS {
+		/// The number of balancing iterations run on the result.
+		#[structopt(long, default_value = "10")]
+		iterations: usize,
+	},
+}
+
+#[derive(Debug, Clone, StructOpt)]
+pub(crate) struct MonitorConfig {
+	/// Follow finalized heads instead of best heads.
+	#[structopt(long)]
+	pub finalized: bool,
+
+	#[structopt(subcommand)]
+	pub solver: Solver,
+}
+
+#[derive(Debug, Clone, StructOpt)]
+pub(crate) struct DryRunConfig {
+	/// The block to mine at. Defaults to the best block.
+	#[structopt(long)]
+	pub at: Option<Hash>,
+
+	/// Create a snapshot from the staking state when the chain is not in an election.
+	#[structopt(long)]
+	pub force_snapshot: bool,
+
+	#[structopt(subcommand)]
+	pub solver: Solver,
+}
+
+#[derive(Debug, Clone, StructOpt)]
+enum Command {
+	/// Follow the chain and submit a solution in every signed phase.
+	Monitor(MonitorConfig),
+	/// Mine a solution at a single block and dry-run its submission without submitting it.
+	DryRun(DryRunConfig),
+}
+
+#[derive(Debug, Clone, StructOpt)]
+#[structopt(name = "staking-miner")]
+struct Opt {
+	/// The websocket endpoint of the node to connect to.
+	#[structopt(long, default_value = DEFAULT_URI)]
+	uri: String,
+
+	/// The secret URI of the account that signs and pays for submissions, e.g. `//Alice`.
+	#[structopt(long)]
+	seed: String,
+
+	#[structopt(subcommand)]
+	command: Command,
+}
+
+/// Download the election state at `at` into a set of externalities.
+///
+/// The staking state is only needed to create a snapshot, which is otherwise already there.
+pub(crate) async fn create_election_ext(
+	uri: String,
+	at: Option<Hash>,
+	with_staking: bool,
+) -> Result<sp_io::TestExternalities, Error> {
+	let mut modules = vec!["ElectionProviderMultiPhase".to_string()];
+	if with_staking {
+		modules.push("Staking".to_string());
+	}
+	let mut builder = Builder::<Block>::new().mode(Mode::Online(OnlineConfig {
+		transport: uri.into(),
+		at,
+		modules,
+		..Default::default()
+	}));
+	if with_staking {
+		// voter weights are scaled by the total issuance.
+		builder = builder.inject_hashed_key(&[twox_128(b"Balances"), twox_128(b"TotalIssuance")].concat());
+	}
+	builder.build().await.map_err(Into::into)
+}
+
+/// Mine a solution over the snapshot in the current externalities.
+///
+/// The result is trimmed to the runtime's weight and length limits and checked the same way the
+/// runtime checks signed submissions, so a returned solution is known to be feasible.
+pub(crate) fn mine_solution(solver: &Solver) -> Result<(Solution, EPM::SolutionOrSnapshotSize), Error> {
+	let EPM::RoundSnapshot { voters, targets } =
+		ElectionProviderMultiPhase::snapshot().ok_or(Error::SnapshotUnavailable)?;
+	let desired_targets =
+		ElectionProviderMultiPhase::desired_targets().ok_or(Error::SnapshotUnavailable)? as usize;
+
+	let election_result = match *solver {
+		Solver::SeqPhragmen { iterations } => seq_phragmen::<_, EPM::CompactAccuracyOf<Runtime>>(
+			desired_targets,
+			targets,
+			voters,
+			Some((iterations, 0)),
+		)?,
+		Solver::PhragMMS { iterations } => phragmms::<_, EPM::CompactAccuracyOf<Runtime>>(
+			desired_targets,
+			targets,
+			voters,
+			Some((iterations, 0)),
+		)?,
+	};
+	let (solution, witness) = ElectionProviderMultiPhase::prepare_election_result(election_result)?;
+	ElectionProviderMultiPhase::feasibility_check(solution.clone(), EPM::ElectionCompute::Signed)?;
+
+	Ok((solution, witness))
+}
+
+/// The call that submits `solution` in the signed phase of the current externalities.
+pub(crate) fn submit_call(solution: Solution) -> node_runtime::Call {
+	let num_signed_submissions =
+		EPM::SignedSubmissionIndices::<Runtime>::decode_len().unwrap_or_default() as u32;
+	node_runtime::Call::ElectionProviderMultiPhase(EPM::Call::submit(solution, num_signed_submissions))
+}
+
+#[tokio::main]
+async fn main() {
+	env_logger::Builder::from_default_env()
+		.filter_module(LOG_TARGET, log::LevelFilter::Info)
+		.init();
+
+	let Opt { uri, seed, command } = Opt::from_args();
+	if let Err(e) = run(uri, seed, command).await {
+		log::error!(target: LOG_TARGET, "{:?}", e);
+		std::process::exit(1);
+	}
+}
+
+async fn run(uri: String, seed: String, command: Command) -> Result<(), Error> {
+	let client = WsClientBuilder::default().build(&uri).await?;
+	let signer = signer::Signer::from_uri(&seed)?;
+	log::info!(target: LOG_TARGET, "connected to {} as {}", uri, signer.account);
+
+	match command {
+		Command::Monitor(config) => monitor::run(&client, &uri, &signer, config).await,
+		Command::DryRun(config) => dry_run::run(&client, &uri, &signer, config).await,
+	}
+}
@@ -0,0 +1,112 @@
+// Copyright 2019 TrustBase Network
+// This file is part of TrustBase library.
+//
+// The TrustBase library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Lesser General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// The TrustBase library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU Lesser General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Follow the chain and submit a solution once per signed phase.
+
+use crate::{
+	create_election_ext, mine_solution, rpc_helpers::{param, rpc}, signer::{create_transaction, Signer},
+	submit_call, Error, MonitorConfig, Solver, LOG_TARGET,
+};
+use codec::Encode;
+use jsonrpsee_ws_client::{traits::SubscriptionClient, v2::params::JsonRpcParams, Subscription, WsClient};
+use node_primitives::{AccountId, Hash};
+use node_runtime::{ElectionProviderMultiPhase, Header, Runtime};
+use pallet_election_provider_multi_phase as EPM;
+use sp_runtime::traits::Header as HeaderT;
+
+pub(crate) async fn run(
+	client: &WsClient,
+	uri: &str,
+	signer: &Signer,
+	config: MonitorConfig,
+) -> Result<(), Error> {
+	let (subscribe, unsubscribe) = if config.finalized {
+		("chain_subscribeFinalizedHeads", "chain_unsubscribeFinalizedHeads")
+	} else {
+		("chain_subscribeNewHeads", "chain_unsubscribeNewHeads")
+	};
+	let mut heads: Subscription<Header> =
+		client.subscribe(subscribe, JsonRpcParams::NoParams, unsubscribe).await?;
+
+	// a submission is only visible once it is in a block, so remember the rounds already submitted
+	// to instead of queueing several solutions, and paying several deposits, per round.
+	let mut submitted_round = None;
+	while let Some(head) = heads.next().await? {
+		let hash = head.hash();
+		log::trace!(target: LOG_TARGET, "new head #{} ({:?})", head.number(), hash);
+
+		match submit_at(client, uri, signer, &config.solver, hash, submitted_round).await {
+			Ok(round) => submitted_round = Some(round),
+			Err(Error::IncorrectPhase) | Err(Error::AlreadySubmitted) => {},
+			Err(e) => log::warn!(target: LOG_TARGET, "failed to submit at {:?}: {:?}", hash, e),
+		}
+	}
+
+	Ok(())
+}
+
+/// Mine and submit a solution at `hash`, returning the round it was submitted to.
+async fn submit_at(
+	client: &WsClient,
+	uri: &str,
+	signer: &Signer,
+	solver: &Solver,
+	hash: Hash,
+	submitted_round: Option<u32>,
+) -> Result<u32, Error> {
+	let mut ext = create_election_ext(uri.to_string(), Some(hash), false).await?;
+	let (call, round, score) = ext.execute_with(|| {
+		let round = ElectionProviderMultiPhase::round();
+		if submitted_round == Some(round) {
+			return Err(Error::AlreadySubmitted);
+		}
+		ensure_signed_phase()?;
+		ensure_no_previous_solution(&signer.account)?;
+
+		let (solution, _) = mine_solution(solver)?;
+		let score = solution.score;
+		Ok((submit_call(solution), round, score))
+	})?;
+
+	let xt = create_transaction(client, signer, call).await?;
+	let tx_hash: Hash =
+		rpc(client, "author_submitExtrinsic", vec![param(sp_core::Bytes(xt.encode()))]).await?;
+	log::info!(
+		target: LOG_TARGET,
+		"submitted solution with score {:?} to round {} in {:?}",
+		score,
+		round,
+		tx_hash,
+	);
+
+	Ok(round)
+}
+
+fn ensure_signed_phase() -> Result<(), Error> {
+	if ElectionProviderMultiPhase::current_phase().is_signed() {
+		Ok(())
+	} else {
+		Err(Error::IncorrectPhase)
+	}
+}
+
+fn ensure_no_previous_solution(who: &AccountId) -> Result<(), Error> {
+	if EPM::SignedSubmissionsMap::<Runtime>::iter_values().any(|submission| submission.who == *who) {
+		Err(Error::AlreadySubmitted)
+	} else {
+		Ok(())
+	}
+}
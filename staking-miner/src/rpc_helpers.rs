@@ -0,0 +1,50 @@
+// Copyright 2019 TrustBase Network
+// This file is part of TrustBase library.
+//
+// The TrustBase library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Lesser General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// The TrustBase library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU Lesser General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Thin wrappers around the node's JSON-RPC methods.
+
+use crate::Error;
+use jsonrpsee_ws_client::{traits::Client, v2::params::JsonRpcParams, WsClient};
+use serde::de::DeserializeOwned;
+
+/// Call `method` with positional `params`.
+pub(crate) async fn rpc<R: DeserializeOwned>(
+	client: &WsClient,
+	method: &str,
+	params: Vec<serde_json::Value>,
+) -> Result<R, Error> {
+	let params = if params.is_empty() {
+		JsonRpcParams::NoParams
+	} else {
+		JsonRpcParams::Array(params)
+	};
+	client.request::<R>(method, params).await.map_err(Into::into)
+}
+
+/// Call `method` with positional `params` and decode the SCALE encoded bytes it returns.
+pub(crate) async fn rpc_decode<R: codec::Decode>(
+	client: &WsClient,
+	method: &str,
+	params: Vec<serde_json::Value>,
+) -> Result<R, Error> {
+	let bytes = rpc::<sp_core::Bytes>(client, method, params).await?;
+	R::decode(&mut &*bytes.0).map_err(Into::into)
+}
+
+/// Serialize an RPC parameter.
+pub(crate) fn param<T: serde::Serialize>(value: T) -> serde_json::Value {
+	serde_json::to_value(value).expect("RPC parameters always serialize to json; qed")
+}
@@ -0,0 +1,84 @@
+// Copyright 2019 TrustBase Network
+// This file is part of TrustBase library.
+//
+// The TrustBase library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Lesser General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// The TrustBase library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU Lesser General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! The account that signs submissions, and how its transactions are built.
+
+use crate::{rpc_helpers::{param, rpc}, Error};
+use jsonrpsee_ws_client::WsClient;
+use node_primitives::{AccountId, Hash, Index};
+use node_runtime::{Address, Call, SignedExtra, SignedPayload, UncheckedExtrinsic};
+use sp_core::{sr25519, Pair};
+use sp_runtime::generic::Era;
+use sp_version::RuntimeVersion;
+
+/// A signing account, derived from a secret URI.
+pub(crate) struct Signer {
+	pub account: AccountId,
+	pub pair: sr25519::Pair,
+}
+
+impl Signer {
+	pub fn from_uri(uri: &str) -> Result<Self, Error> {
+		let pair = sr25519::Pair::from_string(uri, None)?;
+		Ok(Self { account: pair.public().into(), pair })
+	}
+}
+
+/// Sign `call` by `signer`, with its next nonce from the node.
+///
+/// The extension tuple must be kept in line with [`node_runtime::SignedExtra`]. The transaction is
+/// immortal, and versioned against the runtime the node currently runs.
+pub(crate) async fn create_transaction(
+	client: &WsClient,
+	signer: &Signer,
+	call: Call,
+) -> Result<UncheckedExtrinsic, Error> {
+	let nonce: Index = rpc(client, "system_accountNextIndex", vec![param(&signer.account)]).await?;
+	let version: RuntimeVersion = rpc(client, "state_getRuntimeVersion", vec![]).await?;
+	let genesis_hash: Hash = rpc(client, "chain_getBlockHash", vec![param(0u32)]).await?;
+
+	let extra: SignedExtra = (
+		frame_system::CheckSpecVersion::new(),
+		frame_system::CheckTxVersion::new(),
+		frame_system::CheckGenesis::new(),
+		frame_system::CheckEra::from(Era::Immortal),
+		frame_system::CheckNonce::from(nonce),
+		frame_system::CheckWeight::new(),
+		pallet_asset_tx_payment::ChargeAssetTxPayment::from(0, None),
+	);
+	let raw_payload = SignedPayload::from_raw(
+		call,
+		extra,
+		(
+			version.spec_version,
+			version.transaction_version,
+			genesis_hash,
+			genesis_hash,
+			(),
+			(),
+			(),
+		),
+	);
+	let signature = raw_payload.using_encoded(|payload| signer.pair.sign(payload));
+	let (call, extra, _) = raw_payload.deconstruct();
+
+	Ok(UncheckedExtrinsic::new_signed(
+		call,
+		Address::Id(signer.account.clone()),
+		signature.into(),
+		extra,
+	))
+}
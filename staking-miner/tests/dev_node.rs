@@ -0,0 +1,77 @@
+// Copyright 2019 TrustBase Network
+// This file is part of TrustBase library.
+//
+// The TrustBase library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Lesser General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// The TrustBase library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU Lesser General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Runs the miner against a dev node started in this process.
+
+use assert_cmd::cargo::cargo_bin;
+use sc_cli::SubstrateCli;
+use std::{net::TcpStream, process::Command, sync::Once, thread, time::Duration};
+use structopt::StructOpt;
+
+const WS_PORT: u16 = 45789;
+
+static START_NODE: Once = Once::new();
+
+/// Start a dev node on `WS_PORT` once, and wait for its RPC to come up.
+fn dev_node() -> String {
+	START_NODE.call_once(|| {
+		thread::spawn(|| {
+			let port = WS_PORT.to_string();
+			let cli = node_cli::Cli::from_iter(&[
+				"trustbase", "--dev", "--tmp", "--ws-port", &port, "--no-prometheus", "--no-telemetry",
+			]);
+			let runner = cli.create_runner(&cli.run).expect("dev node is configured");
+			runner.run_node_until_exit(|config| async move {
+				node_cli::service::new_full(config, None).map_err(sc_cli::Error::Service)
+			}).expect("dev node runs until the tests exit");
+		});
+	});
+
+	for _ in 0..60 {
+		if TcpStream::connect(("127.0.0.1", WS_PORT)).is_ok() {
+			return format!("ws://127.0.0.1:{}", WS_PORT);
+		}
+		thread::sleep(Duration::from_secs(1));
+	}
+	panic!("dev node did not open its RPC port");
+}
+
+fn dry_run(solver: &[&str]) -> String {
+	let uri = dev_node();
+	let output = Command::new(cargo_bin("staking-miner"))
+		.args(&["--uri", &uri, "--seed", "//Alice", "dry-run", "--force-snapshot"])
+		.args(solver)
+		.output()
+		.expect("staking-miner runs");
+	assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+	String::from_utf8(output.stdout).unwrap()
+}
+
+#[test]
+fn mines_with_seq_phragmen() {
+	let stdout = dry_run(&["seq-phragmen", "--iterations", "2"]);
+	assert!(stdout.contains("score: ["));
+	// the mined solution is accepted by the node.
+	assert!(stdout.contains("dry-run outcome: Ok(Ok(()))"), "{}", stdout);
+}
+
+#[test]
+fn mines_with_phragmms() {
+	let stdout = dry_run(&["phragmms"]);
+	assert!(stdout.contains("score: ["));
+	// the mined solution is accepted by the node.
+	assert!(stdout.contains("dry-run outcome: Ok(Ok(()))"), "{}", stdout);
+}
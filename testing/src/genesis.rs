@@ -114,7 +114,9 @@ pub fn config_endowed(
 			max_members: 999,
 		},
 		vesting: Default::default(),
+		assets: Default::default(),
 		gilt: Default::default(),
 		transaction_storage: Default::default(),
+		migrations: Default::default(),
 	}
 }